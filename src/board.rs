@@ -1,19 +1,24 @@
 use crate::game;
 use crate::parser;
-use crate::parser::{MoveTypes, ParsedMove};
+use crate::parser::MAX_BOARD_SIZE;
+use crate::parser::{CheckOrCheckMate, MoveTypes, ParseError, ParsedMove};
 use crate::piece_types::{PieceColor, QuickPiece};
 use crate::pieces::bishop::Bishop;
+use crate::pieces::fairy::FairyPieceType;
 use crate::pieces::king::King;
 use crate::pieces::knight::Knight;
 use crate::pieces::pawn::Pawn;
 use crate::pieces::queen::Queen;
 use crate::pieces::rook::Rook;
 use crate::pieces::{AnyPiece, PieceMove};
+use crate::pocket::Pockets;
+use crate::variant::Variant;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct MoveError {
@@ -40,7 +45,7 @@ impl Error for MoveError {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct PlayedMove {
     piece_string: String,
     starting_position: (usize, usize),
@@ -66,11 +71,25 @@ impl PlayedMove {
         }
     }
 
+    pub fn get_starting_pos(&self) -> (usize, usize) {
+        self.starting_position
+    }
+
     pub fn get_ending_pos(&self) -> (usize, usize) {
         self.end_position
     }
 }
 
+// What a rank of a FEN is made of once its numbers are read
+#[derive(Debug, PartialEq)]
+enum FenSquare {
+    Empty,
+    Piece(char),
+    /// The ~ after a promoted piece in Crazyhouse
+    Promoted,
+}
+
+#[derive(Clone)]
 pub struct Board {
     pub position_board: Vec<Vec<QuickPiece>>,
     pub live_white_pieces: Vec<AnyPiece>,
@@ -80,6 +99,20 @@ pub struct Board {
     pub last_move_color: PieceColor, // @TODO with last played move being a thing this is redundant
     pub played_moves: Vec<PlayedMove>, // remove this to be the last played move
     pub board_state_hashes: HashMap<u64, usize>,
+    /// The rules the board is played with
+    pub variant: Variant,
+    /// The captured pieces each side can drop.  Only Crazyhouse has them
+    pub pockets: Option<Pockets>,
+    /// Squares with a piece that was a pawn.  In Crazyhouse it goes back to the pocket as a pawn
+    pub promoted_squares: Vec<(usize, usize)>,
+    /// How many checks White and Black have given.  Only counted in Three-check
+    pub check_counts: (u32, u32),
+    /// Plies since the last capture or pawn move, for the fifty move rule
+    pub halfmove_clock: u32,
+    /// The FEN move number, which goes up after each Black move
+    pub fullmove_number: u32,
+    /// The kinds of fairy piece the board knows the letters of
+    pub fairy_pieces: Vec<Arc<FairyPieceType>>,
 }
 
 impl Default for Board {
@@ -99,11 +132,567 @@ impl Board {
             last_move_color: PieceColor::BLACK,
             played_moves: Vec::new(),
             board_state_hashes: HashMap::new(),
+            variant: Variant::Standard,
+            pockets: None,
+            promoted_squares: Vec::new(),
+            check_counts: (0, 0),
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            fairy_pieces: Vec::new(),
+        };
+        board.add_state_hash();
+        board
+    }
+
+    /// A board of the given size with nothing on it.  Each side can be 1 to 16 squares long
+    pub fn empty(width: usize, height: usize) -> Result<Board, ParseError> {
+        let size_range = 1..=MAX_BOARD_SIZE;
+        if !size_range.contains(&width) || !size_range.contains(&height) {
+            return Err(ParseError::new(&format!(
+                "A board can't be {}x{}.  Both sides have to be 1 to {} squares long",
+                width, height, MAX_BOARD_SIZE
+            )));
+        }
+        Ok(Board {
+            position_board: vec![vec![QuickPiece::EMPTY; height]; width],
+            live_white_pieces: Vec::new(),
+            live_black_pieces: Vec::new(),
+            white_king_position: Board::default_white_king_pos(),
+            black_king_position: Board::default_black_king_pos(),
+            last_move_color: PieceColor::BLACK,
+            played_moves: Vec::new(),
+            board_state_hashes: HashMap::new(),
+            variant: Variant::Standard,
+            pockets: None,
+            promoted_squares: Vec::new(),
+            check_counts: (0, 0),
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            fairy_pieces: Vec::new(),
+        })
+    }
+
+    /// How many files the board has
+    pub fn width(&self) -> usize {
+        self.position_board.len()
+    }
+
+    /// How many ranks the board has
+    pub fn height(&self) -> usize {
+        self.position_board.first().map_or(0, Vec::len)
+    }
+
+    /// The starting position for the variant
+    pub fn new_variant(variant: Variant) -> Board {
+        let mut board = match variant.starting_fen() {
+            Some(fen) => Board::from_fen(fen).unwrap(),
+            None => Board::new(),
         };
+        board.set_variant(variant);
+        board.board_state_hashes.clear();
         board.add_state_hash();
         board
     }
 
+    /// Changes the rules the board is played with.  Crazyhouse gets empty pockets if it has none
+    /// and the other variants lose theirs.  Antichess has no castling
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+        match variant {
+            Variant::Crazyhouse => {
+                self.pockets.get_or_insert_with(Pockets::new);
+            }
+            _ => self.pockets = None,
+        }
+        if variant == Variant::Antichess {
+            for piece in self
+                .live_white_pieces
+                .iter_mut()
+                .chain(self.live_black_pieces.iter_mut())
+            {
+                if let AnyPiece::King(king) = piece {
+                    king.set_has_moved(true);
+                }
+            }
+        }
+    }
+
+    /// The number of checks the color has given
+    pub fn checks_given(&self, color: &PieceColor) -> u32 {
+        match color {
+            PieceColor::WHITE => self.check_counts.0,
+            PieceColor::BLACK => self.check_counts.1,
+        }
+    }
+
+    /// Sets up a board from a FEN string.  The halfmove clock and move number can be left off, like
+    /// in an EPD, and are then 0 and 1.  A Crazyhouse FEN has the pockets in brackets after the
+    /// pieces and a ~ after each promoted piece.  A Three-check FEN has the checks each side has
+    /// given as +N+M after the other fields
+    pub fn from_fen(fen: &str) -> Result<Board, ParseError> {
+        Board::from_fen_with_pieces(fen, &[])
+    }
+
+    /// Sets up a board from a FEN that can use the letters of the fairy pieces given
+    pub fn from_fen_with_pieces(
+        fen: &str,
+        fairy_pieces: &[FairyPieceType],
+    ) -> Result<Board, ParseError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() < 4 {
+            return Err(ParseError::new(&format!(
+                "A FEN needs at least 4 fields but {:?} has {}",
+                fen,
+                fields.len()
+            )));
+        }
+
+        let mut placement = fields[0];
+        let mut pockets = None;
+        if let Some(pocket_start) = placement.find('[') {
+            let pocket_string =
+                placement[pocket_start + 1..]
+                    .strip_suffix(']')
+                    .ok_or_else(|| {
+                        ParseError::new(&format!("The pockets in {:?} aren't closed", fen))
+                    })?;
+            pockets = Some(Pockets::from_fen(pocket_string)?);
+            placement = &placement[..pocket_start];
+        }
+
+        // The board is as wide as the first rank and as tall as the number of ranks
+        let ranks: Vec<Vec<FenSquare>> = placement.split('/').map(Board::read_fen_rank).collect();
+        let width = ranks[0]
+            .iter()
+            .filter(|square| **square != FenSquare::Promoted)
+            .count();
+        let height = ranks.len();
+        let mut board = Board::empty(width, height)?;
+        if pockets.is_some() {
+            board.pockets = pockets;
+            board.variant = Variant::Crazyhouse;
+        }
+        for piece_type in fairy_pieces {
+            board.register_piece(piece_type.clone())?;
+        }
+
+        for (rank_index, rank) in ranks.iter().enumerate() {
+            let y_coord = height - 1 - rank_index;
+            let mut x_coord = 0;
+            for square in rank {
+                let piece_char = match square {
+                    FenSquare::Empty => {
+                        x_coord += 1;
+                        continue;
+                    }
+                    FenSquare::Promoted if x_coord > 0 => {
+                        board.promoted_squares.push((x_coord - 1, y_coord));
+                        continue;
+                    }
+                    FenSquare::Promoted => continue,
+                    FenSquare::Piece(piece_char) => *piece_char,
+                };
+                if x_coord >= width {
+                    return Err(ParseError::new(&format!(
+                        "Rank {} of the FEN is too long",
+                        y_coord + 1
+                    )));
+                }
+                let piece_color = if piece_char.is_ascii_uppercase() {
+                    PieceColor::WHITE
+                } else {
+                    PieceColor::BLACK
+                };
+                let piece = match piece_char.to_ascii_uppercase() {
+                    'K' => AnyPiece::King(King::new(x_coord, y_coord, piece_color)),
+                    'Q' => AnyPiece::Queen(Queen::new(x_coord, y_coord, piece_color)),
+                    'R' => {
+                        let mut rook = Rook::new(x_coord, y_coord, piece_color);
+                        // Castling rights are given back below
+                        rook.set_has_moved(true);
+                        AnyPiece::Rook(rook)
+                    }
+                    'B' => AnyPiece::Bishop(Bishop::new(x_coord, y_coord, piece_color)),
+                    'N' => AnyPiece::Knight(Knight::new(x_coord, y_coord, piece_color)),
+                    'P' => {
+                        // Pawns can't stand on the back ranks, apart from the Horde's white pawns
+                        // on the first rank which can move two squares as well
+                        let starting_y = match piece_color {
+                            PieceColor::WHITE if y_coord + 1 < height => y_coord.min(1),
+                            PieceColor::BLACK if y_coord > 0 && y_coord + 1 < height => height - 2,
+                            _ => {
+                                return Err(ParseError::new(&format!(
+                                    "There can't be a pawn on rank {}",
+                                    y_coord + 1
+                                )))
+                            }
+                        };
+                        AnyPiece::Pawn(Pawn::with_starting_y(
+                            x_coord,
+                            y_coord,
+                            piece_color,
+                            starting_y,
+                        ))
+                    }
+                    fairy_char => board
+                        .piece_from_character(
+                            &fairy_char.to_string(),
+                            x_coord,
+                            y_coord,
+                            piece_color,
+                        )
+                        .ok_or_else(|| {
+                            ParseError::new(&format!("Unknown piece {:?} in the FEN", piece_char))
+                        })?,
+                };
+                board.place_piece(piece, piece_color);
+                x_coord += 1;
+            }
+            if x_coord != width {
+                return Err(ParseError::new(&format!(
+                    "Rank {} of the FEN has {} squares but the board is {} wide",
+                    y_coord + 1,
+                    x_coord,
+                    width
+                )));
+            }
+        }
+
+        board.last_move_color = match fields[1] {
+            "w" => PieceColor::BLACK,
+            "b" => PieceColor::WHITE,
+            _ => {
+                return Err(ParseError::new(&format!(
+                    "Unknown side to move {:?}",
+                    fields[1]
+                )))
+            }
+        };
+
+        // Kings are marked as moved unless they are on their home square with a castling right
+        for piece_color in [PieceColor::WHITE, PieceColor::BLACK] {
+            let (king_x, king_y) = match piece_color {
+                PieceColor::WHITE => board.white_king_position,
+                PieceColor::BLACK => board.black_king_position,
+            };
+            if let Some(AnyPiece::King(king)) = board.find_piece_color(king_x, king_y, &piece_color)
+            {
+                king.set_has_moved(true);
+            }
+        }
+        if fields[2] != "-" {
+            for castle_char in fields[2].chars() {
+                let (piece_color, rook_x) = match castle_char {
+                    'K' => (PieceColor::WHITE, width - 1),
+                    'Q' => (PieceColor::WHITE, 0),
+                    'k' => (PieceColor::BLACK, width - 1),
+                    'q' => (PieceColor::BLACK, 0),
+                    _ => {
+                        return Err(ParseError::new(&format!(
+                            "Unknown castling right {:?}",
+                            castle_char
+                        )))
+                    }
+                };
+                let (home_y, king_x) = match piece_color {
+                    PieceColor::WHITE => (0, board.white_king_position.0),
+                    PieceColor::BLACK => (height - 1, board.black_king_position.0),
+                };
+                if let Some(AnyPiece::Rook(rook)) =
+                    board.find_piece_color(rook_x, home_y, &piece_color)
+                {
+                    rook.set_has_moved(false);
+                }
+                // The king can start on any file of its first rank, like f1 in Capablanca chess
+                if let Some(AnyPiece::King(king)) =
+                    board.find_piece_color(king_x, home_y, &piece_color)
+                {
+                    king.set_has_moved(false);
+                }
+            }
+        }
+
+        // En passant is worked out from the last move so the double pawn step is added as one.
+        // The square is behind the pawn that just moved, on the third rank from its side
+        if fields[3] != "-" {
+            let (ep_x, start_y, end_y) =
+                match (parser::parse_square(fields[3]), board.last_move_color) {
+                    (Some((ep_x, 2)), PieceColor::WHITE) if ep_x < width => (ep_x, 1, 3),
+                    (Some((ep_x, ep_y)), PieceColor::BLACK)
+                        if ep_x < width && ep_y + 3 == height =>
+                    {
+                        (ep_x, height - 2, height - 4)
+                    }
+                    _ => {
+                        return Err(ParseError::new(&format!(
+                            "Invalid en passant square {:?} for the side to move",
+                            fields[3]
+                        )))
+                    }
+                };
+            board.played_moves.push(PlayedMove::new(
+                String::from("P"),
+                (ep_x, start_y),
+                (ep_x, end_y),
+                board.last_move_color,
+                None,
+            ));
+        }
+
+        // The move counters can be left off, Three-check puts the checks given after them
+        let counters: Vec<&str> = fields[4..]
+            .iter()
+            .filter(|field| !field.starts_with('+'))
+            .copied()
+            .collect();
+        match counters.as_slice() {
+            [] => (),
+            [halfmove_clock] | [halfmove_clock, _] => {
+                board.halfmove_clock = halfmove_clock.parse().map_err(|_| {
+                    ParseError::new(&format!("Invalid halfmove clock {:?}", halfmove_clock))
+                })?;
+            }
+            _ => {
+                return Err(ParseError::new(&format!(
+                    "A FEN can't have {} fields",
+                    fields.len()
+                )))
+            }
+        }
+        if let [_, fullmove_number] = counters.as_slice() {
+            board.fullmove_number = match fullmove_number.parse() {
+                Ok(fullmove_number) if fullmove_number > 0 => fullmove_number,
+                _ => {
+                    return Err(ParseError::new(&format!(
+                        "Invalid move number {:?}",
+                        fullmove_number
+                    )))
+                }
+            };
+        }
+
+        if let Some(check_field) = fields[4..].iter().find(|field| field.starts_with('+')) {
+            let counts: Vec<Option<u32>> = check_field
+                .split('+')
+                .skip(1)
+                .map(|count| count.parse().ok())
+                .collect();
+            board.check_counts = match counts.as_slice() {
+                [Some(white_checks), Some(black_checks)] => (*white_checks, *black_checks),
+                _ => {
+                    return Err(ParseError::new(&format!(
+                        "The checks given {:?} should look like +1+0",
+                        check_field
+                    )))
+                }
+            };
+            board.variant = Variant::ThreeCheck;
+        }
+
+        board.add_state_hash();
+        Ok(board)
+    }
+
+    // Reads one rank of a FEN.  A number can have two digits on boards wider than 9
+    fn read_fen_rank(rank: &str) -> Vec<FenSquare> {
+        let mut squares = Vec::new();
+        let mut characters = rank.chars().peekable();
+        while let Some(character) = characters.next() {
+            if let Some(digit) = character.to_digit(10) {
+                let mut empty_squares = digit as usize;
+                while let Some(next_digit) = characters.peek().and_then(|next| next.to_digit(10)) {
+                    empty_squares = empty_squares * 10 + next_digit as usize;
+                    characters.next();
+                }
+                squares.extend((0..empty_squares).map(|_| FenSquare::Empty));
+            } else if character == '~' {
+                squares.push(FenSquare::Promoted);
+            } else {
+                squares.push(FenSquare::Piece(character));
+            }
+        }
+        squares
+    }
+
+    /// Writes the board as a FEN string
+    pub fn to_fen(&self) -> String {
+        let mut piece_letters = HashMap::new();
+        for piece in &self.live_white_pieces {
+            piece_letters.insert(piece.get_pos(), piece.get_piece_character().to_string());
+        }
+        for piece in &self.live_black_pieces {
+            piece_letters.insert(
+                piece.get_pos(),
+                piece.get_piece_character().to_ascii_lowercase(),
+            );
+        }
+        if self.pockets.is_some() {
+            for square in &self.promoted_squares {
+                if let Some(letter) = piece_letters.get_mut(square) {
+                    letter.push('~');
+                }
+            }
+        }
+
+        let mut ranks = Vec::with_capacity(self.height());
+        for y_coord in (0..self.height()).rev() {
+            let mut rank = String::new();
+            let mut empty_squares = 0;
+            for x_coord in 0..self.width() {
+                match piece_letters.get(&(x_coord, y_coord)) {
+                    Some(letter) => {
+                        if empty_squares > 0 {
+                            rank.push_str(&empty_squares.to_string());
+                            empty_squares = 0;
+                        }
+                        rank.push_str(letter);
+                    }
+                    None => empty_squares += 1,
+                }
+            }
+            if empty_squares > 0 {
+                rank.push_str(&empty_squares.to_string());
+            }
+            ranks.push(rank);
+        }
+        let mut placement = ranks.join("/");
+        if let Some(pockets) = &self.pockets {
+            placement.push_str(&format!("[{}]", pockets.to_fen()));
+        }
+
+        let side_to_move = match self.last_move_color {
+            PieceColor::WHITE => "b",
+            PieceColor::BLACK => "w",
+        };
+
+        let mut castling_rights = String::new();
+        for (piece_color, home_y, rights) in [
+            (PieceColor::WHITE, 0, ["K", "Q"]),
+            (PieceColor::BLACK, self.height() - 1, ["k", "q"]),
+        ] {
+            let pieces = match piece_color {
+                PieceColor::WHITE => &self.live_white_pieces,
+                PieceColor::BLACK => &self.live_black_pieces,
+            };
+            let king_home = pieces.iter().any(|piece| {
+                matches!(piece, AnyPiece::King(king) if king.get_pos().1 == home_y && !king.get_has_moved())
+            });
+            if !king_home {
+                continue;
+            }
+            for (rook_x, right) in [self.width() - 1, 0].iter().zip(rights) {
+                let rook_home = pieces.iter().any(|piece| {
+                    matches!(piece, AnyPiece::Rook(rook) if rook.get_pos() == (*rook_x, home_y) && !rook.get_has_moved())
+                });
+                if rook_home {
+                    castling_rights.push_str(right);
+                }
+            }
+        }
+        if castling_rights.is_empty() {
+            castling_rights.push('-');
+        }
+
+        // A pawn that just moved two squares can be taken on the square it skipped, unless it
+        // came from the first rank in Horde
+        let en_passant = match self.played_moves.last() {
+            Some(last_move)
+                if last_move.piece_string == "P"
+                    && (last_move.starting_position.1 == 1
+                        || last_move.starting_position.1 + 2 == self.height())
+                    && usize::max(last_move.starting_position.1, last_move.end_position.1)
+                        - usize::min(last_move.starting_position.1, last_move.end_position.1)
+                        == 2 =>
+            {
+                format!(
+                    "{}{}",
+                    parser::file_from_coordinate(last_move.end_position.0),
+                    parser::rank_from_coordinate(
+                        (last_move.starting_position.1 + last_move.end_position.1) / 2
+                    )
+                )
+            }
+            _ => String::from("-"),
+        };
+
+        let mut fen = format!(
+            "{} {} {} {} {} {}",
+            placement,
+            side_to_move,
+            castling_rights,
+            en_passant,
+            self.halfmove_clock,
+            self.fullmove_number
+        );
+        if self.variant == Variant::ThreeCheck {
+            fen.push_str(&format!(
+                " +{}+{}",
+                self.check_counts.0, self.check_counts.1
+            ));
+        }
+        fen
+    }
+
+    /// Puts a piece on an empty square, keeping the position board and the live pieces in step
+    fn place_piece(&mut self, piece: AnyPiece, piece_color: PieceColor) {
+        let (x_coord, y_coord) = piece.get_pos();
+        let quick_piece = match piece {
+            AnyPiece::King(_) => {
+                match piece_color {
+                    PieceColor::WHITE => self.white_king_position = (x_coord, y_coord),
+                    PieceColor::BLACK => self.black_king_position = (x_coord, y_coord),
+                };
+                QuickPiece::KING(piece_color)
+            }
+            _ => QuickPiece::PIECE(piece_color),
+        };
+        self.position_board[x_coord][y_coord] = quick_piece;
+        match piece_color {
+            PieceColor::WHITE => self.live_white_pieces.push(piece),
+            PieceColor::BLACK => self.live_black_pieces.push(piece),
+        };
+    }
+
+    /// Lets the board use a kind of fairy piece.  Its letter can't already be taken
+    pub fn register_piece(&mut self, piece_type: FairyPieceType) -> Result<(), ParseError> {
+        if self
+            .fairy_pieces
+            .iter()
+            .any(|registered| registered.letter() == piece_type.letter())
+        {
+            return Err(ParseError::new(&format!(
+                "{} is already used by the {}",
+                piece_type.letter(),
+                piece_type.name()
+            )));
+        }
+        self.fairy_pieces.push(Arc::new(piece_type));
+        Ok(())
+    }
+
+    /// Makes a piece from its letter, looking at the fairy pieces when it isn't a standard one
+    pub fn piece_from_character(
+        &self,
+        piece_symbol: &str,
+        x_coord: usize,
+        y_coord: usize,
+        piece_color: PieceColor,
+    ) -> Option<AnyPiece> {
+        AnyPiece::from_piece_character(piece_symbol, x_coord, y_coord, piece_color).or_else(|| {
+            self.fairy_pieces
+                .iter()
+                .find(|piece_type| piece_type.letter() == piece_symbol)
+                .map(|piece_type| {
+                    AnyPiece::Fairy(FairyPieceType::make_piece(
+                        piece_type,
+                        x_coord,
+                        y_coord,
+                        piece_color,
+                    ))
+                })
+        })
+    }
+
     fn default_live_white_pieces() -> Vec<AnyPiece> {
         vec![
             AnyPiece::Rook(Rook::new(0, 0, PieceColor::WHITE)),
@@ -155,6 +744,7 @@ impl Board {
             AnyPiece::Rook(_) => compare_type == "R",
             AnyPiece::Pawn(_) => compare_type == "P",
             AnyPiece::Bishop(_) => compare_type == "B",
+            AnyPiece::Fairy(fairy) => compare_type == fairy.piece_type().letter(),
         }
     }
 
@@ -183,15 +773,11 @@ impl Board {
                     let delta_x =
                         usize::max(end_x, piece.get_pos().0) - usize::min(end_x, piece.get_pos().0);
                     match &parsed_move.move_type {
-                        MoveTypes::Take => {
-                            if delta_x == 1 {
-                                return_value = Some(piece);
-                            }
+                        MoveTypes::Take if delta_x == 1 => {
+                            return_value = Some(piece);
                         }
-                        MoveTypes::Move => {
-                            if delta_x == 0 {
-                                return_value = Some(piece);
-                            }
+                        MoveTypes::Move if delta_x == 0 => {
+                            return_value = Some(piece);
                         }
                         MoveTypes::Promote(_) => {
                             match self.position_board.get(end_x).unwrap().get(end_y).unwrap() {
@@ -280,12 +866,9 @@ impl Board {
             PieceColor::BLACK => &mut self.live_black_pieces,
         };
 
-        for piece in piece_list {
-            if piece.get_pos() == (x_coord, y_coord) {
-                return Some(piece);
-            }
-        }
-        None
+        piece_list
+            .iter_mut()
+            .find(|piece| piece.get_pos() == (x_coord, y_coord))
     }
 
     pub fn can_any_piece_check_king(
@@ -294,10 +877,18 @@ impl Board {
         y_coord: usize,
         king_color: &PieceColor,
     ) -> bool {
+        // Atomic kings can't take so they never give check, and touching kings are safe
+        let atomic = self.variant == Variant::Atomic;
+        if atomic && self.kings_touch() {
+            return false;
+        }
         let mut found_movement = false;
         match king_color {
             PieceColor::WHITE => {
                 for piece in &self.live_black_pieces {
+                    if atomic && matches!(piece, AnyPiece::King(_)) {
+                        continue;
+                    }
                     if piece.can_move(x_coord, y_coord, &self.position_board) {
                         found_movement = true;
                     }
@@ -305,6 +896,9 @@ impl Board {
             }
             PieceColor::BLACK => {
                 for piece in &self.live_white_pieces {
+                    if atomic && matches!(piece, AnyPiece::King(_)) {
+                        continue;
+                    }
                     if piece.can_move(x_coord, y_coord, &self.position_board) {
                         found_movement = true;
                     }
@@ -430,6 +1024,7 @@ impl Board {
             .insert(end_y, starting_piece);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn move_piece(
         &mut self,
         piece_symbol: String,
@@ -439,16 +1034,20 @@ impl Board {
         end_x: usize,
         end_y: usize,
         promotion_piece: Option<&str>,
-    ) {
+    ) -> Result<(), MoveError> {
+        let mut took_en_passant = false;
         if let Ok(result) =
             self.valid_en_passant(piece_symbol.as_str(), moving_x, moving_y, end_x, end_y)
         {
             if result {
+                took_en_passant = true;
                 let last_move = self.played_moves.last_mut().unwrap();
                 let last_move_color = last_move.moving_color;
                 let last_move_end_pos = last_move.end_position;
-                println!("removing the piece");
                 self.remove_piece_color(last_move_end_pos.0, last_move_end_pos.1, &last_move_color);
+                if let Some(pockets) = &mut self.pockets {
+                    pockets.add(moving_piece_color, "P");
+                }
 
                 self.position_board
                     .get_mut(last_move_end_pos.0)
@@ -468,7 +1067,23 @@ impl Board {
             PieceColor::BLACK => PieceColor::WHITE,
         };
 
+        let taken_piece = self
+            .find_piece_color(end_x, end_y, &color_being_taken)
+            .map(|piece| piece.get_piece_character());
         let _removed = self.remove_piece_color(end_x, end_y, &color_being_taken);
+        let taken_was_promoted = self.promoted_squares.contains(&(end_x, end_y));
+        self.promoted_squares
+            .retain(|square| *square != (end_x, end_y));
+        if let (Some(pockets), Some(taken_piece)) = (&mut self.pockets, taken_piece) {
+            let pocket_piece = if taken_was_promoted { "P" } else { taken_piece };
+            pockets.add(moving_piece_color, pocket_piece);
+        }
+        // A promoted piece stays promoted wherever it goes
+        for square in self.promoted_squares.iter_mut() {
+            if *square == (moving_x, moving_y) {
+                *square = (end_x, end_y);
+            }
+        }
 
         // Set the piece's new position
         let moving_piece = self
@@ -477,25 +1092,25 @@ impl Board {
         let _ = &moving_piece.set_pos(end_x, end_y);
 
         match moving_piece {
-            AnyPiece::King(_king) => {
+            AnyPiece::King(king) => {
+                king.set_has_moved(true);
                 match moving_piece_color {
                     PieceColor::BLACK => self.black_king_position = (end_x, end_y),
                     PieceColor::WHITE => self.white_king_position = (end_x, end_y),
                 };
             }
-            AnyPiece::Pawn(_) => {
-                if end_y == 7 {
-                    self.promote_pawn_at(
-                        end_x,
-                        end_y,
-                        moving_piece_color,
-                        promotion_piece.unwrap(),
-                    );
-                }
+            AnyPiece::Rook(rook) => rook.set_has_moved(true),
+            AnyPiece::Pawn(_) if promotion_piece.is_some() => {
+                self.promote_pawn_at(end_x, end_y, moving_piece_color, promotion_piece.unwrap())?;
+                self.promoted_squares.push((end_x, end_y));
             }
             _ => (),
         };
 
+        if self.variant == Variant::Atomic && (taken_piece.is_some() || took_en_passant) {
+            self.explode(end_x, end_y);
+        }
+
         match promotion_piece {
             Some(promotion_piece) => self.played_moves.push(PlayedMove::new(
                 piece_symbol,
@@ -514,6 +1129,103 @@ impl Board {
         };
 
         self.add_state_hash();
+        Ok(())
+    }
+
+    // An Atomic capture blows up the capturing piece and every piece next to the square that
+    // isn't a pawn
+    fn explode(&mut self, x_coord: usize, y_coord: usize) {
+        for blast_x in x_coord.saturating_sub(1)..=usize::min(x_coord + 1, self.width() - 1) {
+            for blast_y in y_coord.saturating_sub(1)..=usize::min(y_coord + 1, self.height() - 1) {
+                let is_center = (blast_x, blast_y) == (x_coord, y_coord);
+                for piece_color in [PieceColor::WHITE, PieceColor::BLACK] {
+                    let blown_up = match self.find_piece_color(blast_x, blast_y, &piece_color) {
+                        Some(AnyPiece::Pawn(_)) => is_center,
+                        Some(_) => true,
+                        None => false,
+                    };
+                    if blown_up {
+                        self.remove_piece_color(blast_x, blast_y, &piece_color);
+                        self.position_board[blast_x][blast_y] = QuickPiece::EMPTY;
+                        self.promoted_squares
+                            .retain(|square| *square != (blast_x, blast_y));
+                    }
+                }
+            }
+        }
+    }
+
+    /// False once the color's king has been blown up in Atomic
+    pub fn has_king(&self, piece_color: &PieceColor) -> bool {
+        let pieces = match piece_color {
+            PieceColor::WHITE => &self.live_white_pieces,
+            PieceColor::BLACK => &self.live_black_pieces,
+        };
+        pieces
+            .iter()
+            .any(|piece| matches!(piece, AnyPiece::King(_)))
+    }
+
+    /// Where the color's king is, if it has one that can be checked.  Antichess kings are ordinary
+    /// pieces and an Atomic king can be blown up
+    pub fn king_position(&self, piece_color: &PieceColor) -> Option<(usize, usize)> {
+        if self.variant == Variant::Antichess || !self.has_king(piece_color) {
+            return None;
+        }
+        match piece_color {
+            PieceColor::WHITE => Some(self.white_king_position),
+            PieceColor::BLACK => Some(self.black_king_position),
+        }
+    }
+
+    /// True when the two kings are next to each other.  In Atomic neither can be in check then,
+    /// taking one would blow up the other
+    pub fn kings_touch(&self) -> bool {
+        let (white_x, white_y) = self.white_king_position;
+        let (black_x, black_y) = self.black_king_position;
+        usize::max(white_x, black_x) - usize::min(white_x, black_x) <= 1
+            && usize::max(white_y, black_y) - usize::min(white_y, black_y) <= 1
+    }
+
+    /// True when the Atomic move would leave the color's own king blown up or in check.  Kings
+    /// can't capture, and a move that blows up the other king is fine even out of check
+    pub fn atomic_move_exposes_king(
+        &self,
+        start_position: (usize, usize),
+        end_position: (usize, usize),
+        moving_color: &PieceColor,
+    ) -> bool {
+        let end_occupied = self.position_board[end_position.0][end_position.1] != QuickPiece::EMPTY;
+        let mut board = self.clone();
+        let piece_symbol =
+            match board.find_piece_color(start_position.0, start_position.1, moving_color) {
+                Some(AnyPiece::King(_)) if end_occupied => return true,
+                Some(piece) => String::from(piece.get_piece_character()),
+                None => return true,
+            };
+        if board
+            .move_piece(
+                piece_symbol,
+                start_position.0,
+                start_position.1,
+                moving_color,
+                end_position.0,
+                end_position.1,
+                None,
+            )
+            .is_err()
+            || !board.has_king(moving_color)
+        {
+            return true;
+        }
+        if !board.has_king(&PieceColor::opposite_color(moving_color)) {
+            return false;
+        }
+        let (king_x, king_y) = match moving_color {
+            PieceColor::WHITE => board.white_king_position,
+            PieceColor::BLACK => board.black_king_position,
+        };
+        board.can_any_piece_check_king(king_x, king_y, moving_color)
     }
 
     pub fn add_state_hash(&mut self) {
@@ -522,6 +1234,8 @@ impl Board {
         let mut hasher = DefaultHasher::new();
         self.live_black_pieces.hash(&mut hasher);
         self.live_white_pieces.hash(&mut hasher);
+        self.pockets.hash(&mut hasher);
+        self.check_counts.hash(&mut hasher);
         let hash = hasher.finish();
         if self.board_state_hashes.contains_key(&hash) {
             let current_count = *self.board_state_hashes.get_mut(&hash).unwrap();
@@ -537,31 +1251,113 @@ impl Board {
         let mut hasher = DefaultHasher::new();
         self.live_black_pieces.hash(&mut hasher);
         self.live_white_pieces.hash(&mut hasher);
+        self.pockets.hash(&mut hasher);
+        self.check_counts.hash(&mut hasher);
         hasher.finish()
     }
 
-    // @TODO this would be another prime function for adding Result to
     fn promote_pawn_at(
         &mut self,
         x_coord: usize,
         y_coord: usize,
         pawn_color: &PieceColor,
         promotion_piece: &str,
-    ) {
-        // first remove the pawn that is at that location
+    ) -> Result<(), MoveError> {
+        let promoted_piece = self.promoted_piece(promotion_piece, x_coord, y_coord, pawn_color)?;
+        // Only remove the pawn once we know what it turns into
         self.remove_piece_color(x_coord, y_coord, pawn_color);
-        let promoted_piece = match promotion_piece {
-            "N" => AnyPiece::Knight(Knight::new(x_coord, y_coord, *pawn_color)),
-            "Q" => AnyPiece::Queen(Queen::new(x_coord, y_coord, *pawn_color)),
-            "B" => AnyPiece::Bishop(Bishop::new(x_coord, y_coord, *pawn_color)),
-            "R" => AnyPiece::Rook(Rook::new(x_coord, y_coord, *pawn_color)),
-            _ => panic!("AAAAAAAAAAAAAA in promoting pawn "),
-        };
-
+        if let AnyPiece::King(_) = promoted_piece {
+            self.position_board[x_coord][y_coord] = QuickPiece::KING(*pawn_color);
+        }
         match pawn_color {
             PieceColor::WHITE => self.live_white_pieces.push(promoted_piece),
             PieceColor::BLACK => self.live_black_pieces.push(promoted_piece),
         };
+        Ok(())
+    }
+
+    // Antichess pawns can also promote to a king
+    fn promoted_piece(
+        &self,
+        promotion_piece: &str,
+        x_coord: usize,
+        y_coord: usize,
+        pawn_color: &PieceColor,
+    ) -> Result<AnyPiece, MoveError> {
+        let antichess = self.variant == Variant::Antichess;
+        let can_promote = match promotion_piece {
+            "Q" | "R" | "B" | "N" => true,
+            "K" => antichess,
+            // Any fairy piece the board knows
+            "P" => false,
+            _ => self
+                .piece_from_character(promotion_piece, 0, 0, *pawn_color)
+                .is_some(),
+        };
+        if !can_promote {
+            return Err(MoveError::new(&format!(
+                "A pawn can't promote to {:?}.  It has to be one of {}",
+                promotion_piece,
+                if antichess {
+                    "Q, R, B, N or K"
+                } else {
+                    "Q, R, B or N"
+                }
+            )));
+        }
+        Ok(self
+            .piece_from_character(promotion_piece, x_coord, y_coord, *pawn_color)
+            .unwrap())
+    }
+
+    /// The letters a pawn can promote to, the variant's pieces and then any fairy pieces
+    pub fn promotion_pieces(&self) -> Vec<&'static str> {
+        let mut promotion_pieces = self.variant.promotion_pieces().to_vec();
+        promotion_pieces.extend(
+            self.fairy_pieces
+                .iter()
+                .map(|piece_type| piece_type.letter()),
+        );
+        promotion_pieces
+    }
+
+    /// True when the move takes a pawn to the last rank but doesn't say what it promotes to
+    pub fn needs_promotion_piece(&self, parsed_move: &ParsedMove) -> bool {
+        parsed_move.piece_char == "P"
+            && parsed_move.end_coords.1 == self.last_rank()
+            && matches!(parsed_move.move_type, MoveTypes::Move | MoveTypes::Take)
+    }
+
+    // The rank the side to move promotes on, 8 on a standard board for White
+    fn last_rank(&self) -> String {
+        match PieceColor::opposite_color(&self.last_move_color) {
+            PieceColor::WHITE => parser::rank_from_coordinate(self.height() - 1),
+            PieceColor::BLACK => parser::rank_from_coordinate(0),
+        }
+    }
+
+    // Makes sure a promotion is on the last rank, is to a real piece, and that pawns on the last
+    // rank always promote
+    fn check_promotion(&self, parsed_move: &ParsedMove) -> Result<(), MoveError> {
+        if self.needs_promotion_piece(parsed_move) {
+            return Err(MoveError::new(
+                "A pawn reaching the last rank needs a piece to promote to",
+            ));
+        }
+        if let MoveTypes::Promote(promotion_piece) = &parsed_move.move_type {
+            if parsed_move.piece_char != "P" || parsed_move.end_coords.1 != self.last_rank() {
+                return Err(MoveError::new(
+                    "Only a pawn reaching the last rank can promote",
+                ));
+            }
+            self.promoted_piece(
+                promotion_piece,
+                0,
+                0,
+                &PieceColor::opposite_color(&self.last_move_color),
+            )?;
+        }
+        Ok(())
     }
 
     // This will return false as long as this is not a pawn attempting and failing to make an en passant
@@ -577,19 +1373,11 @@ impl Board {
         end_x: usize,
         end_y: usize,
     ) -> Result<bool, MoveError> {
-        if (7, 4) == (start_x, _start_y) && (6, 5) == (end_x, end_y) {
-            println!("break");
-            println!(
-                "validEn:{:?}",
-                self.position_board.get(end_x).unwrap().get(end_y).unwrap()
-            );
-        };
-
         if let "P" = moving_piece_symbol {
             if let QuickPiece::EMPTY = self.position_board.get(end_x).unwrap().get(end_y).unwrap() {
                 let delta_x = usize::max(start_x, end_x) - usize::min(start_x, end_x);
                 if delta_x == 1 {
-                    if end_y == 5 || end_y == 2 {
+                    if end_y + 3 == self.height() || end_y == 2 {
                         if let Some(last_played_move) = self.played_moves.last() {
                             if let "P" = last_played_move.piece_string.as_str() {
                                 if last_played_move.starting_position.0
@@ -644,12 +1432,30 @@ impl Board {
     // @TODO Maybe add if move says check or check mate make that check too
     pub fn play_move(&mut self, parsed_move: ParsedMove) -> Result<(), MoveError> {
         let current_move_color = PieceColor::opposite_color(&self.last_move_color);
+        let piece_count = self.live_white_pieces.len() + self.live_black_pieces.len();
+        let pawn_move =
+            parsed_move.piece_char == "P" && !matches!(parsed_move.move_type, MoveTypes::Castle(_));
 
         match &parsed_move.move_type {
+            // SAN doesn't say which file the king goes to, only which side
             MoveTypes::Castle(king_end_x) => {
-                self.castle_king(&current_move_color, *king_end_x);
+                let king_x = match current_move_color {
+                    PieceColor::WHITE => self.white_king_position.0,
+                    PieceColor::BLACK => self.black_king_position.0,
+                };
+                let king_end_x = self.castle_end_x(*king_end_x > king_x);
+                self.castle_king(&current_move_color, king_end_x)?;
+            }
+            MoveTypes::Drop => {
+                self.drop_piece(
+                    &current_move_color,
+                    &parsed_move.piece_char,
+                    parser::parse_coordinate(&parsed_move.end_coords.0),
+                    parser::parse_coordinate(&parsed_move.end_coords.1),
+                )?;
             }
             _ => {
+                self.check_promotion(&parsed_move)?;
                 let moving_piece = match self.find_start_piece_from_move(&parsed_move) {
                     None => {
                         return Err(MoveError::new(
@@ -664,8 +1470,21 @@ impl Board {
                 };
 
                 let (moving_x, moving_y) = moving_piece.get_pos();
+                let moving_king = matches!(moving_piece, AnyPiece::King(_));
                 let end_x = parser::parse_coordinate(&parsed_move.end_coords.0);
                 let end_y = parser::parse_coordinate(&parsed_move.end_coords.1);
+                if self.variant == Variant::Atomic
+                    && moving_king
+                    && self.position_board[end_x][end_y] != QuickPiece::EMPTY
+                {
+                    return Err(MoveError::new("A king can't capture in Atomic"));
+                }
+                if self.variant == Variant::Antichess
+                    && !game::is_capture(self, (moving_x, moving_y), (end_x, end_y))
+                    && game::can_capture(&current_move_color, self)
+                {
+                    return Err(MoveError::new("A capture has to be made in Antichess"));
+                }
                 if !game::will_move_be_in_check(
                     moving_x,
                     moving_y,
@@ -693,7 +1512,7 @@ impl Board {
                             end_x,
                             end_y,
                             Some(piece_promote.as_str()),
-                        );
+                        )?;
                     } else {
                         self.move_piece(
                             parsed_move.piece_char,
@@ -703,7 +1522,7 @@ impl Board {
                             end_x,
                             end_y,
                             None,
-                        );
+                        )?;
                     }
                 } else {
                     return Err(MoveError::new(
@@ -716,158 +1535,368 @@ impl Board {
                 }
             }
         };
+        if self.variant == Variant::ThreeCheck {
+            self.count_check(&current_move_color);
+        }
+        // A capture leaves fewer pieces on the board, which covers en passant and Atomic too
+        if pawn_move || self.live_white_pieces.len() + self.live_black_pieces.len() < piece_count {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if current_move_color == PieceColor::BLACK {
+            self.fullmove_number += 1;
+        }
         self.last_move_color = PieceColor::opposite_color(&self.last_move_color);
         Ok(())
     }
 
+    // Counts the check if the color's move gave one.  The state hash for the move went in before
+    // the count so it is swapped for one with the new count
+    fn count_check(&mut self, moving_color: &PieceColor) {
+        if !game::is_board_in_check(moving_color, self) {
+            return;
+        }
+        let stale_hash = self.get_current_hash();
+        if let Some(count) = self.board_state_hashes.get_mut(&stale_hash) {
+            *count -= 1;
+            if *count == 0 {
+                self.board_state_hashes.remove(&stale_hash);
+            }
+        }
+        match moving_color {
+            PieceColor::WHITE => self.check_counts.0 += 1,
+            PieceColor::BLACK => self.check_counts.1 += 1,
+        }
+        self.add_state_hash();
+    }
+
+    /// The squares the last move went from and to.  Castling saves the rook move after the king
+    /// move so when the last two moves are the same color the king move is used
+    pub fn last_move_squares(&self) -> Option<((usize, usize), (usize, usize))> {
+        let last_move = self.played_moves.last()?;
+        let shown_move = match self.played_moves.len().checked_sub(2) {
+            Some(index) if self.played_moves[index].moving_color == last_move.moving_color => {
+                &self.played_moves[index]
+            }
+            _ => last_move,
+        };
+        Some((shown_move.starting_position, shown_move.end_position))
+    }
+
+    /// Every move played as (start, end, promotion piece).  Castling is only the king's move
+    pub fn played_plies(&self) -> Vec<game::ResolvedMove> {
+        let mut plies = Vec::with_capacity(self.played_moves.len());
+        let mut last_color = None;
+        for played_move in &self.played_moves {
+            // The rook move of a castle is saved right after the king move
+            if last_color != Some(played_move.moving_color) {
+                plies.push((
+                    played_move.starting_position,
+                    played_move.end_position,
+                    played_move.promotion_piece.clone(),
+                ));
+            }
+            last_color = Some(played_move.moving_color);
+        }
+        plies
+    }
+
+    /// Builds the move of the piece at the start location to the end location.  The gui knows
+    /// squares instead of notation so this turns the squares into what play_move expects
+    pub fn parsed_move_from_coords(
+        &self,
+        start_position: (usize, usize),
+        end_position: (usize, usize),
+        promotion_piece: Option<&str>,
+    ) -> Result<ParsedMove, MoveError> {
+        // A drop is written as the square twice with the piece that is dropped
+        if let (true, Some(drop_piece)) = (start_position == end_position, promotion_piece) {
+            return Ok(ParsedMove::new(
+                String::from(drop_piece),
+                (None, None),
+                (
+                    parser::file_from_coordinate(end_position.0),
+                    parser::rank_from_coordinate(end_position.1),
+                ),
+                MoveTypes::Drop,
+                CheckOrCheckMate::Neither,
+            ));
+        }
+        let current_move_color = PieceColor::opposite_color(&self.last_move_color);
+        let piece_list = match current_move_color {
+            PieceColor::WHITE => &self.live_white_pieces,
+            PieceColor::BLACK => &self.live_black_pieces,
+        };
+        let moving_piece = match piece_list
+            .iter()
+            .find(|piece| piece.get_pos() == start_position)
+        {
+            Some(piece) => piece,
+            None => {
+                return Err(MoveError::new(&format!(
+                    "There is no {:?} piece on {}{}",
+                    current_move_color,
+                    parser::file_from_coordinate(start_position.0),
+                    parser::rank_from_coordinate(start_position.1)
+                )))
+            }
+        };
+
+        let delta_x = usize::max(start_position.0, end_position.0)
+            - usize::min(start_position.0, end_position.0);
+        let is_take = self.position_board[end_position.0][end_position.1] != QuickPiece::EMPTY;
+        let last_rank = match current_move_color {
+            PieceColor::WHITE => self.height() - 1,
+            PieceColor::BLACK => 0,
+        };
+
+        let move_type = match moving_piece {
+            AnyPiece::King(_) if delta_x >= 2 => MoveTypes::Castle(end_position.0),
+            AnyPiece::Pawn(_) if end_position.1 == last_rank && promotion_piece.is_some() => {
+                MoveTypes::Promote(String::from(promotion_piece.unwrap()))
+            }
+            AnyPiece::Pawn(_) if delta_x == 1 => MoveTypes::Take,
+            _ if is_take => MoveTypes::Take,
+            _ => MoveTypes::Move,
+        };
+
+        Ok(ParsedMove::new(
+            String::from(moving_piece.get_piece_character()),
+            (
+                Some(parser::file_from_coordinate(start_position.0)),
+                Some(parser::rank_from_coordinate(start_position.1)),
+            ),
+            (
+                parser::file_from_coordinate(end_position.0),
+                parser::rank_from_coordinate(end_position.1),
+            ),
+            move_type,
+            CheckOrCheckMate::Neither,
+        ))
+    }
+
+    pub fn play_move_from_coords(
+        &mut self,
+        start_position: (usize, usize),
+        end_position: (usize, usize),
+        promotion_piece: Option<&str>,
+    ) -> Result<(), MoveError> {
+        let parsed_move =
+            self.parsed_move_from_coords(start_position, end_position, promotion_piece)?;
+        self.play_move(parsed_move)
+    }
+
+    /// True when the color's king would be in check with a piece of its own dropped on the square.
+    /// A drop can only block a check so this only matters when the king is already in check
+    pub fn drop_leaves_king_in_check(
+        &mut self,
+        piece_color: &PieceColor,
+        x_coord: usize,
+        y_coord: usize,
+    ) -> bool {
+        let king_position = match self.king_position(piece_color) {
+            Some(king_position) => king_position,
+            None => return false,
+        };
+        let square_before = self.position_board[x_coord][y_coord];
+        self.position_board[x_coord][y_coord] = QuickPiece::PIECE(*piece_color);
+        let in_check = self.can_any_piece_check_king(king_position.0, king_position.1, piece_color);
+        self.position_board[x_coord][y_coord] = square_before;
+        in_check
+    }
+
+    // Puts a piece from the pocket on an empty square
+    fn drop_piece(
+        &mut self,
+        piece_color: &PieceColor,
+        piece_symbol: &str,
+        x_coord: usize,
+        y_coord: usize,
+    ) -> Result<(), MoveError> {
+        let in_pocket = match &self.pockets {
+            Some(pockets) => pockets.count(piece_color, piece_symbol) > 0,
+            None => return Err(MoveError::new("Pieces can only be dropped in Crazyhouse")),
+        };
+        let square = format!(
+            "{}{}",
+            parser::file_from_coordinate(x_coord),
+            parser::rank_from_coordinate(y_coord)
+        );
+        if !in_pocket {
+            return Err(MoveError::new(&format!(
+                "{:?} has no {} to drop",
+                piece_color, piece_symbol
+            )));
+        }
+        if x_coord >= self.width() || y_coord >= self.height() {
+            return Err(MoveError::new(&format!("{} is off the board", square)));
+        }
+        if self.position_board[x_coord][y_coord] != QuickPiece::EMPTY {
+            return Err(MoveError::new(&format!("{} isn't empty", square)));
+        }
+        if piece_symbol == "P" && (y_coord == 0 || y_coord == self.height() - 1) {
+            return Err(MoveError::new(
+                "Pawns can't be dropped on the first or last rank",
+            ));
+        }
+        if self.drop_leaves_king_in_check(piece_color, x_coord, y_coord) {
+            return Err(MoveError::new(&format!(
+                "Dropping on {} leaves the king in check",
+                square
+            )));
+        }
+
+        let mut piece = self
+            .piece_from_character(piece_symbol, x_coord, y_coord, *piece_color)
+            .ok_or_else(|| MoveError::new(&format!("{} can't be dropped", piece_symbol)))?;
+        match &mut piece {
+            // A dropped rook can't castle
+            AnyPiece::Rook(rook) => rook.set_has_moved(true),
+            // and a dropped pawn only moves two squares from its own starting rank
+            AnyPiece::Pawn(_) => {
+                let starting_y = match piece_color {
+                    PieceColor::WHITE => 1,
+                    PieceColor::BLACK => self.height() - 2,
+                };
+                piece = AnyPiece::Pawn(Pawn::with_starting_y(
+                    x_coord,
+                    y_coord,
+                    *piece_color,
+                    starting_y,
+                ));
+            }
+            _ => (),
+        }
+        self.place_piece(piece, *piece_color);
+        if let Some(pockets) = &mut self.pockets {
+            pockets.take(piece_color, piece_symbol);
+        }
+        self.played_moves.push(PlayedMove::new(
+            String::from(piece_symbol),
+            (x_coord, y_coord),
+            (x_coord, y_coord),
+            *piece_color,
+            Some(String::from(piece_symbol)),
+        ));
+        self.add_state_hash();
+        Ok(())
+    }
+
+    /// The file the king castles to.  Like the g and c files it is one file in from the rook, so a
+    /// Capablanca king goes to the i or c file
+    pub fn castle_end_x(&self, kingside: bool) -> usize {
+        if kingside {
+            self.width() - 2
+        } else {
+            2
+        }
+    }
+
+    // Where the rook starts and ends when the king castles to the file.  It lands on the square
+    // the king went over
+    fn castle_rook_squares(&self, kingside: bool, king_x_end: usize) -> (usize, usize) {
+        if kingside {
+            (self.width() - 1, king_x_end - 1)
+        } else {
+            (0, king_x_end + 1)
+        }
+    }
+
     pub fn can_castle_king(&mut self, king_color: &PieceColor, king_x_end: usize) -> bool {
-        let mut can_castle = false;
         // 1. The king must not have moved
-        let king_coords = match king_color {
-            PieceColor::WHITE => self.white_king_position,
-            PieceColor::BLACK => self.black_king_position,
+        let king_coords = match self.king_position(king_color) {
+            Some(king_coords) => king_coords,
+            None => return false,
         };
 
         let king_has_moved = match self.find_piece_color(king_coords.0, king_coords.1, king_color) {
-            Some(AnyPiece::King(king)) =>{
-                king.get_has_moved()
-            },
-            _ => panic!("This should always be a king"),
-        };
-
-        if !king_has_moved {
-            // @TODO feels like I could replace this match with something cleaner.
-            let check_rooks_and_empty = match king_x_end {
-                6 => {
-                    let rook = self.find_piece_color(7, king_coords.1, king_color);
-                    let rook_has_not_moved = match rook {
-                        Some(AnyPiece::Rook(rook)) => {
-                            !rook.get_has_moved()
-                        },
-                        _ => false,
-                    };
-                    let no_pieces_in_path = self
-                        .position_board
-                        .get(5)
-                        .unwrap()
-                        .get(king_coords.1)
-                        .unwrap()
-                        == &QuickPiece::EMPTY
-                        && self
-                            .position_board
-                            .get(6)
-                            .unwrap()
-                            .get(king_coords.1)
-                            .unwrap()
-                            == &QuickPiece::EMPTY;
-
-                    rook_has_not_moved && no_pieces_in_path
-                }
-                2 => {
-                    let rook = self.find_piece_color(0, king_coords.1, king_color);
-                    let rook_has_not_moved = match rook {
-                        Some(AnyPiece::Rook(rook)) => {
-                !rook.get_has_moved()
-                        },
-                        _ => false,
-                    };
-                    let no_pieces_in_path = self
-                        .position_board
-                        .get(2)
-                        .unwrap()
-                        .get(king_coords.1)
-                        .unwrap()
-                        == &QuickPiece::EMPTY
-                        && self
-                            .position_board
-                            .get(3)
-                            .unwrap()
-                            .get(king_coords.1)
-                            .unwrap()
-                            == &QuickPiece::EMPTY;
-                    rook_has_not_moved && no_pieces_in_path
-                }
-                _ => false,
-            };
-            if check_rooks_and_empty {
-                // Then check if the king would be in check for those give positions
-                can_castle = match king_x_end {
-                    6 => {
-                        let space1_check = !game::will_move_be_in_check(
-                            king_coords.0,
-                            king_coords.1,
-                            king_x_end,
-                            king_coords.1,
-                            king_color,
-                            king_color,
-                            self,
-                        );
-                        let space2_check = !game::will_move_be_in_check(
-                            king_coords.0,
-                            king_coords.1,
-                            5,
-                            king_coords.1,
-                            king_color,
-                            king_color,
-                            self,
-                        );
-                        space1_check && space2_check
-                    }
-                    2 => {
-                        let space1_check = !game::will_move_be_in_check(
-                            king_coords.0,
-                            king_coords.1,
-                            king_x_end,
-                            king_coords.1,
-                            king_color,
-                            king_color,
-                            self,
-                        );
-                        let space2_check = !game::will_move_be_in_check(
-                            king_coords.0,
-                            king_coords.1,
-                            3,
-                            king_coords.1,
-                            king_color,
-                            king_color,
-                            self,
-                        );
-                        space1_check && space2_check
-                    }
-                    _ => false, // @TODO THis may be a mistake
-                }
-            }
+            Some(AnyPiece::King(king)) => king.get_has_moved(),
+            // A king blown up in Atomic can't castle
+            _ => return false,
+        };
+
+        // 2. The king can't castle out of check
+        let in_check = self.can_any_piece_check_king(king_coords.0, king_coords.1, king_color);
+        if king_has_moved || in_check {
+            return false;
         }
 
-        can_castle
+        // 3. The rook must not have moved either
+        let kingside = king_x_end > king_coords.0;
+        if king_x_end != self.castle_end_x(kingside) {
+            return false;
+        }
+        let (rook_x, rook_x_end) = self.castle_rook_squares(kingside, king_x_end);
+        let rook_has_not_moved = match self.find_piece_color(rook_x, king_coords.1, king_color) {
+            Some(AnyPiece::Rook(rook)) => !rook.get_has_moved(),
+            _ => false,
+        };
+
+        // 4. Everything the king and rook go over has to be empty, so the rook passes the b file
+        // on the long side
+        let files = [king_coords.0, king_x_end, rook_x, rook_x_end];
+        let lowest_file = *files.iter().min().unwrap();
+        let highest_file = *files.iter().max().unwrap();
+        let no_pieces_in_path = (lowest_file..=highest_file)
+            .filter(|x_coord| *x_coord != king_coords.0 && *x_coord != rook_x)
+            .all(|x_coord| self.position_board[x_coord][king_coords.1] == QuickPiece::EMPTY);
+        if !rook_has_not_moved || !no_pieces_in_path {
+            return false;
+        }
+
+        // 5. The king can't go over or land on a square that is attacked
+        let king_path: Vec<usize> = if kingside {
+            (king_coords.0 + 1..=king_x_end).collect()
+        } else {
+            (king_x_end..king_coords.0).collect()
+        };
+        king_path.into_iter().all(|x_coord| {
+            !game::will_move_be_in_check(
+                king_coords.0,
+                king_coords.1,
+                x_coord,
+                king_coords.1,
+                king_color,
+                king_color,
+                self,
+            )
+        })
     }
 
     // This function will also castle the rook
-    fn castle_king(&mut self, king_color: &PieceColor, king_x_end: usize) -> bool {
-        if self.can_castle_king(king_color, king_x_end) {
-            // Move the king to king_x_end
-            let (king_x, king_y) = match king_color {
-                PieceColor::WHITE => self.white_king_position,
-                PieceColor::BLACK => self.black_king_position,
-            };
-            self.move_piece(
-                String::from("K"),
-                king_x,
-                king_y,
+    fn castle_king(&mut self, king_color: &PieceColor, king_x_end: usize) -> Result<(), MoveError> {
+        if !self.can_castle_king(king_color, king_x_end) {
+            return Err(MoveError::new(&format!(
+                "{:?} can't castle to the {} file",
                 king_color,
-                king_x_end,
-                king_y,
-                None,
-            );
-            if king_x_end == 6 {
-                self.move_piece(String::from("R"), 7, king_y, king_color, 5, king_y, None);
-            } else {
-                self.move_piece(String::from("R"), 0, king_y, king_color, 3, king_y, None);
-            }
-            true
-        } else {
-            false
+                parser::file_from_coordinate(king_x_end)
+            )));
         }
+        // Move the king to king_x_end
+        let (king_x, king_y) = match king_color {
+            PieceColor::WHITE => self.white_king_position,
+            PieceColor::BLACK => self.black_king_position,
+        };
+        self.move_piece(
+            String::from("K"),
+            king_x,
+            king_y,
+            king_color,
+            king_x_end,
+            king_y,
+            None,
+        )?;
+        let (rook_x, rook_x_end) = self.castle_rook_squares(king_x_end > king_x, king_x_end);
+        self.move_piece(
+            String::from("R"),
+            rook_x,
+            king_y,
+            king_color,
+            rook_x_end,
+            king_y,
+            None,
+        )
     }
 }
 
@@ -0,0 +1,250 @@
+use crate::board::Board;
+use crate::parser;
+use crate::piece_types::PieceColor;
+use crate::pieces::PieceMove;
+use crate::variant::Variant;
+use std::collections::HashMap;
+use std::fmt;
+
+const RESET: &str = "\x1b[0m";
+const LIGHT_SQUARE: &str = "\x1b[48;5;187m";
+const DARK_SQUARE: &str = "\x1b[48;5;65m";
+const WHITE_PIECE: &str = "\x1b[1;97m";
+const BLACK_PIECE: &str = "\x1b[1;30m";
+
+/// How a board is drawn in the terminal
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DisplayOptions {
+    /// Chess glyphs instead of letters
+    pub unicode: bool,
+    /// ANSI colors for the squares and pieces
+    pub colors: bool,
+    /// Black at the bottom
+    pub flipped: bool,
+}
+
+/// A board together with the options it is drawn with.  Made by Board::display
+pub struct BoardDisplay<'a> {
+    board: &'a Board,
+    options: DisplayOptions,
+}
+
+impl Board {
+    pub fn display(&self, options: DisplayOptions) -> BoardDisplay<'_> {
+        BoardDisplay {
+            board: self,
+            options,
+        }
+    }
+}
+
+fn piece_symbol(piece_character: &str, color: PieceColor, unicode: bool) -> String {
+    if !unicode {
+        return match color {
+            PieceColor::WHITE => piece_character.to_string(),
+            PieceColor::BLACK => piece_character.to_ascii_lowercase(),
+        };
+    }
+    let glyph = match (color, piece_character) {
+        (PieceColor::WHITE, "K") => "♔",
+        (PieceColor::WHITE, "Q") => "♕",
+        (PieceColor::WHITE, "R") => "♖",
+        (PieceColor::WHITE, "B") => "♗",
+        (PieceColor::WHITE, "N") => "♘",
+        (PieceColor::WHITE, _) => "♙",
+        (PieceColor::BLACK, "K") => "♚",
+        (PieceColor::BLACK, "Q") => "♛",
+        (PieceColor::BLACK, "R") => "♜",
+        (PieceColor::BLACK, "B") => "♝",
+        (PieceColor::BLACK, "N") => "♞",
+        (PieceColor::BLACK, _) => "♟",
+    };
+    glyph.to_string()
+}
+
+impl fmt::Display for BoardDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut pieces = HashMap::new();
+        for piece in &self.board.live_white_pieces {
+            pieces.insert(
+                piece.get_pos(),
+                (piece.get_piece_character(), PieceColor::WHITE),
+            );
+        }
+        for piece in &self.board.live_black_pieces {
+            pieces.insert(
+                piece.get_pos(),
+                (piece.get_piece_character(), PieceColor::BLACK),
+            );
+        }
+
+        let (width, height) = (self.board.width(), self.board.height());
+        let files: Vec<usize> = match self.options.flipped {
+            true => (0..width).rev().collect(),
+            false => (0..width).collect(),
+        };
+        let ranks: Vec<usize> = match self.options.flipped {
+            true => (0..height).collect(),
+            false => (0..height).rev().collect(),
+        };
+        // Rank numbers are lined up on the right when some have two digits
+        let label_width = parser::rank_from_coordinate(height - 1).len();
+
+        for y_coord in &ranks {
+            write!(
+                f,
+                "{:>width$} ",
+                parser::rank_from_coordinate(*y_coord),
+                width = label_width
+            )?;
+            for x_coord in &files {
+                let symbol = match pieces.get(&(*x_coord, *y_coord)) {
+                    Some((piece_character, color)) => {
+                        piece_symbol(piece_character, *color, self.options.unicode)
+                    }
+                    None if self.options.colors => String::from(" "),
+                    None => String::from("."),
+                };
+                if self.options.colors {
+                    // a1 is a dark square
+                    let background = match (x_coord + y_coord) % 2 {
+                        0 => DARK_SQUARE,
+                        _ => LIGHT_SQUARE,
+                    };
+                    let foreground = match pieces.get(&(*x_coord, *y_coord)) {
+                        Some((_, PieceColor::BLACK)) => BLACK_PIECE,
+                        _ => WHITE_PIECE,
+                    };
+                    write!(f, "{}{} {} {}", background, foreground, symbol, RESET)?;
+                } else {
+                    write!(f, " {}", symbol)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        // The colored squares are three characters wide
+        let file_separator = if self.options.colors { "  " } else { " " };
+        let file_labels: Vec<String> = files
+            .iter()
+            .map(|x_coord| parser::file_from_coordinate(*x_coord))
+            .collect();
+        write!(
+            f,
+            "{}  {}",
+            " ".repeat(label_width),
+            file_labels.join(file_separator)
+        )?;
+
+        // Crazyhouse pockets go under the board
+        if let Some(pockets) = &self.board.pockets {
+            let pocket_text = |color: PieceColor| {
+                let pieces: Vec<String> = pockets
+                    .pieces(&color)
+                    .iter()
+                    .map(|piece| piece_symbol(piece, color, self.options.unicode))
+                    .collect();
+                match pieces.is_empty() {
+                    true => String::from("-"),
+                    false => pieces.join(" "),
+                }
+            };
+            write!(
+                f,
+                "\n   White: {}  Black: {}",
+                pocket_text(PieceColor::WHITE),
+                pocket_text(PieceColor::BLACK)
+            )?;
+        }
+        if self.board.variant == Variant::ThreeCheck {
+            write!(
+                f,
+                "\n   Checks  White: {}  Black: {}",
+                self.board.checks_given(&PieceColor::WHITE),
+                self.board.checks_given(&PieceColor::BLACK)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Plain letters with White at the bottom
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(DisplayOptions::default()).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::board_display::DisplayOptions;
+
+    #[test]
+    fn test_ascii_board() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K2R w K - 0 1").unwrap();
+        let expected = [
+            "8  . . . . k . . .",
+            "7  . . . . . . . .",
+            "6  . . . . . . . .",
+            "5  . . . . . . . .",
+            "4  . . . . . . . .",
+            "3  . . . . . . . .",
+            "2  . . . . P . . .",
+            "1  . . . . K . . R",
+            "   a b c d e f g h",
+        ];
+        assert_eq!(board.to_string(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_tall_board_lines_up() {
+        let board = Board::from_fen("4k/5/5/5/5/5/5/5/5/4K w - - 0 1").unwrap();
+        let lines: Vec<String> = board.to_string().lines().map(String::from).collect();
+        assert_eq!(lines[0], "10  . . . . k");
+        assert_eq!(lines[9], " 1  . . . . K");
+        assert_eq!(lines[10], "    a b c d e");
+    }
+
+    #[test]
+    fn test_flipped_unicode_board() {
+        let board = Board::new();
+        let options = DisplayOptions {
+            unicode: true,
+            flipped: true,
+            ..DisplayOptions::default()
+        };
+        let drawn = board.display(options).to_string();
+        let lines: Vec<&str> = drawn.lines().collect();
+        assert_eq!(lines[0], "1  ♖ ♘ ♗ ♔ ♕ ♗ ♘ ♖");
+        assert_eq!(lines[7], "8  ♜ ♞ ♝ ♚ ♛ ♝ ♞ ♜");
+        assert_eq!(lines[8], "   h g f e d c b a");
+    }
+
+    #[test]
+    fn test_colored_board() {
+        let options = DisplayOptions {
+            colors: true,
+            ..DisplayOptions::default()
+        };
+        let drawn = Board::new().display(options).to_string();
+        assert!(drawn.contains("\x1b["));
+        assert!(drawn.ends_with("   a  b  c  d  e  f  g  h"));
+    }
+
+    #[test]
+    fn test_crazyhouse_pockets() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3[QNp] w - - 0 1").unwrap();
+        assert!(board
+            .to_string()
+            .ends_with("   a b c d e f g h\n   White: Q N  Black: p"));
+    }
+
+    #[test]
+    fn test_three_check_counts() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1 +2+0").unwrap();
+        assert!(board
+            .to_string()
+            .ends_with("\n   Checks  White: 2  Black: 0"));
+    }
+}
@@ -0,0 +1,320 @@
+use iced_graphics::{Backend, Defaults, Primitive, Renderer};
+use iced_native::layout::{self, Layout};
+use iced_native::{
+    event, image, mouse, Background, Clipboard, Color, Element, Event, Hasher, Length, Point,
+    Rectangle, Size, Widget,
+};
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+
+// x and y on the position board
+type Square = (usize, usize);
+
+/// The piece being dragged and where the mouse has got to.  It is kept by the GUI between frames
+/// since the widget is made again for every one
+#[derive(Debug, Default)]
+pub struct DragState {
+    start: Option<(usize, usize)>,
+    // None until the mouse moves, so a press and release without moving is just a click
+    cursor: Option<Point>,
+}
+
+/// How one square is drawn
+#[derive(Debug, Clone)]
+pub struct SquareView {
+    pub background: Color,
+    pub border_color: Color,
+    pub border_width: f32,
+    pub piece_image: Option<PathBuf>,
+    /// The piece on it can be picked up and dragged
+    pub draggable: bool,
+}
+
+/// The board as a single widget.  Pressing a square is a click, and a piece dragged to another
+/// square and let go is a drop
+pub struct BoardWidget<'a, Message> {
+    // Indexed the same as the position board, [x][y]
+    squares: Vec<Vec<SquareView>>,
+    drag: &'a mut DragState,
+    on_click: fn(usize, usize) -> Message,
+    on_drop: Option<fn(Square, Square) -> Message>,
+    square_size: u16,
+    padding: u16,
+    white_at_bottom: bool,
+}
+
+impl<'a, Message> BoardWidget<'a, Message> {
+    pub fn new(
+        squares: Vec<Vec<SquareView>>,
+        drag: &'a mut DragState,
+        on_click: fn(usize, usize) -> Message,
+    ) -> Self {
+        BoardWidget {
+            squares,
+            drag,
+            on_click,
+            on_drop: None,
+            square_size: 100,
+            padding: 0,
+            white_at_bottom: true,
+        }
+    }
+
+    pub fn on_drop(mut self, on_drop: fn(Square, Square) -> Message) -> Self {
+        self.on_drop = Some(on_drop);
+        self
+    }
+
+    pub fn square_size(mut self, square_size: u16) -> Self {
+        self.square_size = square_size;
+        self
+    }
+
+    /// Room between the edge of a square and its piece
+    pub fn padding(mut self, padding: u16) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn white_at_bottom(mut self, white_at_bottom: bool) -> Self {
+        self.white_at_bottom = white_at_bottom;
+        self
+    }
+
+    fn files(&self) -> usize {
+        self.squares.len()
+    }
+
+    fn ranks(&self) -> usize {
+        self.squares.first().map_or(0, Vec::len)
+    }
+
+    // Where the square is drawn
+    fn square_bounds(&self, bounds: Rectangle, (x, y): (usize, usize)) -> Rectangle {
+        let (column, row) = if self.white_at_bottom {
+            (x, self.ranks() - 1 - y)
+        } else {
+            (self.files() - 1 - x, y)
+        };
+        let size = f32::from(self.square_size);
+        Rectangle {
+            x: bounds.x + column as f32 * size,
+            y: bounds.y + row as f32 * size,
+            width: size,
+            height: size,
+        }
+    }
+
+    // The square under the point
+    fn square_at(&self, bounds: Rectangle, point: Point) -> Option<(usize, usize)> {
+        if !bounds.contains(point) {
+            return None;
+        }
+        let size = f32::from(self.square_size);
+        let column = (((point.x - bounds.x) / size) as usize).min(self.files() - 1);
+        let row = (((point.y - bounds.y) / size) as usize).min(self.ranks() - 1);
+        Some(if self.white_at_bottom {
+            (column, self.ranks() - 1 - row)
+        } else {
+            (self.files() - 1 - column, row)
+        })
+    }
+
+    fn piece_primitive(&self, image_path: &Path, square_bounds: Rectangle) -> Primitive {
+        let padding = f32::from(self.padding);
+        Primitive::Image {
+            handle: image::Handle::from_path(image_path),
+            bounds: Rectangle {
+                x: square_bounds.x + padding,
+                y: square_bounds.y + padding,
+                width: square_bounds.width - 2.0 * padding,
+                height: square_bounds.height - 2.0 * padding,
+            },
+        }
+    }
+}
+
+impl<'a, Message, B> Widget<Message, Renderer<B>> for BoardWidget<'a, Message>
+where
+    B: Backend,
+{
+    fn width(&self) -> Length {
+        Length::Units(self.square_size * self.files() as u16)
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(self.square_size * self.ranks() as u16)
+    }
+
+    fn layout(&self, _renderer: &Renderer<B>, _limits: &layout::Limits) -> layout::Node {
+        let size = f32::from(self.square_size);
+        layout::Node::new(Size::new(
+            size * self.files() as f32,
+            size * self.ranks() as f32,
+        ))
+    }
+
+    fn draw(
+        &self,
+        _renderer: &mut Renderer<B>,
+        _defaults: &Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _viewport: &Rectangle,
+    ) -> (Primitive, mouse::Interaction) {
+        let bounds = layout.bounds();
+        let mut primitives = Vec::new();
+        let mut pieces = Vec::new();
+        for (x, column) in self.squares.iter().enumerate() {
+            for (y, square) in column.iter().enumerate() {
+                let square_bounds = self.square_bounds(bounds, (x, y));
+                primitives.push(Primitive::Quad {
+                    bounds: square_bounds,
+                    background: Background::Color(square.background),
+                    border_radius: 0.0,
+                    border_width: square.border_width,
+                    border_color: square.border_color,
+                });
+                let is_dragged = self.drag.start == Some((x, y)) && self.drag.cursor.is_some();
+                if let (Some(image_path), false) = (&square.piece_image, is_dragged) {
+                    pieces.push(self.piece_primitive(image_path, square_bounds));
+                }
+            }
+        }
+        primitives.append(&mut pieces);
+
+        // The dragged piece goes on top of everything, centered on the mouse
+        let mut interaction = mouse::Interaction::Idle;
+        if let (Some((x, y)), Some(cursor)) = (self.drag.start, self.drag.cursor) {
+            if let Some(image_path) = &self.squares[x][y].piece_image {
+                let size = f32::from(self.square_size);
+                let square_bounds = Rectangle {
+                    x: cursor.x - size / 2.0,
+                    y: cursor.y - size / 2.0,
+                    width: size,
+                    height: size,
+                };
+                primitives.push(self.piece_primitive(image_path, square_bounds));
+            }
+            interaction = mouse::Interaction::Grabbing;
+        } else if let Some((x, y)) = self.square_at(bounds, cursor_position) {
+            if self.squares[x][y].draggable {
+                interaction = mouse::Interaction::Grab;
+            }
+        }
+        (Primitive::Group { primitives }, interaction)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+        self.files().hash(state);
+        self.ranks().hash(state);
+        self.square_size.hash(state);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer<B>,
+        _clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        let bounds = layout.bounds();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some((x, y)) = self.square_at(bounds, cursor_position) {
+                    messages.push((self.on_click)(x, y));
+                    if self.on_drop.is_some() && self.squares[x][y].draggable {
+                        self.drag.start = Some((x, y));
+                        self.drag.cursor = None;
+                    }
+                    return event::Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) if self.drag.start.is_some() => {
+                self.drag.cursor = Some(position);
+                return event::Status::Captured;
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some(start) = self.drag.start.take() {
+                    let moved = self.drag.cursor.take().is_some();
+                    match (self.square_at(bounds, cursor_position), self.on_drop) {
+                        (Some(end), Some(on_drop)) if moved && end != start => {
+                            messages.push(on_drop(start, end))
+                        }
+                        _ => {}
+                    }
+                    return event::Status::Captured;
+                }
+            }
+            _ => {}
+        }
+        event::Status::Ignored
+    }
+}
+
+impl<'a, Message, B> From<BoardWidget<'a, Message>> for Element<'a, Message, Renderer<B>>
+where
+    B: Backend,
+    Message: 'a,
+{
+    fn from(board_widget: BoardWidget<'a, Message>) -> Element<'a, Message, Renderer<B>> {
+        Element::new(board_widget)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board_widget::{BoardWidget, DragState, SquareView};
+    use iced_native::{Color, Point, Rectangle};
+
+    fn board_widget(
+        drag: &mut DragState,
+        white_at_bottom: bool,
+    ) -> BoardWidget<'_, (usize, usize)> {
+        let square = SquareView {
+            background: Color::WHITE,
+            border_color: Color::TRANSPARENT,
+            border_width: 0.0,
+            piece_image: None,
+            draggable: false,
+        };
+        BoardWidget::new(vec![vec![square; 8]; 10], drag, |x, y| (x, y))
+            .square_size(50)
+            .white_at_bottom(white_at_bottom)
+    }
+
+    #[test]
+    fn test_squares_under_the_mouse() {
+        let bounds = Rectangle {
+            x: 30.0,
+            y: 0.0,
+            width: 500.0,
+            height: 400.0,
+        };
+        let mut drag = DragState::default();
+        let widget = board_widget(&mut drag, true);
+        // a1 is in the bottom left corner and the board is 10 files by 8 ranks
+        assert_eq!(
+            widget.square_at(bounds, Point::new(31.0, 399.0)),
+            Some((0, 0))
+        );
+        assert_eq!(
+            widget.square_at(bounds, Point::new(529.0, 1.0)),
+            Some((9, 7))
+        );
+        assert_eq!(widget.square_at(bounds, Point::new(10.0, 10.0)), None);
+        assert_eq!(widget.square_bounds(bounds, (1, 0)).y, 350.0);
+
+        let mut drag = DragState::default();
+        let widget = board_widget(&mut drag, false);
+        assert_eq!(
+            widget.square_at(bounds, Point::new(31.0, 399.0)),
+            Some((9, 7))
+        );
+        assert_eq!(widget.square_bounds(bounds, (0, 0)).x, 480.0);
+    }
+}
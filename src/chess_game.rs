@@ -0,0 +1,493 @@
+use crate::board::{Board, MoveError};
+use crate::clock::{Clock, TimeControl};
+use crate::controller::{ControllerError, GameController, Outcome, Termination};
+use crate::game::{self, ResolvedMove};
+use crate::parser::{GameResult, ParseError};
+use crate::pgn::PgnGame;
+use crate::piece_types::PieceColor;
+use crate::player::Players;
+use crate::san;
+use std::time::Duration;
+
+/// A move that was played in the game, kept so the game can be replayed up to any point
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameMove {
+    pub start_position: (usize, usize),
+    pub end_position: (usize, usize),
+    pub promotion_piece: Option<String>,
+    pub san: String,
+}
+
+/// What happened in the game.  The front ends drain these after each call and show them however
+/// they like
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    MovePlayed {
+        color: PieceColor,
+        game_move: GameMove,
+    },
+    /// The color is in check
+    Check(PieceColor),
+    DrawOffered(PieceColor),
+    /// The color turned down the other side's offer, by saying so or by moving
+    DrawDeclined(PieceColor),
+    DrawWithdrawn(PieceColor),
+    GameOver(Outcome),
+    ClockUpdate {
+        white: Duration,
+        black: Duration,
+    },
+}
+
+/// A game of chess without any input or output.  It owns the board, the players, the clock, the
+/// moves and how the game ended, and keeps to the rules.  The CLI and the GUI only read input,
+/// call it and show the events that come out
+#[derive(Clone)]
+pub struct Game {
+    start_board: Board,
+    board: Board,
+    players: Players,
+    clock: Option<Clock>,
+    moves: Vec<GameMove>,
+    controller: GameController,
+    events: Vec<GameEvent>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new(Players::default(), None)
+    }
+}
+
+impl Game {
+    /// A game from the starting position.  The clock starts for White straight away
+    pub fn new(players: Players, time_control: Option<TimeControl>) -> Game {
+        Game::from_board(Board::new(), players, time_control)
+    }
+
+    /// A game from a set up position
+    pub fn from_board(
+        start_board: Board,
+        players: Players,
+        time_control: Option<TimeControl>,
+    ) -> Game {
+        let mut clock = time_control.map(Clock::new);
+        if let Some(clock) = &mut clock {
+            clock.start(PieceColor::opposite_color(&start_board.last_move_color));
+        }
+        Game {
+            board: start_board.clone(),
+            start_board,
+            players,
+            clock,
+            moves: Vec::new(),
+            controller: GameController::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Plays out a saved game.  The clock picks up where it was saved and a game saved with a
+    /// result is over.  No events are left behind for the moves
+    pub fn from_pgn(pgn_game: &PgnGame, players: Players) -> Result<Game, ParseError> {
+        let mut game = Game::from_board(pgn_game.start_board()?, players, None);
+        for (index, move_string) in pgn_game.moves.iter().enumerate() {
+            let (start, end, promotion_piece) = game.resolve_move(move_string)?;
+            game.submit_move(start, end, promotion_piece.as_deref())
+                .map_err(|error| {
+                    ParseError::new(&format!(
+                        "Move {} {} can't be played: {}",
+                        index / 2 + 1,
+                        move_string,
+                        error
+                    ))
+                })?;
+        }
+        game.clock = pgn_game.restore_clock()?;
+        let result = match pgn_game.result() {
+            "1-0" => Some(GameResult::WhiteWin),
+            "0-1" => Some(GameResult::BlackWin),
+            "1/2-1/2" => Some(GameResult::Draw),
+            _ => None,
+        };
+        if let Some(result) = result {
+            game.finish(result, Termination::Unknown);
+        }
+        if game.is_over() {
+            if let Some(clock) = &mut game.clock {
+                clock.stop();
+            }
+        }
+        game.events.clear();
+        Ok(game)
+    }
+
+    /// The game as PGN with the result if it is over
+    pub fn to_pgn(&self, white_name: &str, black_name: &str) -> PgnGame {
+        let mut pgn_game = PgnGame::from_board(
+            &self.start_board,
+            &self.board,
+            white_name,
+            black_name,
+            self.clock.as_ref(),
+        );
+        if let Some(outcome) = self.outcome() {
+            pgn_game.set_tag("Result", outcome.result.to_pgn_string());
+        }
+        pgn_game
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn start_board(&self) -> &Board {
+        &self.start_board
+    }
+
+    pub fn players(&self) -> &Players {
+        &self.players
+    }
+
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    pub fn moves(&self) -> &[GameMove] {
+        &self.moves
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.controller.outcome()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.controller.is_over()
+    }
+
+    /// The color whose draw offer hasn't been answered yet
+    pub fn draw_offer(&self) -> Option<PieceColor> {
+        self.controller.draw_offer()
+    }
+
+    pub fn side_to_move(&self) -> PieceColor {
+        PieceColor::opposite_color(&self.board.last_move_color)
+    }
+
+    /// Who is on move once the given number of moves have been played
+    pub fn side_to_move_after(&self, plies: usize) -> PieceColor {
+        let first_move_color = PieceColor::opposite_color(&self.start_board.last_move_color);
+        match plies % 2 {
+            0 => first_move_color,
+            _ => PieceColor::opposite_color(&first_move_color),
+        }
+    }
+
+    /// The board after the first plies of the game.  The game itself isn't touched
+    pub fn board_after(&self, plies: usize) -> Board {
+        let mut board = self.start_board.clone();
+        for game_move in self.moves.iter().take(plies) {
+            let _ = board.play_move_from_coords(
+                game_move.start_position,
+                game_move.end_position,
+                game_move.promotion_piece.as_deref(),
+            );
+        }
+        board
+    }
+
+    /// Everything that happened since the last time the events were taken
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Finds the squares of a SAN move in the current position
+    pub fn resolve_move(&mut self, move_string: &str) -> Result<ResolvedMove, ParseError> {
+        game::resolve_move(&mut self.board, move_string)
+    }
+
+    /// True when the move is a pawn reaching the last rank, so it can't be played without saying
+    /// what it becomes
+    pub fn needs_promotion_piece(&self, start: (usize, usize), end: (usize, usize)) -> bool {
+        match self.board.parsed_move_from_coords(start, end, None) {
+            Ok(parsed_move) => self.board.needs_promotion_piece(&parsed_move),
+            Err(_) => false,
+        }
+    }
+
+    /// Plays a move for the side on move.  A move the rules don't allow leaves the game as it was
+    pub fn submit_move(
+        &mut self,
+        start: (usize, usize),
+        end: (usize, usize),
+        promotion_piece: Option<&str>,
+    ) -> Result<(), MoveError> {
+        if let Some(outcome) = self.outcome() {
+            return Err(MoveError::new(&format!(
+                "The game is already over. {}",
+                outcome
+            )));
+        }
+        let color = self.side_to_move();
+        let san = san::move_to_san(&self.board, start, end, promotion_piece);
+        // Played on a copy since a move that fails can leave the board half changed
+        let mut board = self.board.clone();
+        board.play_move_from_coords(start, end, promotion_piece)?;
+        self.board = board;
+        let game_move = GameMove {
+            start_position: start,
+            end_position: end,
+            promotion_piece: promotion_piece.map(String::from),
+            san,
+        };
+        self.moves.push(game_move.clone());
+        if let Some(clock) = &mut self.clock {
+            clock.end_turn();
+        }
+        self.events.push(GameEvent::MovePlayed { color, game_move });
+        if self.controller.move_played(color) {
+            self.events.push(GameEvent::DrawDeclined(color));
+        }
+        self.push_clock_update();
+
+        let opponent = PieceColor::opposite_color(&color);
+        if let Some((winner, termination)) = self.board.variant.winner(&self.board) {
+            self.finish(GameResult::win_for(&winner), termination);
+        } else if self.board.variant.is_draw(&self.board) {
+            self.finish(GameResult::Draw, Termination::EighthRankTie);
+        } else if game::is_board_check_mate(&color, &mut self.board) {
+            self.finish(GameResult::win_for(&color), Termination::Checkmate);
+        } else if game::legal_moves(&opponent, &mut self.board).is_empty()
+            && game::legal_drops(&opponent, &mut self.board).is_empty()
+        {
+            self.finish(GameResult::Draw, Termination::Stalemate);
+        } else if game::is_board_in_check(&color, &self.board) {
+            self.events.push(GameEvent::Check(opponent));
+        }
+        Ok(())
+    }
+
+    /// Counts time used by the side on move.  Running out ends the game
+    pub fn tick(&mut self, elapsed: Duration) {
+        if self.is_over() {
+            return;
+        }
+        let result = match &mut self.clock {
+            Some(clock) => {
+                clock.tick(elapsed);
+                game::result_on_time(clock, &self.board)
+            }
+            None => return,
+        };
+        self.push_clock_update();
+        if let Some(result) = result {
+            self.finish(result, Termination::TimeForfeit);
+        }
+    }
+
+    /// Offers a draw for the color.  An offer from both sides is a draw.  Engines can't answer
+    /// through UCI so an engine turns it down straight away
+    pub fn offer_draw(&mut self, color: PieceColor) -> Result<(), ControllerError> {
+        if let Some(outcome) = self.controller.offer_draw(color)? {
+            self.game_over(outcome);
+            return Ok(());
+        }
+        self.events.push(GameEvent::DrawOffered(color));
+        let opponent = PieceColor::opposite_color(&color);
+        if !self.players.get(&opponent).is_human() {
+            self.decline_draw(opponent)?;
+        }
+        Ok(())
+    }
+
+    pub fn accept_draw(&mut self, color: PieceColor) -> Result<(), ControllerError> {
+        let outcome = self.controller.accept_draw(color)?;
+        self.game_over(outcome);
+        Ok(())
+    }
+
+    pub fn decline_draw(&mut self, color: PieceColor) -> Result<(), ControllerError> {
+        self.controller.decline_draw(color)?;
+        self.events.push(GameEvent::DrawDeclined(color));
+        Ok(())
+    }
+
+    pub fn withdraw_draw(&mut self, color: PieceColor) -> Result<(), ControllerError> {
+        self.controller.withdraw_draw(color)?;
+        self.events.push(GameEvent::DrawWithdrawn(color));
+        Ok(())
+    }
+
+    pub fn resign(&mut self, color: PieceColor) -> Result<(), ControllerError> {
+        let outcome = self.controller.resign(color)?;
+        self.game_over(outcome);
+        Ok(())
+    }
+
+    /// Ends the game with the result without playing it out
+    pub fn adjudicate(&mut self, result: GameResult) {
+        self.finish(result, Termination::Adjudicated);
+    }
+
+    /// Ends the game when the color can't go on, like an engine that stopped working
+    pub fn abandon(&mut self, color: PieceColor) {
+        let winner = PieceColor::opposite_color(&color);
+        self.finish(GameResult::win_for(&winner), Termination::Abandoned);
+    }
+
+    /// Takes back the last plies.  Any outcome and draw offer go with them and the clock runs
+    /// for whoever is on move again
+    pub fn take_back(&mut self, plies: usize) -> Result<(), MoveError> {
+        if plies > self.moves.len() {
+            return Err(MoveError::new(&format!(
+                "There are only {} moves to take back",
+                self.moves.len()
+            )));
+        }
+        let kept_plies = self.moves.len() - plies;
+        self.board = self.board_after(kept_plies);
+        self.moves.truncate(kept_plies);
+        self.controller.reset();
+        let side_to_move = self.side_to_move();
+        if let Some(clock) = &mut self.clock {
+            clock.start(side_to_move);
+        }
+        self.push_clock_update();
+        Ok(())
+    }
+
+    fn push_clock_update(&mut self) {
+        if let Some(clock) = &self.clock {
+            self.events.push(GameEvent::ClockUpdate {
+                white: clock.remaining(&PieceColor::WHITE),
+                black: clock.remaining(&PieceColor::BLACK),
+            });
+        }
+    }
+
+    fn finish(&mut self, result: GameResult, termination: Termination) {
+        if !self.is_over() {
+            let outcome = self.controller.finish(result, termination);
+            self.game_over(outcome);
+        }
+    }
+
+    fn game_over(&mut self, outcome: Outcome) {
+        if let Some(clock) = &mut self.clock {
+            clock.stop();
+        }
+        self.events.push(GameEvent::GameOver(outcome));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chess_game::{Game, GameEvent};
+    use crate::clock::TimeControl;
+    use crate::controller::Termination;
+    use crate::parser::GameResult;
+    use crate::pgn::parse_pgn;
+    use crate::piece_types::PieceColor;
+    use crate::player::{Player, Players};
+    use crate::uci::SearchLimit;
+    use std::time::Duration;
+
+    fn play(game: &mut Game, move_string: &str) {
+        let (start, end, promotion_piece) = game.resolve_move(move_string).unwrap();
+        game.submit_move(start, end, promotion_piece.as_deref())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_checkmate_ends_the_game() {
+        let mut game = Game::default();
+        for move_string in ["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6"] {
+            play(&mut game, move_string);
+        }
+        game.drain_events();
+        play(&mut game, "Qxf7#");
+
+        let events = game.drain_events();
+        assert!(matches!(
+            &events[0],
+            GameEvent::MovePlayed { color: PieceColor::WHITE, game_move } if game_move.san == "Qxf7#"
+        ));
+        let outcome = game.outcome().unwrap();
+        assert_eq!(outcome.result, GameResult::WhiteWin);
+        assert_eq!(outcome.termination, Termination::Checkmate);
+        assert_eq!(events.last(), Some(&GameEvent::GameOver(outcome)));
+        assert!(game.submit_move((4, 6), (4, 4), None).is_err());
+
+        game.take_back(1).unwrap();
+        assert!(!game.is_over());
+        assert_eq!(game.moves().len(), 6);
+    }
+
+    #[test]
+    fn test_check_and_bad_moves() {
+        let mut game = Game::default();
+        assert!(game.submit_move((4, 1), (4, 4), None).is_err());
+        assert!(game.drain_events().is_empty());
+        for move_string in ["e4", "f5"] {
+            play(&mut game, move_string);
+        }
+        game.drain_events();
+        play(&mut game, "Qh5+");
+        assert!(game
+            .drain_events()
+            .contains(&GameEvent::Check(PieceColor::BLACK)));
+    }
+
+    #[test]
+    fn test_clock_runs_out() {
+        let time_control = TimeControl::fischer(Duration::from_secs(10), Duration::ZERO);
+        let mut game = Game::new(Players::default(), Some(time_control));
+        game.tick(Duration::from_secs(4));
+        assert_eq!(
+            game.drain_events(),
+            vec![GameEvent::ClockUpdate {
+                white: Duration::from_secs(6),
+                black: Duration::from_secs(10)
+            }]
+        );
+        game.tick(Duration::from_secs(7));
+        let outcome = game.outcome().unwrap();
+        assert_eq!(outcome.termination, Termination::TimeForfeit);
+        assert_eq!(outcome.result, GameResult::BlackWin);
+    }
+
+    #[test]
+    fn test_engines_decline_draws() {
+        let players = Players {
+            white: Player::Human,
+            black: Player::Uci {
+                path: String::from("engine"),
+                limit: Some(SearchLimit::Depth(1)),
+            },
+        };
+        let mut game = Game::new(players, None);
+        game.offer_draw(PieceColor::WHITE).unwrap();
+        assert_eq!(
+            game.drain_events(),
+            vec![
+                GameEvent::DrawOffered(PieceColor::WHITE),
+                GameEvent::DrawDeclined(PieceColor::BLACK)
+            ]
+        );
+        assert_eq!(game.draw_offer(), None);
+
+        game.resign(PieceColor::WHITE).unwrap();
+        assert!(game.offer_draw(PieceColor::WHITE).is_err());
+    }
+
+    #[test]
+    fn test_from_pgn() {
+        let pgn_game = parse_pgn("1. f3 e5 2. g4 Qh4# 0-1").unwrap();
+        let mut game = Game::from_pgn(&pgn_game, Players::default()).unwrap();
+        assert_eq!(game.moves()[3].san, "Qh4#");
+        assert_eq!(game.outcome().unwrap().result, GameResult::BlackWin);
+        assert!(game.drain_events().is_empty());
+        assert_eq!(game.to_pgn("?", "?"), pgn_game);
+        assert_eq!(game.board_after(0).to_fen(), game.start_board().to_fen());
+        assert_eq!(game.board_after(4).to_fen(), game.board().to_fen());
+    }
+}
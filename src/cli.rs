@@ -0,0 +1,671 @@
+use crate::board::Board;
+use crate::board_display::DisplayOptions;
+use crate::chess_game::{Game, GameEvent};
+use crate::clock::{format_duration, TimeControl};
+use crate::engine::Engine;
+use crate::game;
+use crate::parser::ParseError;
+use crate::pgn;
+use crate::piece_types::PieceColor;
+use crate::player::Players;
+use crate::san;
+use crate::uci::EngineError;
+use crate::variant::Variant;
+use rustyline::DefaultEditor;
+use std::fs;
+use std::time::Instant;
+
+const HELP: &str = "Type a move like e4, Nf3, exd5, O-O or e8=Q, or one of these commands:
+  help          show this list
+  board         draw the board
+  moves         list the legal moves
+  undo          take back the last move (and the engine's reply)
+  fen           show the position as FEN
+  pgn           show the game as PGN
+  save <file>   save the game as PGN
+  load <file>   load a game saved as PGN
+  resign        give up the game
+  offer draw    offer the other player a draw.  It stays open until they answer or move
+  accept        accept the other player's draw offer
+  decline       turn down the other player's draw offer
+  withdraw      take back your own draw offer
+  flip          turn the board around
+  hint          ask an engine for a move
+  new           start a new game
+  quit          leave";
+
+/// A line typed at the CLI
+#[derive(Debug, PartialEq)]
+pub enum CliCommand {
+    Help,
+    Board,
+    Moves,
+    Undo,
+    Fen,
+    Pgn,
+    Save(String),
+    Load(String),
+    Resign,
+    OfferDraw,
+    AcceptDraw,
+    DeclineDraw,
+    WithdrawDraw,
+    Flip,
+    Hint,
+    New,
+    Quit,
+    Move(String),
+}
+
+impl CliCommand {
+    /// Reads a command.  None when the line is empty.  A single word that isn't a command is
+    /// taken to be a move
+    pub fn parse(line: &str) -> Result<Option<CliCommand>, ParseError> {
+        let line = line.trim();
+        let mut words = line.split_whitespace();
+        let first_word = match words.next() {
+            Some(first_word) => first_word,
+            None => return Ok(None),
+        };
+        let other_words: Vec<&str> = words.collect();
+        let keyword = first_word.to_ascii_lowercase();
+        // Everything after the keyword so file names can have spaces in them
+        let argument = line[first_word.len()..].trim();
+
+        let command = match (keyword.as_str(), other_words.as_slice()) {
+            ("help" | "?", []) => CliCommand::Help,
+            ("board", []) => CliCommand::Board,
+            ("moves", []) => CliCommand::Moves,
+            ("undo", []) => CliCommand::Undo,
+            ("fen", []) => CliCommand::Fen,
+            ("pgn", []) => CliCommand::Pgn,
+            ("resign", []) => CliCommand::Resign,
+            ("offer", [draw]) | ("draw", [draw]) if draw.eq_ignore_ascii_case("draw") => {
+                CliCommand::OfferDraw
+            }
+            ("draw", []) => CliCommand::OfferDraw,
+            ("accept", []) => CliCommand::AcceptDraw,
+            ("decline", []) => CliCommand::DeclineDraw,
+            ("withdraw", []) => CliCommand::WithdrawDraw,
+            ("accept" | "decline" | "withdraw", [draw]) if draw.eq_ignore_ascii_case("draw") => {
+                match keyword.as_str() {
+                    "accept" => CliCommand::AcceptDraw,
+                    "decline" => CliCommand::DeclineDraw,
+                    _ => CliCommand::WithdrawDraw,
+                }
+            }
+            ("flip", []) => CliCommand::Flip,
+            ("hint", []) => CliCommand::Hint,
+            ("new", []) => CliCommand::New,
+            ("quit" | "exit", []) => CliCommand::Quit,
+            ("save" | "load", []) => {
+                return Err(ParseError::new(&format!("{} needs a file name", keyword)))
+            }
+            ("save", _) => CliCommand::Save(argument.to_string()),
+            ("load", _) => CliCommand::Load(argument.to_string()),
+            (
+                "help" | "?" | "board" | "moves" | "undo" | "fen" | "pgn" | "resign" | "flip"
+                | "hint" | "new" | "quit" | "exit",
+                _,
+            ) => {
+                return Err(ParseError::new(&format!(
+                    "{} doesn't take anything after it",
+                    keyword
+                )))
+            }
+            ("offer" | "draw" | "accept" | "decline" | "withdraw", _) => {
+                return Err(ParseError::new(&format!("Did you mean {} draw?", keyword)));
+            }
+            (_, []) => CliCommand::Move(first_word.to_string()),
+            _ => {
+                return Err(ParseError::new(&format!(
+                    "Unknown command {:?}.  Type help to see the commands",
+                    line
+                )))
+            }
+        };
+        Ok(Some(command))
+    }
+}
+
+/// Where the commands come from.  A person at a terminal or the lines of a script
+enum CommandInput {
+    Interactive(Box<DefaultEditor>),
+    Script(std::vec::IntoIter<String>),
+}
+
+impl CommandInput {
+    // None when there is nothing more to read
+    fn read_line(&mut self, prompt: &str) -> Option<String> {
+        match self {
+            CommandInput::Interactive(editor) => match editor.readline(prompt) {
+                Ok(line) => {
+                    if !line.trim().is_empty() {
+                        let _ = editor.add_history_entry(line.as_str());
+                    }
+                    Some(line)
+                }
+                Err(_) => None,
+            },
+            CommandInput::Script(lines) => {
+                // Echo the line so the output reads like someone typed it
+                let line = lines.next()?;
+                println!("{}{}", prompt, line);
+                Some(line)
+            }
+        }
+    }
+}
+
+// Engines are only given games they can be told the position of
+fn check_engines(engines: [Option<&Engine>; 2], board: &Board) -> Result<(), EngineError> {
+    for engine in engines.into_iter().flatten() {
+        engine.can_play(board)?;
+    }
+    Ok(())
+}
+
+// What goes in the PGN for a player.  Humans don't have a name we know of
+fn player_name(engine: Option<&Engine>) -> String {
+    match engine {
+        Some(engine) => String::from(engine.name()),
+        None => String::from("?"),
+    }
+}
+
+/// A game being played in the terminal
+struct CliGame {
+    time_control: Option<TimeControl>,
+    variant: Variant,
+    display_options: DisplayOptions,
+    white_engine: Option<Engine>,
+    black_engine: Option<Engine>,
+    // A loaded game keeps the names it was saved with
+    white_name: String,
+    black_name: String,
+    game: Game,
+    turn_start: Instant,
+    // Turned around with the flip command
+    flipped: bool,
+}
+
+impl CliGame {
+    fn new(
+        time_control: Option<TimeControl>,
+        players: Players,
+        variant: Variant,
+        display_options: DisplayOptions,
+    ) -> Result<CliGame, EngineError> {
+        let white_engine = Engine::start(&players.white)?;
+        let black_engine = Engine::start(&players.black)?;
+        check_engines(
+            [white_engine.as_ref(), black_engine.as_ref()],
+            &Board::new_variant(variant),
+        )?;
+        let mut cli_game = CliGame {
+            time_control,
+            variant,
+            display_options,
+            white_engine,
+            black_engine,
+            white_name: String::new(),
+            black_name: String::new(),
+            game: Game::new(players, None),
+            turn_start: Instant::now(),
+            flipped: false,
+        };
+        cli_game.new_game();
+        Ok(cli_game)
+    }
+
+    fn new_game(&mut self) {
+        self.white_name = player_name(self.white_engine.as_ref());
+        self.black_name = player_name(self.black_engine.as_ref());
+        self.game = Game::from_board(
+            Board::new_variant(self.variant),
+            self.game.players().clone(),
+            self.time_control.clone(),
+        );
+        self.turn_start = Instant::now();
+        self.print_position();
+    }
+
+    // Draws the board from the side of the human who moves next, or of the one who just moved
+    // when an engine is on move
+    fn print_board(&self) {
+        let board = self.game.board();
+        let next_color = self.game.side_to_move();
+        let seen_from = match self.game.players().get(&next_color).is_human() {
+            true => next_color,
+            false => board.last_move_color,
+        };
+        let display_options = DisplayOptions {
+            flipped: (seen_from == PieceColor::BLACK) != self.flipped,
+            ..self.display_options
+        };
+        println!("{}", board.display(display_options));
+    }
+
+    fn print_position(&self) {
+        self.print_board();
+        if let Some(clock) = self.game.clock() {
+            println!(
+                "White:{} Black:{}",
+                format_duration(clock.remaining(&PieceColor::WHITE)),
+                format_duration(clock.remaining(&PieceColor::BLACK))
+            );
+        }
+    }
+
+    // Prints whatever the game says happened since the last call
+    fn print_events(&mut self) {
+        let mut move_played = false;
+        for event in self.game.drain_events() {
+            match event {
+                GameEvent::MovePlayed { .. } => {
+                    move_played = true;
+                    self.print_position();
+                }
+                GameEvent::Check(color) => println!("{:?} is in check", color),
+                GameEvent::DrawOffered(color) => {
+                    let opponent = PieceColor::opposite_color(&color);
+                    if self.game.players().get(&opponent).is_human() {
+                        println!(
+                            "{:?} offers a draw.  {:?} can answer after this move",
+                            color, opponent
+                        );
+                    }
+                }
+                GameEvent::DrawDeclined(color) => println!("{:?} declines the draw", color),
+                GameEvent::DrawWithdrawn(color) => {
+                    println!("{:?} takes back the draw offer", color)
+                }
+                GameEvent::GameOver(outcome) => println!("{}", outcome),
+                // The clocks are printed with the board
+                GameEvent::ClockUpdate { .. } => (),
+            }
+        }
+        // Remind the side on move of the offer it can answer now
+        if let (true, false, Some(offered_by)) =
+            (move_played, self.game.is_over(), self.game.draw_offer())
+        {
+            if offered_by != self.game.side_to_move() {
+                println!(
+                    "{:?} offers a draw.  Type accept or decline, or make a move to play on",
+                    offered_by
+                );
+            }
+        }
+    }
+
+    fn engine_to_move(&mut self) -> Option<&mut Engine> {
+        match self.game.side_to_move() {
+            PieceColor::WHITE => self.white_engine.as_mut(),
+            PieceColor::BLACK => self.black_engine.as_mut(),
+        }
+    }
+
+    // Counts the time the player on move has used
+    fn tick_clock(&mut self) {
+        self.game.tick(self.turn_start.elapsed());
+        self.turn_start = Instant::now();
+    }
+
+    fn play_human_move(
+        &mut self,
+        move_string: &str,
+        input: &mut CommandInput,
+    ) -> Result<(), String> {
+        let (start, end, mut promotion_piece) = self
+            .game
+            .resolve_move(move_string)
+            .map_err(|error| error.to_string())?;
+        if promotion_piece.is_none() && self.game.needs_promotion_piece(start, end) {
+            let prompt = format!(
+                "Promote to ({})? ",
+                self.game.board().promotion_pieces().join("/")
+            );
+            let answer = input
+                .read_line(&prompt)
+                .ok_or_else(|| String::from("The move needs a promotion piece"))?;
+            promotion_piece = Some(answer.trim().to_ascii_uppercase());
+        }
+
+        // Make sure the move can be played before the clock is charged for it
+        self.game
+            .board()
+            .clone()
+            .play_move_from_coords(start, end, promotion_piece.as_deref())
+            .map_err(|error| error.to_string())?;
+        self.tick_clock();
+        let played = self
+            .game
+            .submit_move(start, end, promotion_piece.as_deref())
+            .map_err(|error| error.to_string());
+        self.print_events();
+        played
+    }
+
+    fn play_engine_move(&mut self) -> Result<(), EngineError> {
+        let side_to_move = self.game.side_to_move();
+        let limit = self
+            .game
+            .players()
+            .get(&side_to_move)
+            .search_limit(self.game.clock(), &side_to_move);
+        let start_board = self.game.start_board().clone();
+        let board = self.game.board().clone();
+        let engine = match self.engine_to_move() {
+            Some(engine) => engine,
+            None => return Ok(()),
+        };
+        let (start, end, promotion_piece) = engine.best_move(&start_board, &board, &limit)?;
+        let engine_name = String::from(engine.name());
+        self.tick_clock();
+        if !self.game.is_over() {
+            self.game
+                .submit_move(start, end, promotion_piece.as_deref())
+                .map_err(|error| EngineError::new(&error.to_string()))?;
+            let san = &self.game.moves()[self.game.moves().len() - 1].san;
+            println!("{} plays {}", engine_name, san);
+        }
+        self.print_events();
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        let moves_played = self.game.moves().len();
+        if moves_played == 0 {
+            return Err(String::from("There are no moves to take back"));
+        }
+        let mut kept_plies = moves_played - 1;
+        // Take back the engine's reply too so it is the human's turn again
+        while kept_plies > 0
+            && !self
+                .game
+                .players()
+                .get(&self.game.side_to_move_after(kept_plies))
+                .is_human()
+        {
+            kept_plies -= 1;
+        }
+        self.game
+            .take_back(moves_played - kept_plies)
+            .map_err(|error| error.to_string())?;
+        self.game.drain_events();
+        self.turn_start = Instant::now();
+        self.print_position();
+        Ok(())
+    }
+
+    fn list_moves(&self) {
+        let mut board = self.game.board().clone();
+        let side_to_move = self.game.side_to_move();
+        let mut moves: Vec<String> = game::legal_moves(&side_to_move, &mut board)
+            .into_iter()
+            .flat_map(|(start, end)| {
+                let promotion_pieces = match self.game.needs_promotion_piece(start, end) {
+                    true => board.promotion_pieces().into_iter().map(Some).collect(),
+                    false => vec![None],
+                };
+                promotion_pieces
+                    .into_iter()
+                    .map(move |promotion_piece| (start, end, promotion_piece))
+            })
+            .map(|(start, end, promotion_piece)| {
+                san::move_to_san(&board, start, end, promotion_piece)
+            })
+            .collect();
+        // Crazyhouse drops
+        for (piece, square) in game::legal_drops(&side_to_move, &mut board.clone()) {
+            moves.push(san::move_to_san(&board, square, square, Some(&piece)));
+        }
+        moves.sort();
+        println!("{}", moves.join(" "));
+    }
+
+    fn hint(&mut self) -> Result<(), String> {
+        let side_to_move = self.game.side_to_move();
+        let start_board = self.game.start_board().clone();
+        let board = self.game.board().clone();
+        let players = self.game.players();
+        let (engine, player) =
+            match (self.white_engine.as_mut(), self.black_engine.as_mut()) {
+                (Some(engine), _) => (engine, &players.white),
+                (None, Some(engine)) => (engine, &players.black),
+                (None, None) => return Err(String::from(
+                    "There is no engine to ask.  Play against one with --white or --black engine",
+                )),
+            };
+        // The engine's own limit, the clock is left out since a hint isn't its turn
+        let limit = player.search_limit(None, &side_to_move);
+        let (start, end, promotion_piece) = engine
+            .best_move(&start_board, &board, &limit)
+            .map_err(|error| error.to_string())?;
+        println!(
+            "{} suggests {}",
+            engine.name(),
+            san::move_to_san(&board, start, end, promotion_piece.as_deref())
+        );
+        Ok(())
+    }
+
+    fn save(&mut self, path: &str) -> Result<(), String> {
+        // Charge the player on move for the time up to now so it is in the saved clock
+        self.tick_clock();
+        let pgn_game = self.game.to_pgn(&self.white_name, &self.black_name);
+        pgn::save_pgn_file(path, &pgn_game).map_err(|error| error.to_string())?;
+        println!("Saved the game to {}", path);
+        self.print_events();
+        Ok(())
+    }
+
+    fn load(&mut self, path: &str) -> Result<(), String> {
+        let pgn_game = pgn::load_pgn_file(path).map_err(|error| error.to_string())?;
+        let game = Game::from_pgn(&pgn_game, self.game.players().clone())
+            .map_err(|error| error.to_string())?;
+        check_engines(
+            [self.white_engine.as_ref(), self.black_engine.as_ref()],
+            game.start_board(),
+        )
+        .map_err(|error| error.to_string())?;
+
+        let first_move_color = PieceColor::opposite_color(&game.start_board().last_move_color);
+        self.white_name = String::from(pgn_game.tag("White").unwrap_or("?"));
+        self.black_name = String::from(pgn_game.tag("Black").unwrap_or("?"));
+        println!(
+            "Loaded {} vs {}: {}",
+            self.white_name,
+            self.black_name,
+            san::numbered_move_list(&pgn_game.moves, first_move_color)
+        );
+        self.game = game;
+        self.turn_start = Instant::now();
+        self.print_position();
+        if let Some(outcome) = self.game.outcome() {
+            println!("{}", outcome);
+        }
+        Ok(())
+    }
+
+    fn run_command(&mut self, command: CliCommand, input: &mut CommandInput) -> Result<(), String> {
+        let game_over = |game: &Game| match game.outcome() {
+            Some(outcome) => Err(format!(
+                "The game is over ({}).  Type new to play again",
+                outcome.result.to_pgn_string()
+            )),
+            None => Ok(()),
+        };
+        let side_to_move = self.game.side_to_move();
+        match command {
+            CliCommand::Help => println!("{}", HELP),
+            CliCommand::Board => self.print_position(),
+            CliCommand::Moves => {
+                game_over(&self.game)?;
+                self.list_moves();
+            }
+            CliCommand::Undo => self.undo()?,
+            CliCommand::Fen => println!("{}", self.game.board().to_fen()),
+            CliCommand::Pgn => print!(
+                "{}",
+                self.game
+                    .to_pgn(&self.white_name, &self.black_name)
+                    .to_pgn()
+            ),
+            CliCommand::Save(path) => self.save(&path)?,
+            CliCommand::Load(path) => self.load(&path)?,
+            CliCommand::Resign => self
+                .game
+                .resign(side_to_move)
+                .map_err(|error| error.to_string())?,
+            CliCommand::OfferDraw => self
+                .game
+                .offer_draw(side_to_move)
+                .map_err(|error| error.to_string())?,
+            CliCommand::AcceptDraw => self
+                .game
+                .accept_draw(side_to_move)
+                .map_err(|error| error.to_string())?,
+            CliCommand::DeclineDraw => self
+                .game
+                .decline_draw(side_to_move)
+                .map_err(|error| error.to_string())?,
+            CliCommand::WithdrawDraw => self
+                .game
+                .withdraw_draw(side_to_move)
+                .map_err(|error| error.to_string())?,
+            CliCommand::Flip => {
+                self.flipped = !self.flipped;
+                self.print_board();
+            }
+            CliCommand::Hint => {
+                game_over(&self.game)?;
+                self.hint()?;
+            }
+            CliCommand::New => self.new_game(),
+            CliCommand::Quit => (),
+            CliCommand::Move(move_string) => {
+                game_over(&self.game)?;
+                self.play_human_move(&move_string, input)?;
+            }
+        }
+        self.print_events();
+        Ok(())
+    }
+}
+
+/// Plays a game in the terminal.  With a script the commands are read from the file instead of
+/// the keyboard and the game stops at the end of it
+pub fn play_game_cli(
+    time_control: Option<TimeControl>,
+    players: Players,
+    variant: Variant,
+    display_options: DisplayOptions,
+    script: Option<&str>,
+) {
+    let mut input = match script {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(script) => CommandInput::Script(
+                script
+                    .lines()
+                    .filter(|line| !line.trim_start().starts_with('#'))
+                    .map(String::from)
+                    .collect::<Vec<String>>()
+                    .into_iter(),
+            ),
+            Err(error) => {
+                println!("Could not read {:?}: {}", path, error);
+                return;
+            }
+        },
+        None => match DefaultEditor::new() {
+            Ok(editor) => CommandInput::Interactive(Box::new(editor)),
+            Err(error) => {
+                println!("Could not read from the terminal: {}", error);
+                return;
+            }
+        },
+    };
+    let mut cli_game = match CliGame::new(time_control, players, variant, display_options) {
+        Ok(cli_game) => cli_game,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+    if script.is_none() {
+        println!("Type help to see the commands");
+    }
+
+    loop {
+        if !cli_game.game.is_over() && cli_game.engine_to_move().is_some() {
+            if let Err(error) = cli_game.play_engine_move() {
+                // Nobody is left to move for that side so it loses
+                println!("{}", error);
+                let side_to_move = cli_game.game.side_to_move();
+                cli_game.game.abandon(side_to_move);
+                cli_game.print_events();
+            }
+            continue;
+        }
+
+        let prompt = match cli_game.game.is_over() {
+            true => String::from("> "),
+            false => format!("{:?} to move> ", cli_game.game.side_to_move()),
+        };
+        let line = match input.read_line(&prompt) {
+            Some(line) => line,
+            None => break,
+        };
+        let command = match CliCommand::parse(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(error) => {
+                println!("{}", error);
+                continue;
+            }
+        };
+        if command == CliCommand::Quit {
+            break;
+        }
+        if let Err(error) = cli_game.run_command(command, &mut input) {
+            println!("{}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::CliCommand;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(CliCommand::parse("  ").unwrap(), None);
+        assert_eq!(CliCommand::parse("HELP").unwrap(), Some(CliCommand::Help));
+        assert_eq!(
+            CliCommand::parse("offer draw").unwrap(),
+            Some(CliCommand::OfferDraw)
+        );
+        assert_eq!(
+            CliCommand::parse("save my games/today.pgn").unwrap(),
+            Some(CliCommand::Save(String::from("my games/today.pgn")))
+        );
+        assert_eq!(
+            CliCommand::parse("Bxe5").unwrap(),
+            Some(CliCommand::Move(String::from("Bxe5")))
+        );
+        // Only whole words are commands
+        assert_eq!(
+            CliCommand::parse("Bxprint").unwrap(),
+            Some(CliCommand::Move(String::from("Bxprint")))
+        );
+    }
+
+    #[test]
+    fn test_bad_commands() {
+        assert!(CliCommand::parse("save").is_err());
+        assert!(CliCommand::parse("undo 2").is_err());
+        assert!(CliCommand::parse("offer tea").is_err());
+        assert!(CliCommand::parse("e4 e5").is_err());
+    }
+}
@@ -0,0 +1,601 @@
+use crate::piece_types::PieceColor;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+// When there is no move count to the next time control the engine guesses this many moves are left
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+// Never plan to use the last bit of the clock.  Moves still take time to get onto the board
+const MOVE_OVERHEAD: Duration = Duration::from_millis(50);
+
+#[derive(Debug)]
+pub struct ClockError {
+    details: String,
+}
+
+impl ClockError {
+    fn new(msg: &str) -> ClockError {
+        ClockError {
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ClockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl Error for ClockError {
+    fn description(&self) -> &str {
+        &self.details
+    }
+}
+
+/// How the bonus time of a stage is handed out
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DelayKind {
+    /// No bonus time at all.  When the clock runs out the player has lost
+    SuddenDeath,
+    /// The increment is added to the player's clock after every move
+    Fischer,
+    /// The time used for a move is given back after the move, but never more than the increment
+    Bronstein,
+    /// US delay.  The clock waits for the increment before it starts counting down
+    Simple,
+}
+
+/// A single period of a time control.  40/90 would be 40 moves with 90 minutes
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TimeStage {
+    pub moves: Option<u32>, // None means the stage lasts for the rest of the game
+    pub base_time: Duration,
+    pub increment: Duration,
+}
+
+impl TimeStage {
+    pub fn new(moves: Option<u32>, base_time: Duration, increment: Duration) -> TimeStage {
+        TimeStage {
+            moves,
+            base_time,
+            increment,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TimeControl {
+    pub stages: Vec<TimeStage>,
+    pub delay_kind: DelayKind,
+}
+
+impl TimeControl {
+    pub fn sudden_death(base_time: Duration) -> TimeControl {
+        TimeControl {
+            stages: vec![TimeStage::new(None, base_time, Duration::ZERO)],
+            delay_kind: DelayKind::SuddenDeath,
+        }
+    }
+
+    pub fn fischer(base_time: Duration, increment: Duration) -> TimeControl {
+        TimeControl {
+            stages: vec![TimeStage::new(None, base_time, increment)],
+            delay_kind: DelayKind::Fischer,
+        }
+    }
+
+    pub fn bronstein(base_time: Duration, delay: Duration) -> TimeControl {
+        TimeControl {
+            stages: vec![TimeStage::new(None, base_time, delay)],
+            delay_kind: DelayKind::Bronstein,
+        }
+    }
+
+    pub fn simple_delay(base_time: Duration, delay: Duration) -> TimeControl {
+        TimeControl {
+            stages: vec![TimeStage::new(None, base_time, delay)],
+            delay_kind: DelayKind::Simple,
+        }
+    }
+
+    /// Parses the over the board way of writing a time control.  Base times are in minutes and
+    /// increments are in seconds.  Stages are separated by ':'
+    ///
+    /// "5" is sudden death, "3+2" is Fischer, "90d5" is a simple delay and "90b5" is Bronstein.
+    /// "40/90+30:30+30" is 40 moves in 90 minutes then 30 minutes for the rest of the game with
+    /// 30 seconds added for every move.
+    pub fn from_string(time_control_string: &str) -> Result<TimeControl, ClockError> {
+        let mut stages = Vec::new();
+        let mut delay_kind = DelayKind::SuddenDeath;
+
+        for stage_string in time_control_string.trim().split(':') {
+            let (moves, rest) = match stage_string.split_once('/') {
+                Some((moves, rest)) => match moves.parse::<u32>() {
+                    Ok(moves) if moves > 0 => (Some(moves), rest),
+                    _ => {
+                        return Err(ClockError::new(&format!(
+                            "Could not parse the move count in the time control {:?}",
+                            stage_string
+                        )))
+                    }
+                },
+                None => (None, stage_string),
+            };
+
+            let (base, increment, stage_kind) = match rest.find(['+', 'd', 'b']) {
+                Some(index) => {
+                    let stage_kind = match &rest[index..index + 1] {
+                        "+" => DelayKind::Fischer,
+                        "d" => DelayKind::Simple,
+                        _ => DelayKind::Bronstein,
+                    };
+                    (&rest[..index], Some(&rest[index + 1..]), stage_kind)
+                }
+                None => (rest, None, DelayKind::SuddenDeath),
+            };
+
+            let base_time = match base.parse::<f64>() {
+                Ok(minutes) if minutes >= 0.0 => Duration::from_secs_f64(minutes * 60.0),
+                _ => {
+                    return Err(ClockError::new(&format!(
+                        "Could not parse the base time in the time control {:?}",
+                        stage_string
+                    )))
+                }
+            };
+            let increment = match increment.map(|seconds| seconds.parse::<f64>()) {
+                None => Duration::ZERO,
+                Some(Ok(seconds)) if seconds >= 0.0 => Duration::from_secs_f64(seconds),
+                Some(_) => {
+                    return Err(ClockError::new(&format!(
+                        "Could not parse the increment in the time control {:?}",
+                        stage_string
+                    )))
+                }
+            };
+
+            if stage_kind != DelayKind::SuddenDeath {
+                if delay_kind != DelayKind::SuddenDeath && delay_kind != stage_kind {
+                    return Err(ClockError::new(
+                        "Every stage of a time control must use the same kind of delay",
+                    ));
+                }
+                delay_kind = stage_kind;
+            }
+            stages.push(TimeStage::new(moves, base_time, increment));
+        }
+
+        if stages.last().unwrap().moves.is_some() {
+            // After the last control the game keeps going.  Repeat the last stage like FIDE does
+            let last_stage = stages.last().unwrap().clone();
+            stages.push(last_stage);
+        }
+
+        Ok(TimeControl { stages, delay_kind })
+    }
+}
+
+// Writes the time control the same way from_string reads it
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut stages = &self.stages[..];
+        // from_string repeats a last stage that has a move count so don't write it twice
+        if let [.., second_last, last] = stages {
+            if last.moves.is_some() && second_last == last {
+                stages = &stages[..stages.len() - 1];
+            }
+        }
+        let delay_character = match self.delay_kind {
+            DelayKind::SuddenDeath => "",
+            DelayKind::Fischer => "+",
+            DelayKind::Bronstein => "b",
+            DelayKind::Simple => "d",
+        };
+
+        let stage_strings: Vec<String> = stages
+            .iter()
+            .map(|stage| {
+                let mut stage_string = String::new();
+                if let Some(moves) = stage.moves {
+                    stage_string.push_str(&format!("{}/", moves));
+                }
+                stage_string.push_str(&(stage.base_time.as_secs_f64() / 60.0).to_string());
+                if !delay_character.is_empty() {
+                    stage_string.push_str(delay_character);
+                    stage_string.push_str(&stage.increment.as_secs_f64().to_string());
+                }
+                stage_string
+            })
+            .collect();
+        write!(f, "{}", stage_strings.join(":"))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PlayerClock {
+    remaining: Duration,
+    moves_made: u32,
+    stage: usize,
+    moves_in_stage: u32,
+    turn_used: Duration,
+}
+
+impl PlayerClock {
+    fn new(time_control: &TimeControl) -> PlayerClock {
+        PlayerClock {
+            remaining: time_control.stages.first().unwrap().base_time,
+            moves_made: 0,
+            stage: 0,
+            moves_in_stage: 0,
+            turn_used: Duration::ZERO,
+        }
+    }
+}
+
+/// A two sided chess clock.  The clock itself does not read the system time.  The front ends
+/// pass in how much time has gone by with tick so the same clock works in the cli, the gui and tests.
+#[derive(Debug, Clone)]
+pub struct Clock {
+    time_control: TimeControl,
+    white: PlayerClock,
+    black: PlayerClock,
+    running: Option<PieceColor>,
+    flagged: Option<PieceColor>,
+}
+
+impl Clock {
+    pub fn new(time_control: TimeControl) -> Clock {
+        Clock {
+            white: PlayerClock::new(&time_control),
+            black: PlayerClock::new(&time_control),
+            time_control,
+            running: None,
+            flagged: None,
+        }
+    }
+
+    pub fn get_time_control(&self) -> &TimeControl {
+        &self.time_control
+    }
+
+    fn player(&self, color: &PieceColor) -> &PlayerClock {
+        match color {
+            PieceColor::WHITE => &self.white,
+            PieceColor::BLACK => &self.black,
+        }
+    }
+
+    fn player_mut(&mut self, color: &PieceColor) -> &mut PlayerClock {
+        match color {
+            PieceColor::WHITE => &mut self.white,
+            PieceColor::BLACK => &mut self.black,
+        }
+    }
+
+    /// Starts the clock for the given color
+    pub fn start(&mut self, color: PieceColor) {
+        if self.flagged.is_some() {
+            return;
+        }
+        self.player_mut(&color).turn_used = Duration::ZERO;
+        self.running = Some(color);
+    }
+
+    pub fn stop(&mut self) {
+        self.running = None;
+    }
+
+    pub fn running(&self) -> Option<PieceColor> {
+        self.running
+    }
+
+    pub fn remaining(&self, color: &PieceColor) -> Duration {
+        self.player(color).remaining
+    }
+
+    /// Manually set a player's time.  This is used when the other side of a protocol tells us the time
+    pub fn set_remaining(&mut self, color: &PieceColor, remaining: Duration) {
+        self.player_mut(color).remaining = remaining;
+    }
+
+    pub fn moves_made(&self, color: &PieceColor) -> u32 {
+        self.player(color).moves_made
+    }
+
+    /// The color that ran out of time, if any
+    pub fn flagged(&self) -> Option<PieceColor> {
+        self.flagged
+    }
+
+    /// Counts elapsed time against the color whose clock is running
+    pub fn tick(&mut self, elapsed: Duration) {
+        let color = match (self.running, self.flagged) {
+            (Some(color), None) => color,
+            _ => return,
+        };
+        let delay_kind = self.time_control.delay_kind;
+        let delay = self.current_stage(&color).increment;
+
+        let player = self.player_mut(&color);
+        let used_before = player.turn_used;
+        player.turn_used += elapsed;
+
+        // With a simple delay the first part of every turn is free
+        let charged = match delay_kind {
+            DelayKind::Simple => {
+                player.turn_used.saturating_sub(delay) - used_before.saturating_sub(delay)
+            }
+            _ => elapsed,
+        };
+
+        if charged >= player.remaining {
+            player.remaining = Duration::ZERO;
+            self.flagged = Some(color);
+            self.running = None;
+        } else {
+            player.remaining -= charged;
+        }
+    }
+
+    /// The player whose clock is running has finished their move.  Any bonus time is added,
+    /// the next time control is reached if needed and the opponent's clock is started
+    pub fn end_turn(&mut self) {
+        let color = match (self.running, self.flagged) {
+            (Some(color), None) => color,
+            _ => return,
+        };
+        let delay_kind = self.time_control.delay_kind;
+        let stage = self.current_stage(&color).clone();
+        let next_stage = self
+            .time_control
+            .stages
+            .get(self.player(&color).stage + 1)
+            .cloned();
+
+        let player = self.player_mut(&color);
+        match delay_kind {
+            DelayKind::Fischer => player.remaining += stage.increment,
+            DelayKind::Bronstein => {
+                player.remaining += Duration::min(player.turn_used, stage.increment)
+            }
+            DelayKind::SuddenDeath | DelayKind::Simple => (),
+        };
+
+        player.moves_made += 1;
+        player.moves_in_stage += 1;
+        if let Some(stage_moves) = stage.moves {
+            if player.moves_in_stage >= stage_moves {
+                // Past the last control in the list the last stage starts over
+                let base_time = match next_stage {
+                    Some(next_stage) => {
+                        player.stage += 1;
+                        next_stage.base_time
+                    }
+                    None => stage.base_time,
+                };
+                player.moves_in_stage = 0;
+                player.remaining += base_time;
+            }
+        }
+
+        self.start(PieceColor::opposite_color(&color));
+    }
+
+    fn current_stage(&self, color: &PieceColor) -> &TimeStage {
+        let stage_index = usize::min(self.player(color).stage, self.time_control.stages.len() - 1);
+        self.time_control.stages.get(stage_index).unwrap()
+    }
+
+    /// How many moves the player has to make before more time is added.  None is the rest of the game
+    pub fn moves_to_go(&self, color: &PieceColor) -> Option<u32> {
+        self.current_stage(color)
+            .moves
+            .map(|moves| moves.saturating_sub(self.player(color).moves_in_stage))
+    }
+
+    /// How long the color can take over the turn that has just started before its flag falls.  A
+    /// simple delay comes before any time is taken off
+    pub fn time_until_flag(&self, color: &PieceColor) -> Duration {
+        match self.time_control.delay_kind {
+            DelayKind::Simple => self.remaining(color) + self.current_stage(color).increment,
+            _ => self.remaining(color),
+        }
+    }
+
+    /// How long an engine playing this color should think about its next move
+    pub fn allocate_think_time(&self, color: &PieceColor) -> Duration {
+        let remaining = self.remaining(color);
+        let moves_to_go = self
+            .moves_to_go(color)
+            .unwrap_or(DEFAULT_MOVES_TO_GO)
+            .max(1);
+
+        let bonus = match self.time_control.delay_kind {
+            DelayKind::SuddenDeath => Duration::ZERO,
+            _ => self.current_stage(color).increment * 3 / 4,
+        };
+
+        let think_time = remaining / moves_to_go + bonus;
+        Duration::min(think_time, remaining.saturating_sub(MOVE_OVERHEAD))
+    }
+}
+
+/// Formats a duration the way a clock face would.  Tenths are shown in the last 10 seconds
+pub fn format_duration(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else if total_seconds < 10 {
+        format!(
+            "{:02}:{:02}.{}",
+            minutes,
+            seconds,
+            duration.subsec_millis() / 100
+        )
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::{format_duration, Clock, DelayKind, TimeControl};
+    use crate::piece_types::PieceColor;
+    use std::time::Duration;
+
+    #[test]
+    fn test_time_control_round_trip() {
+        for time_control_string in ["5", "3+2", "90d5", "90b5", "40/90+30:30+30", "40/120:20/60"] {
+            let time_control = TimeControl::from_string(time_control_string).unwrap();
+            assert_eq!(time_control.to_string(), time_control_string);
+            assert_eq!(
+                TimeControl::from_string(&time_control.to_string()).unwrap(),
+                time_control
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_multi_stage_time_control() {
+        let time_control = TimeControl::from_string("40/90+30:30+30").unwrap();
+        assert_eq!(time_control.delay_kind, DelayKind::Fischer);
+        assert_eq!(time_control.stages.len(), 2);
+        assert_eq!(time_control.stages[0].moves, Some(40));
+        assert_eq!(
+            time_control.stages[0].base_time,
+            Duration::from_secs(90 * 60)
+        );
+        assert_eq!(time_control.stages[1].moves, None);
+        assert_eq!(time_control.stages[1].increment, Duration::from_secs(30));
+
+        assert!(TimeControl::from_string("abc").is_err());
+        assert!(TimeControl::from_string("5+3:5d3").is_err());
+    }
+
+    #[test]
+    fn test_fischer_increment_added_after_move() {
+        let mut clock = Clock::new(TimeControl::fischer(
+            Duration::from_secs(60),
+            Duration::from_secs(2),
+        ));
+        clock.start(PieceColor::WHITE);
+        clock.tick(Duration::from_secs(10));
+        clock.end_turn();
+
+        assert_eq!(clock.remaining(&PieceColor::WHITE), Duration::from_secs(52));
+        assert_eq!(clock.running(), Some(PieceColor::BLACK));
+    }
+
+    #[test]
+    fn test_bronstein_gives_back_at_most_the_delay() {
+        let mut clock = Clock::new(TimeControl::bronstein(
+            Duration::from_secs(60),
+            Duration::from_secs(5),
+        ));
+        clock.start(PieceColor::WHITE);
+        clock.tick(Duration::from_secs(3));
+        clock.end_turn();
+        assert_eq!(clock.remaining(&PieceColor::WHITE), Duration::from_secs(60));
+
+        clock.tick(Duration::from_secs(8));
+        clock.end_turn();
+        assert_eq!(clock.remaining(&PieceColor::BLACK), Duration::from_secs(57));
+    }
+
+    #[test]
+    fn test_simple_delay_waits_before_counting() {
+        let mut clock = Clock::new(TimeControl::simple_delay(
+            Duration::from_secs(60),
+            Duration::from_secs(5),
+        ));
+        clock.start(PieceColor::WHITE);
+        assert_eq!(
+            clock.time_until_flag(&PieceColor::WHITE),
+            Duration::from_secs(65)
+        );
+        clock.tick(Duration::from_secs(3));
+        assert_eq!(clock.remaining(&PieceColor::WHITE), Duration::from_secs(60));
+        clock.tick(Duration::from_secs(4));
+        assert_eq!(clock.remaining(&PieceColor::WHITE), Duration::from_secs(58));
+    }
+
+    #[test]
+    fn test_next_stage_time_added_at_control() {
+        let mut clock = Clock::new(TimeControl::from_string("2/1:1").unwrap());
+        clock.start(PieceColor::WHITE);
+        for _ in 0..4 {
+            clock.tick(Duration::from_secs(1));
+            clock.end_turn();
+        }
+        assert_eq!(
+            clock.remaining(&PieceColor::WHITE),
+            Duration::from_secs(118)
+        );
+        assert_eq!(clock.moves_to_go(&PieceColor::WHITE), None);
+    }
+
+    #[test]
+    fn test_last_stage_repeats_after_the_last_control() {
+        // Time added by move 80 and the moves to go at move 80 and 85
+        for (time_control_string, minutes, moves_to_go) in [
+            ("40/90", 3 * 90, (40, 35)),
+            ("40/120:20/60", 120 + 3 * 60, (20, 15)),
+        ] {
+            let mut clock = Clock::new(TimeControl::from_string(time_control_string).unwrap());
+            clock.start(PieceColor::WHITE);
+            for _ in 0..85 {
+                clock.tick(Duration::from_secs(1));
+                clock.end_turn();
+                clock.tick(Duration::from_secs(1));
+                clock.end_turn();
+                if clock.moves_made(&PieceColor::WHITE) == 80 {
+                    assert_eq!(
+                        clock.remaining(&PieceColor::WHITE),
+                        Duration::from_secs(minutes * 60 - 80)
+                    );
+                    assert_eq!(clock.moves_to_go(&PieceColor::WHITE), Some(moves_to_go.0));
+                }
+            }
+            assert_eq!(clock.moves_to_go(&PieceColor::BLACK), Some(moves_to_go.1));
+            assert!(clock.allocate_think_time(&PieceColor::WHITE) > Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_sudden_death_flags() {
+        let mut clock = Clock::new(TimeControl::sudden_death(Duration::from_secs(1)));
+        clock.start(PieceColor::WHITE);
+        clock.tick(Duration::from_millis(1500));
+
+        assert_eq!(clock.flagged(), Some(PieceColor::WHITE));
+        assert_eq!(clock.remaining(&PieceColor::WHITE), Duration::ZERO);
+        assert_eq!(clock.running(), None);
+    }
+
+    #[test]
+    fn test_allocate_think_time_stays_under_remaining() {
+        let clock = Clock::new(TimeControl::from_string("40/90").unwrap());
+        assert_eq!(
+            clock.allocate_think_time(&PieceColor::WHITE),
+            Duration::from_secs(90 * 60 / 40)
+        );
+
+        let clock = Clock::new(TimeControl::sudden_death(Duration::from_millis(40)));
+        assert_eq!(
+            clock.allocate_think_time(&PieceColor::BLACK),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(3725)), "1:02:05");
+        assert_eq!(format_duration(Duration::from_secs(125)), "02:05");
+        assert_eq!(format_duration(Duration::from_millis(9500)), "00:09.5");
+    }
+}
@@ -0,0 +1,310 @@
+use crate::parser::GameResult;
+use crate::piece_types::PieceColor;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct ControllerError {
+    details: String,
+}
+
+impl ControllerError {
+    pub(crate) fn new(msg: &str) -> ControllerError {
+        ControllerError {
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ControllerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl Error for ControllerError {
+    fn description(&self) -> &str {
+        &self.details
+    }
+}
+
+/// Why a game ended
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Termination {
+    Checkmate,
+    Stalemate,
+    Resignation,
+    DrawAgreed,
+    TimeForfeit,
+    /// A side couldn't go on, like an engine that stopped working
+    Abandoned,
+    /// The third check in Three-check
+    ThreeChecks,
+    /// A king reached the center in King of the Hill
+    KingOfTheHill,
+    /// A king was blown up in Atomic
+    Explosion,
+    /// A side gave away all of its pieces in Antichess
+    NoPiecesLeft,
+    /// A side had no moves in Antichess, which is a win there
+    NoMovesLeft,
+    /// White's pawns were all taken in Horde
+    HordeDestroyed,
+    /// A king got to the eighth rank first in Racing Kings
+    EighthRank,
+    /// Both kings got to the eighth rank in Racing Kings
+    EighthRankTie,
+    /// Called before the end, like an engine match stopping a game that is going nowhere
+    Adjudicated,
+    /// A game that was loaded with a result but no reason
+    Unknown,
+}
+
+/// How a game ended
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Outcome {
+    pub result: GameResult,
+    pub termination: Termination,
+}
+
+impl Outcome {
+    /// How the game ended in words, without the result
+    pub fn reason(&self) -> &'static str {
+        match (self.termination, self.result) {
+            (Termination::Checkmate, GameResult::WhiteWin) => "White wins by checkmate",
+            (Termination::Checkmate, _) => "Black wins by checkmate",
+            (Termination::Resignation, GameResult::WhiteWin) => "Black resigns",
+            (Termination::Resignation, _) => "White resigns",
+            (Termination::TimeForfeit, GameResult::WhiteWin) => "Black ran out of time",
+            (Termination::TimeForfeit, GameResult::BlackWin) => "White ran out of time",
+            (Termination::TimeForfeit, _) => "Time ran out but there is no mate left on the board",
+            (Termination::Abandoned, GameResult::WhiteWin) => "Black abandons the game",
+            (Termination::Abandoned, _) => "White abandons the game",
+            (Termination::ThreeChecks, GameResult::WhiteWin) => "White wins by giving three checks",
+            (Termination::ThreeChecks, _) => "Black wins by giving three checks",
+            (Termination::KingOfTheHill, GameResult::WhiteWin) => "White's king reaches the hill",
+            (Termination::KingOfTheHill, _) => "Black's king reaches the hill",
+            (Termination::Explosion, GameResult::WhiteWin) => "Black's king is blown up",
+            (Termination::Explosion, _) => "White's king is blown up",
+            (Termination::NoPiecesLeft, GameResult::WhiteWin) => "White has lost every piece",
+            (Termination::NoPiecesLeft, _) => "Black has lost every piece",
+            (Termination::NoMovesLeft, GameResult::WhiteWin) => "White has no moves left",
+            (Termination::NoMovesLeft, _) => "Black has no moves left",
+            (Termination::HordeDestroyed, _) => "The horde is wiped out",
+            (Termination::EighthRank, GameResult::WhiteWin) => {
+                "White's king reaches the eighth rank"
+            }
+            (Termination::EighthRank, _) => "Black's king reaches the eighth rank",
+            (Termination::EighthRankTie, _) => "Both kings reach the eighth rank",
+            (Termination::Stalemate, _) => "Stalemate",
+            (Termination::DrawAgreed, _) => "The players agreed to a draw",
+            (Termination::Adjudicated, GameResult::WhiteWin) => "White wins by adjudication",
+            (Termination::Adjudicated, GameResult::BlackWin) => "Black wins by adjudication",
+            (Termination::Adjudicated, _) => "Drawn by adjudication",
+            (Termination::Unknown, _) => "The game is over",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}. {}", self.reason(), self.result.to_pgn_string())
+    }
+}
+
+/// Keeps track of draw offers, resignations and how the game ended.  It doesn't know about the
+/// board, the front ends tell it what happened and ask it what is allowed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameController {
+    // The color whose draw offer hasn't been answered yet
+    draw_offer: Option<PieceColor>,
+    outcome: Option<Outcome>,
+}
+
+impl GameController {
+    pub fn new() -> GameController {
+        GameController::default()
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// The color that offered a draw the other side hasn't answered
+    pub fn draw_offer(&self) -> Option<PieceColor> {
+        self.draw_offer
+    }
+
+    fn check_playing(&self) -> Result<(), ControllerError> {
+        match self.outcome {
+            Some(outcome) => Err(ControllerError::new(&format!(
+                "The game is already over. {}",
+                outcome
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Offers a draw.  If the other side already offered one the draw is agreed
+    pub fn offer_draw(&mut self, color: PieceColor) -> Result<Option<Outcome>, ControllerError> {
+        self.check_playing()?;
+        match self.draw_offer {
+            Some(offered_by) if offered_by == color => Err(ControllerError::new(&format!(
+                "{:?} has already offered a draw",
+                color
+            ))),
+            Some(_) => self.accept_draw(color).map(Some),
+            None => {
+                self.draw_offer = Some(color);
+                Ok(None)
+            }
+        }
+    }
+
+    // The offer the color can answer, which is the one the other side made
+    fn offer_to(&self, color: PieceColor) -> Result<(), ControllerError> {
+        self.check_playing()?;
+        match self.draw_offer {
+            Some(offered_by) if offered_by != color => Ok(()),
+            _ => Err(ControllerError::new(&format!(
+                "{:?} hasn't offered a draw",
+                PieceColor::opposite_color(&color)
+            ))),
+        }
+    }
+
+    pub fn accept_draw(&mut self, color: PieceColor) -> Result<Outcome, ControllerError> {
+        self.offer_to(color)?;
+        self.draw_offer = None;
+        Ok(self.finish(GameResult::Draw, Termination::DrawAgreed))
+    }
+
+    pub fn decline_draw(&mut self, color: PieceColor) -> Result<(), ControllerError> {
+        self.offer_to(color)?;
+        self.draw_offer = None;
+        Ok(())
+    }
+
+    /// Takes back the color's own draw offer
+    pub fn withdraw_draw(&mut self, color: PieceColor) -> Result<(), ControllerError> {
+        self.check_playing()?;
+        if self.draw_offer != Some(color) {
+            return Err(ControllerError::new(&format!(
+                "{:?} has no draw offer to withdraw",
+                color
+            )));
+        }
+        self.draw_offer = None;
+        Ok(())
+    }
+
+    pub fn resign(&mut self, color: PieceColor) -> Result<Outcome, ControllerError> {
+        self.check_playing()?;
+        let winner = PieceColor::opposite_color(&color);
+        Ok(self.finish(GameResult::win_for(&winner), Termination::Resignation))
+    }
+
+    /// Tells the controller the color made a move.  Moving instead of answering turns down the
+    /// other side's draw offer.  Returns true when an offer was turned down that way
+    pub fn move_played(&mut self, color: PieceColor) -> bool {
+        match self.draw_offer {
+            Some(offered_by) if offered_by != color => {
+                self.draw_offer = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Ends the game for a reason found on the board or the clock.  A game that is already over
+    /// keeps the outcome it had
+    pub fn finish(&mut self, result: GameResult, termination: Termination) -> Outcome {
+        if let Some(outcome) = self.outcome {
+            return outcome;
+        }
+        let outcome = Outcome {
+            result,
+            termination,
+        };
+        self.draw_offer = None;
+        self.outcome = Some(outcome);
+        outcome
+    }
+
+    /// Forgets the outcome and any offer, used when moves are taken back
+    pub fn reset(&mut self) {
+        *self = GameController::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::{GameController, Termination};
+    use crate::parser::GameResult;
+    use crate::piece_types::PieceColor;
+
+    #[test]
+    fn test_accepted_draw() {
+        let mut controller = GameController::new();
+        assert_eq!(controller.offer_draw(PieceColor::WHITE).unwrap(), None);
+        assert!(controller.offer_draw(PieceColor::WHITE).is_err());
+        // The side that offered can't accept its own offer
+        assert!(controller.accept_draw(PieceColor::WHITE).is_err());
+
+        let outcome = controller.accept_draw(PieceColor::BLACK).unwrap();
+        assert_eq!(outcome.result, GameResult::Draw);
+        assert_eq!(outcome.termination, Termination::DrawAgreed);
+        assert!(controller.is_over());
+        assert!(controller.resign(PieceColor::WHITE).is_err());
+    }
+
+    #[test]
+    fn test_declined_and_withdrawn_draws() {
+        let mut controller = GameController::new();
+        controller.offer_draw(PieceColor::WHITE).unwrap();
+        controller.decline_draw(PieceColor::BLACK).unwrap();
+        assert_eq!(controller.draw_offer(), None);
+        assert!(controller.decline_draw(PieceColor::BLACK).is_err());
+
+        controller.offer_draw(PieceColor::BLACK).unwrap();
+        assert!(controller.withdraw_draw(PieceColor::WHITE).is_err());
+        controller.withdraw_draw(PieceColor::BLACK).unwrap();
+        assert!(!controller.is_over());
+    }
+
+    #[test]
+    fn test_moving_declines_the_other_sides_offer() {
+        let mut controller = GameController::new();
+        controller.offer_draw(PieceColor::WHITE).unwrap();
+        // White still has to make the move it offered with
+        assert!(!controller.move_played(PieceColor::WHITE));
+        assert_eq!(controller.draw_offer(), Some(PieceColor::WHITE));
+        assert!(controller.move_played(PieceColor::BLACK));
+        assert_eq!(controller.draw_offer(), None);
+    }
+
+    #[test]
+    fn test_offers_from_both_sides_make_a_draw() {
+        let mut controller = GameController::new();
+        controller.offer_draw(PieceColor::WHITE).unwrap();
+        let outcome = controller.offer_draw(PieceColor::BLACK).unwrap().unwrap();
+        assert_eq!(outcome.result, GameResult::Draw);
+    }
+
+    #[test]
+    fn test_resign_and_first_outcome_stays() {
+        let mut controller = GameController::new();
+        let outcome = controller.resign(PieceColor::BLACK).unwrap();
+        assert_eq!(outcome.result, GameResult::WhiteWin);
+        assert_eq!(outcome.to_string(), "Black resigns. 1-0");
+        let outcome = controller.finish(GameResult::BlackWin, Termination::TimeForfeit);
+        assert_eq!(outcome.termination, Termination::Resignation);
+
+        controller.reset();
+        assert!(!controller.is_over());
+    }
+}
@@ -0,0 +1,343 @@
+use crate::board::Board;
+use crate::game::{self, ResolvedMove};
+use crate::piece_types::PieceColor;
+use crate::pieces::AnyPiece;
+use crate::player::Player;
+use crate::uci::{EngineError, Score, SearchLimit, SearchResult, UciEngine};
+use crate::variant::Variant;
+use std::time::Instant;
+
+const BUILT_IN_NAME: &str = "Built-in engine";
+
+// Scores are from the side on move.  A mate is worth this less the plies it takes to get there
+const MATE_SCORE: i32 = 100_000;
+
+// A move time runs out well before a search gets this deep
+const MAX_DEPTH: u32 = 64;
+
+// What the fairy pieces are counted as.  Most of them are somewhere between a rook and a queen
+const FAIRY_VALUE: i32 = 600;
+
+/// Whatever picks the moves for a side that isn't human.  Either an external UCI engine or the
+/// one built into the crate
+pub enum Engine {
+    Uci(UciEngine),
+    /// An alpha-beta search that counts material.  It plays legal chess in every variant but
+    /// isn't strong
+    BuiltIn,
+}
+
+impl Engine {
+    /// Starts the player's engine.  None for a human
+    pub fn start(player: &Player) -> Result<Option<Engine>, EngineError> {
+        match player {
+            Player::Human => Ok(None),
+            Player::Uci { path, .. } => {
+                UciEngine::start(path).map(|engine| Some(Engine::Uci(engine)))
+            }
+            Player::Engine { .. } => Ok(Some(Engine::BuiltIn)),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Engine::Uci(engine) => engine.name(),
+            Engine::BuiltIn => BUILT_IN_NAME,
+        }
+    }
+
+    /// Whether the engine can play a game from the board.  The built-in engine plays on any board
+    pub fn can_play(&self, board: &Board) -> Result<(), EngineError> {
+        match self {
+            Engine::Uci(engine) => engine.can_play(board),
+            Engine::BuiltIn => Ok(()),
+        }
+    }
+
+    /// The engine's move in the board's position, which has to be one played from the start board
+    pub fn search(
+        &mut self,
+        start_board: &Board,
+        board: &Board,
+        limit: &SearchLimit,
+    ) -> Result<SearchResult, EngineError> {
+        match self {
+            Engine::Uci(engine) => engine.search(start_board, board, limit),
+            Engine::BuiltIn => search(board, limit)
+                .ok_or_else(|| EngineError::new("There is no legal move in the position")),
+        }
+    }
+
+    /// Like search but gives up on a UCI engine that hasn't moved by the deadline.  The built-in
+    /// engine keeps to its own limit so it isn't cut off
+    pub fn search_until(
+        &mut self,
+        start_board: &Board,
+        board: &Board,
+        limit: &SearchLimit,
+        deadline: Option<Instant>,
+    ) -> Result<Option<SearchResult>, EngineError> {
+        match self {
+            Engine::Uci(engine) => engine.search_until(start_board, board, limit, deadline),
+            Engine::BuiltIn => self.search(start_board, board, limit).map(Some),
+        }
+    }
+
+    pub fn best_move(
+        &mut self,
+        start_board: &Board,
+        board: &Board,
+        limit: &SearchLimit,
+    ) -> Result<ResolvedMove, EngineError> {
+        self.search(start_board, board, limit)
+            .map(|search_result| search_result.best_move)
+    }
+}
+
+/// How far the built-in search has got.  Sent after every depth it finishes
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchProgress {
+    pub depth: u32,
+    /// Positions looked at so far
+    pub nodes: u64,
+    pub search_result: SearchResult,
+}
+
+/// Searches for the best move of the side on move, one ply deeper at a time until the limit is
+/// reached.  With a move time the depth that was cut short is thrown away.  None when there is no
+/// legal move
+pub fn search(board: &Board, limit: &SearchLimit) -> Option<SearchResult> {
+    search_with_progress(board, limit, |_| {})
+}
+
+/// Like search but tells progress about every depth it finishes
+pub fn search_with_progress(
+    board: &Board,
+    limit: &SearchLimit,
+    mut progress: impl FnMut(&SearchProgress),
+) -> Option<SearchResult> {
+    let (max_depth, deadline) = match limit {
+        SearchLimit::Depth(depth) => (u32::max(*depth, 1), None),
+        SearchLimit::MoveTime(move_time) => (MAX_DEPTH, Some(Instant::now() + *move_time)),
+    };
+    let mut board = board.clone();
+    let mut moves = ordered_moves(&mut board);
+    let mut search_result = SearchResult {
+        best_move: moves.first()?.clone(),
+        score: None,
+    };
+    let mut searcher = Searcher {
+        deadline,
+        stopped: false,
+        nodes: 0,
+    };
+
+    for depth in 1..=max_depth {
+        let mut alpha = -MATE_SCORE;
+        let mut best_index = None;
+        for (index, (start, end, promotion_piece)) in moves.iter().enumerate() {
+            let mut next_board = board.clone();
+            if next_board
+                .play_move_from_coords(*start, *end, promotion_piece.as_deref())
+                .is_err()
+            {
+                continue;
+            }
+            let score = -searcher.negamax(&next_board, depth - 1, 1, -MATE_SCORE, -alpha);
+            if searcher.stopped {
+                break;
+            }
+            if best_index.is_none() || score > alpha {
+                alpha = score;
+                best_index = Some(index);
+            }
+        }
+        if searcher.stopped {
+            break;
+        }
+        let best_index = best_index?;
+        // The best move is looked at first on the next depth
+        let best_move = moves.remove(best_index);
+        moves.insert(0, best_move.clone());
+        search_result = SearchResult {
+            best_move,
+            score: Some(score_from_side_to_move(alpha)),
+        };
+        progress(&SearchProgress {
+            depth,
+            nodes: searcher.nodes,
+            search_result: search_result.clone(),
+        });
+        // Nothing deeper changes a forced mate
+        if alpha.abs() > MATE_SCORE - MAX_DEPTH as i32 {
+            break;
+        }
+    }
+    Some(search_result)
+}
+
+struct Searcher {
+    deadline: Option<Instant>,
+    stopped: bool,
+    nodes: u64,
+}
+
+impl Searcher {
+    // The score of the board for the side on move, searched depth plies deep.  ply is how far the
+    // board is from the root so quicker mates score higher
+    fn negamax(&mut self, board: &Board, depth: u32, ply: i32, mut alpha: i32, beta: i32) -> i32 {
+        if matches!(self.deadline, Some(deadline) if Instant::now() >= deadline) {
+            self.stopped = true;
+            return 0;
+        }
+        self.nodes += 1;
+        let side_to_move = PieceColor::opposite_color(&board.last_move_color);
+        if let Some((winner, _)) = board.variant.winner(board) {
+            return match winner == side_to_move {
+                true => MATE_SCORE - ply,
+                false => ply - MATE_SCORE,
+            };
+        }
+        if board.variant.is_draw(board) || board.halfmove_clock >= 100 {
+            return 0;
+        }
+        if depth == 0 {
+            return evaluate(board);
+        }
+
+        let mut board = board.clone();
+        let moves = ordered_moves(&mut board);
+        if moves.is_empty() {
+            return match game::is_board_in_check(&board.last_move_color, &board) {
+                true => ply - MATE_SCORE,
+                false => 0,
+            };
+        }
+        for (start, end, promotion_piece) in moves {
+            let mut next_board = board.clone();
+            if next_board
+                .play_move_from_coords(start, end, promotion_piece.as_deref())
+                .is_err()
+            {
+                continue;
+            }
+            let score = -self.negamax(&next_board, depth - 1, ply + 1, -beta, -alpha);
+            if self.stopped {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = i32::max(alpha, score);
+        }
+        alpha
+    }
+}
+
+// The legal moves with captures and then promotions first, since they are the ones most likely
+// to cut the search short
+fn ordered_moves(board: &mut Board) -> Vec<ResolvedMove> {
+    let mut moves = game::legal_plies(board);
+    moves.sort_by_key(|(start, end, promotion_piece)| {
+        if start != end && game::is_capture(board, *start, *end) {
+            0
+        } else if promotion_piece.is_some() {
+            1
+        } else {
+            2
+        }
+    });
+    moves
+}
+
+// Material for the side on move less the other side's.  In Antichess having less is better
+fn evaluate(board: &Board) -> i32 {
+    let material = |pieces: &Vec<AnyPiece>| -> i32 {
+        pieces
+            .iter()
+            .map(|piece| match piece {
+                AnyPiece::Pawn(_) => 100,
+                AnyPiece::Knight(_) | AnyPiece::Bishop(_) => 300,
+                AnyPiece::Rook(_) => 500,
+                AnyPiece::Queen(_) => 900,
+                AnyPiece::Fairy(_) => FAIRY_VALUE,
+                AnyPiece::King(_) => 0,
+            })
+            .sum()
+    };
+    let white = material(&board.live_white_pieces) - material(&board.live_black_pieces);
+    let score = match board.last_move_color {
+        PieceColor::BLACK => white,
+        PieceColor::WHITE => -white,
+    };
+    match board.variant {
+        Variant::Antichess => -score,
+        _ => score,
+    }
+}
+
+// Mates are given in moves the way UCI engines report them
+fn score_from_side_to_move(score: i32) -> Score {
+    let mate_plies = MATE_SCORE - score.abs();
+    if mate_plies > MAX_DEPTH as i32 {
+        Score::Centipawns(score)
+    } else if score > 0 {
+        Score::Mate((mate_plies + 1) / 2)
+    } else {
+        Score::Mate(-mate_plies / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::engine::{search, search_with_progress};
+    use crate::uci::{Score, SearchLimit};
+    use std::time::Duration;
+
+    #[test]
+    fn test_finds_mate_in_one() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let search_result = search(&board, &SearchLimit::Depth(2)).unwrap();
+        assert_eq!(search_result.best_move, ((0, 0), (0, 7), None));
+        assert_eq!(search_result.score, Some(Score::Mate(1)));
+    }
+
+    #[test]
+    fn test_takes_a_hanging_queen() {
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+        let search_result = search(&board, &SearchLimit::Depth(1)).unwrap();
+        assert_eq!(search_result.best_move, ((3, 1), (3, 4), None));
+        assert_eq!(search_result.score, Some(Score::Centipawns(500)));
+    }
+
+    #[test]
+    fn test_progress_after_every_depth() {
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+        let mut depths = Vec::new();
+        let search_result = search_with_progress(&board, &SearchLimit::Depth(3), |progress| {
+            assert!(progress.nodes > 0);
+            depths.push(progress.depth);
+        });
+        assert_eq!(depths, vec![1, 2, 3]);
+        assert_eq!(search_result.unwrap().best_move, ((3, 1), (3, 4), None));
+    }
+
+    #[test]
+    fn test_move_time_still_gives_a_move() {
+        let board = Board::new();
+        let search_result = search(&board, &SearchLimit::MoveTime(Duration::ZERO)).unwrap();
+        assert!(board
+            .clone()
+            .play_move_from_coords(
+                search_result.best_move.0,
+                search_result.best_move.1,
+                search_result.best_move.2.as_deref()
+            )
+            .is_ok());
+
+        // Mated, so there is nothing to play
+        let board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        assert_eq!(search(&board, &SearchLimit::Depth(3)), None);
+    }
+}
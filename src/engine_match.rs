@@ -0,0 +1,494 @@
+use crate::board::Board;
+use crate::chess_game::Game;
+use crate::clock::TimeControl;
+use crate::controller::Termination;
+use crate::engine::Engine;
+use crate::epd;
+use crate::game;
+use crate::parser::{GameResult, ParseError};
+use crate::pgn::{self, PgnGame};
+use crate::piece_types::PieceColor;
+use crate::player::{Player, Players};
+use crate::sprt::{Pentanomial, Sprt, SprtDecision};
+use crate::uci::EngineError;
+use std::fmt;
+use std::fs;
+use std::time::{Duration, Instant};
+
+// A game still going after this many plies is called a draw
+const MAX_GAME_PLIES: usize = 600;
+
+// The fifty move rule counts plies
+const FIFTY_MOVE_PLIES: u32 = 100;
+
+const DEFAULT_GAMES: usize = 10;
+
+// 95% of a normal distribution is inside this many standard deviations
+const CONFIDENCE_DEVIATIONS: f64 = 1.96;
+
+/// An engine resigns once its own score has been at or below -score for moves of its moves in a
+/// row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResignRule {
+    pub score: i32,
+    pub moves: u32,
+}
+
+/// From move number after_move on, a game is a draw once both engines have scored it within score
+/// of level for moves moves each
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawRule {
+    pub after_move: u32,
+    pub score: i32,
+    pub moves: u32,
+}
+
+/// How every game of a match is played
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchSettings {
+    pub time_control: Option<TimeControl>,
+    pub resign: Option<ResignRule>,
+    pub draw: Option<DrawRule>,
+}
+
+/// What chess match was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchOptions {
+    pub engines: Vec<Player>,
+    pub games: usize,
+    /// An EPD or PGN file of positions to start the games from
+    pub openings: Option<String>,
+    /// Where the games get written
+    pub pgn: Option<String>,
+    pub settings: MatchSettings,
+    /// Stops the match once the test is decided, games is then the most that get played
+    pub sprt: Option<Sprt>,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        MatchOptions {
+            engines: Vec::new(),
+            games: DEFAULT_GAMES,
+            openings: None,
+            pgn: None,
+            settings: MatchSettings::default(),
+            sprt: None,
+        }
+    }
+}
+
+/// The first engine's wins, losses and draws
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchScore {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl MatchScore {
+    /// Adds a game the first engine got points from, 1, 0.5 or 0
+    pub fn add(&mut self, points: f64) {
+        if points > 0.75 {
+            self.wins += 1;
+        } else if points < 0.25 {
+            self.losses += 1;
+        } else {
+            self.draws += 1;
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+
+    /// The share of the points the first engine got
+    pub fn ratio(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64
+    }
+
+    /// The first engine's Elo over the second and the margin of error at 95% confidence.  A
+    /// match with no losses or no wins has an infinite difference
+    pub fn elo_difference(&self) -> (f64, f64) {
+        let games = self.games() as f64;
+        if games == 0.0 {
+            return (0.0, 0.0);
+        }
+        let ratio = self.ratio();
+        let variance = (self.wins as f64 * (1.0 - ratio).powi(2)
+            + self.draws as f64 * (0.5 - ratio).powi(2)
+            + self.losses as f64 * ratio.powi(2))
+            / games;
+        let deviation = (variance / games).sqrt();
+        let margin = (elo_from_ratio(ratio + CONFIDENCE_DEVIATIONS * deviation)
+            - elo_from_ratio(ratio - CONFIDENCE_DEVIATIONS * deviation))
+            / 2.0;
+        (elo_from_ratio(ratio), margin)
+    }
+}
+
+impl fmt::Display for MatchScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} - {} - {} [{:.3}] {}",
+            self.wins,
+            self.losses,
+            self.draws,
+            self.ratio(),
+            self.games()
+        )
+    }
+}
+
+/// The Elo difference that makes the stronger side expect that share of the points
+pub fn elo_from_ratio(ratio: f64) -> f64 {
+    400.0 * (ratio / (1.0 - ratio)).log10()
+}
+
+/// Reads the positions in an EPD file, or the final positions of the games in a PGN file
+pub fn load_openings(path: &str) -> Result<Vec<Board>, ParseError> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| ParseError::new(&format!("Could not read {:?}: {}", path, error)))?;
+    let openings = if path.to_ascii_lowercase().ends_with(".pgn") {
+        pgn::parse_pgn_games(&text)?
+            .iter()
+            .map(|pgn_game| pgn_game.replay().map(|(board, _)| board))
+            .collect::<Result<Vec<Board>, ParseError>>()?
+    } else {
+        epd::parse_epd_suite(&text)
+            .into_iter()
+            .map(|position| position?.board())
+            .collect::<Result<Vec<Board>, ParseError>>()?
+    };
+    if openings.is_empty() {
+        return Err(ParseError::new(&format!(
+            "There are no openings in {:?}",
+            path
+        )));
+    }
+    Ok(openings)
+}
+
+/// Two engines playing each other.  Every opening is played twice in a row with the colors
+/// swapped, and the openings go round again when there are more games than openings
+pub struct EngineMatch {
+    players: [Player; 2],
+    engines: [Engine; 2],
+    openings: Vec<Board>,
+    settings: MatchSettings,
+    games: Vec<PgnGame>,
+    // The first engine's points in each game played
+    results: Vec<f64>,
+}
+
+impl EngineMatch {
+    /// Starts both engines and makes sure they can play every opening.  No openings means the
+    /// starting position
+    pub fn start(
+        players: [Player; 2],
+        openings: Vec<Board>,
+        settings: MatchSettings,
+    ) -> Result<EngineMatch, EngineError> {
+        let start_engine = |player: &Player| {
+            Engine::start(player)?.ok_or_else(|| {
+                EngineError::new(
+                    "A match is played between two engines.  Use engine or uci:<path to engine>",
+                )
+            })
+        };
+        let engines = [start_engine(&players[0])?, start_engine(&players[1])?];
+        let openings = if openings.is_empty() {
+            vec![Board::new()]
+        } else {
+            openings
+        };
+        for engine in &engines {
+            for opening in &openings {
+                engine.can_play(opening)?;
+            }
+        }
+        Ok(EngineMatch {
+            players,
+            engines,
+            openings,
+            settings,
+            games: Vec::new(),
+            results: Vec::new(),
+        })
+    }
+
+    pub fn names(&self) -> [&str; 2] {
+        [self.engines[0].name(), self.engines[1].name()]
+    }
+
+    pub fn games(&self) -> &[PgnGame] {
+        &self.games
+    }
+
+    /// The first engine's points in each game played
+    pub fn results(&self) -> &[f64] {
+        &self.results
+    }
+
+    pub fn score(&self) -> MatchScore {
+        let mut score = MatchScore::default();
+        for points in &self.results {
+            score.add(*points);
+        }
+        score
+    }
+
+    /// The game pairs played so far
+    pub fn pentanomial(&self) -> Pentanomial {
+        Pentanomial::from_results(&self.results)
+    }
+
+    /// Score of A vs B: 3 - 1 - 2 [0.667] 6 and the Elo difference under it
+    pub fn summary(&self) -> String {
+        let score = self.score();
+        let (elo, margin) = score.elo_difference();
+        format!(
+            "Score of {} vs {}: {}\nElo difference: {:.1} +/- {:.1}",
+            self.names()[0],
+            self.names()[1],
+            score,
+            elo,
+            margin
+        )
+    }
+
+    /// Plays the next game.  The first engine has White in the first game of every pair.
+    /// Returns the first engine's points
+    pub fn play_next_game(&mut self) -> f64 {
+        let round = self.results.len();
+        let opening = self.openings[round / 2 % self.openings.len()].clone();
+        let first_is_white = round.is_multiple_of(2);
+        // Which engine plays each color
+        let white = if first_is_white { 0 } else { 1 };
+        let engine_of = |color: &PieceColor| match color {
+            PieceColor::WHITE => white,
+            PieceColor::BLACK => 1 - white,
+        };
+        let players = Players {
+            white: self.players[white].clone(),
+            black: self.players[1 - white].clone(),
+        };
+        let mut game = Game::from_board(opening, players, self.settings.time_control.clone());
+        // Moves in a row each engine thought it was lost, and plies in a row that were level
+        let mut losing_moves = [0u32; 2];
+        let mut level_plies = 0u32;
+
+        while !game.is_over() {
+            let mut board = game.board().clone();
+            if game.moves().len() >= MAX_GAME_PLIES
+                || board.halfmove_clock >= FIFTY_MOVE_PLIES
+                || game::is_board_draw_by_repetition(&mut board)
+            {
+                game.adjudicate(GameResult::Draw);
+                break;
+            }
+            let color = game.side_to_move();
+            let index = engine_of(&color);
+            let limit = game
+                .players()
+                .get(&color)
+                .search_limit(game.clock(), &color);
+            let think_start = Instant::now();
+            // With a clock an engine that hangs loses on time instead of holding up the match
+            let deadline = game
+                .clock()
+                .map(|clock| think_start + clock.time_until_flag(&color));
+            let search = self.engines[index].search_until(
+                game.start_board(),
+                game.board(),
+                &limit,
+                deadline,
+            );
+            game.tick(think_start.elapsed());
+            if game.is_over() {
+                break;
+            }
+            let search_result = match search {
+                Ok(Some(search_result)) => search_result,
+                // Past the deadline is past the flag, this only makes sure of it
+                Ok(None) => {
+                    let time_left = game
+                        .clock()
+                        .map_or(Duration::ZERO, |clock| clock.time_until_flag(&color));
+                    game.tick(time_left);
+                    break;
+                }
+                Err(error) => {
+                    println!("{}", error);
+                    game.abandon(color);
+                    break;
+                }
+            };
+            let (start, end, promotion_piece) = search_result.best_move;
+            if let Err(error) = game.submit_move(start, end, promotion_piece.as_deref()) {
+                println!(
+                    "{} played an illegal move: {}",
+                    self.engines[index].name(),
+                    error
+                );
+                game.abandon(color);
+                break;
+            }
+            if game.is_over() {
+                break;
+            }
+            // Engines that don't give a score are never adjudicated
+            let score = match search_result.score {
+                Some(score) => score.centipawns(),
+                None => {
+                    losing_moves[index] = 0;
+                    level_plies = 0;
+                    continue;
+                }
+            };
+            if let Some(resign) = self.settings.resign {
+                if score <= -resign.score {
+                    losing_moves[index] += 1;
+                } else {
+                    losing_moves[index] = 0;
+                }
+                if losing_moves[index] >= resign.moves {
+                    let _ = game.resign(color);
+                    break;
+                }
+            }
+            if let Some(draw) = self.settings.draw {
+                if game.board().fullmove_number > draw.after_move && score.abs() <= draw.score {
+                    level_plies += 1;
+                } else {
+                    level_plies = 0;
+                }
+                if level_plies >= 2 * draw.moves {
+                    game.adjudicate(GameResult::Draw);
+                }
+            }
+        }
+        game.drain_events();
+
+        let names = self.names();
+        let mut pgn_game = game.to_pgn(names[white], names[1 - white]);
+        pgn_game.set_tag("Event", "Engine match");
+        pgn_game.set_tag("Round", &(round + 1).to_string());
+        let outcome = game.outcome().unwrap();
+        let termination = match outcome.termination {
+            Termination::Adjudicated => "adjudication",
+            Termination::TimeForfeit => "time forfeit",
+            Termination::Abandoned => "abandoned",
+            _ => "normal",
+        };
+        pgn_game.set_tag("Termination", termination);
+        let points = match (outcome.result, first_is_white) {
+            (GameResult::Draw, _) => 0.5,
+            (GameResult::WhiteWin, true) | (GameResult::BlackWin, false) => 1.0,
+            _ => 0.0,
+        };
+        println!(
+            "Game {}: {} vs {}: {}",
+            round + 1,
+            names[white],
+            names[1 - white],
+            outcome
+        );
+        self.games.push(pgn_game);
+        self.results.push(points);
+        points
+    }
+}
+
+/// Plays a match for chess match, printing each game as it ends and the score at the end.  The
+/// PGN file is written again after every game so a match that gets stopped keeps its games
+pub fn run_match(options: &MatchOptions) -> Result<MatchScore, String> {
+    let players: [Player; 2] = match options.engines.as_slice() {
+        [first, second] => [first.clone(), second.clone()],
+        _ => return Err(String::from("A match needs two --engine options")),
+    };
+    let openings = match &options.openings {
+        Some(path) => load_openings(path).map_err(|error| error.to_string())?,
+        None => Vec::new(),
+    };
+    let mut engine_match = EngineMatch::start(players, openings, options.settings.clone())
+        .map_err(|error| error.to_string())?;
+    println!(
+        "Started {} vs {}",
+        engine_match.names()[0],
+        engine_match.names()[1]
+    );
+    for _ in 0..options.games {
+        engine_match.play_next_game();
+        if let Some(path) = &options.pgn {
+            let pgn_text: Vec<String> = engine_match
+                .games()
+                .iter()
+                .map(|pgn_game| pgn_game.to_pgn())
+                .collect();
+            fs::write(path, pgn_text.join("\n"))
+                .map_err(|error| format!("Could not write {:?}: {}", path, error))?;
+        }
+        if let Some(sprt) = &options.sprt {
+            if engine_match.results().len().is_multiple_of(2) {
+                let llr = sprt.llr(&engine_match.pentanomial());
+                let (lower, upper) = sprt.bounds();
+                let decision = sprt.decision(llr);
+                println!(
+                    "SPRT [{}, {}]: LLR {:.2} ({:.2}, {:.2}) {}",
+                    sprt.elo0, sprt.elo1, llr, lower, upper, decision
+                );
+                if decision != SprtDecision::Undecided {
+                    break;
+                }
+            }
+        }
+    }
+    println!("{}", engine_match.summary());
+    Ok(engine_match.score())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine_match::{elo_from_ratio, MatchScore};
+
+    #[test]
+    fn test_elo_difference() {
+        assert_eq!(elo_from_ratio(0.5), 0.0);
+        assert!((elo_from_ratio(0.75) - 190.85).abs() < 0.01);
+        assert!((elo_from_ratio(0.25) + 190.85).abs() < 0.01);
+
+        let mut score = MatchScore::default();
+        for points in [1.0, 1.0, 0.0, 0.5, 1.0, 0.5, 1.0, 0.0] {
+            score.add(points);
+        }
+        assert_eq!(
+            score,
+            MatchScore {
+                wins: 4,
+                losses: 2,
+                draws: 2
+            }
+        );
+        assert_eq!(score.to_string(), "4 - 2 - 2 [0.625] 8");
+
+        let score = MatchScore {
+            wins: 10,
+            losses: 5,
+            draws: 5,
+        };
+        let (elo, margin) = score.elo_difference();
+        assert!((elo - 88.74).abs() < 0.01);
+        assert!((margin - 143.9).abs() < 0.1);
+
+        // Nothing lost is as far ahead as it gets
+        let score = MatchScore {
+            wins: 2,
+            losses: 0,
+            draws: 0,
+        };
+        assert_eq!(score.elo_difference().0, f64::INFINITY);
+        assert_eq!(MatchScore::default().elo_difference(), (0.0, 0.0));
+    }
+}
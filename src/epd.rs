@@ -0,0 +1,237 @@
+use crate::board::Board;
+use crate::engine::Engine;
+use crate::game;
+use crate::parser::ParseError;
+use crate::piece_types::PieceColor;
+use crate::player::Player;
+use crate::san;
+use crate::uci::SearchLimit;
+use std::collections::HashMap;
+use std::fs;
+
+/// A single line of an EPD test suite
+#[derive(Debug, PartialEq)]
+pub struct EpdPosition {
+    pub fen: String,
+    pub best_moves: Vec<String>,
+    pub avoid_moves: Vec<String>,
+    pub id: Option<String>,
+    pub comment: Option<String>,
+    pub other_operations: HashMap<String, Vec<String>>,
+}
+
+impl EpdPosition {
+    pub fn board(&self) -> Result<Board, ParseError> {
+        Board::from_fen(&self.fen)
+    }
+
+    /// A move passes when it is one of the bm moves (if there are any) and none of the am moves.
+    /// Moves are compared by the squares they use so Nbd7 and Nd7 are the same move
+    pub fn is_solved_by(&self, move_string: &str) -> Result<bool, ParseError> {
+        let mut board = self.board()?;
+        let played = game::resolve_move(&mut board, move_string)?;
+
+        let mut is_best_move = self.best_moves.is_empty();
+        for best_move in &self.best_moves {
+            if game::resolve_move(&mut board, best_move)? == played {
+                is_best_move = true;
+            }
+        }
+        for avoid_move in &self.avoid_moves {
+            if game::resolve_move(&mut board, avoid_move)? == played {
+                return Ok(false);
+            }
+        }
+        Ok(is_best_move)
+    }
+}
+
+/// Splits the operation section of an EPD line into opcodes and their operands.
+/// Quoted operands can contain spaces and semicolons
+fn parse_operations(operation_string: &str) -> Result<Vec<(String, Vec<String>)>, ParseError> {
+    let mut operations = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current_token = String::new();
+    let mut in_quotes = false;
+
+    for character in operation_string.chars() {
+        match character {
+            '"' if in_quotes => {
+                tokens.push(current_token.clone());
+                current_token.clear();
+                in_quotes = false;
+            }
+            '"' => in_quotes = true,
+            _ if in_quotes => current_token.push(character),
+            ' ' | '\t' | ';' => {
+                if !current_token.is_empty() {
+                    tokens.push(current_token.clone());
+                    current_token.clear();
+                }
+                if character == ';' {
+                    if let Some((opcode, operands)) = tokens.split_first() {
+                        operations.push((opcode.clone(), operands.to_vec()));
+                    }
+                    tokens.clear();
+                }
+            }
+            _ => current_token.push(character),
+        }
+    }
+
+    if in_quotes {
+        return Err(ParseError::new(&format!(
+            "Unterminated string in the EPD operations {:?}",
+            operation_string
+        )));
+    }
+    if !current_token.is_empty() {
+        tokens.push(current_token);
+    }
+    if let Some((opcode, operands)) = tokens.split_first() {
+        operations.push((opcode.clone(), operands.to_vec()));
+    }
+    Ok(operations)
+}
+
+pub fn parse_epd_line(epd_line: &str) -> Result<EpdPosition, ParseError> {
+    let epd_line = epd_line.trim();
+    let mut fields = epd_line.splitn(5, char::is_whitespace);
+    let fen_fields: Vec<&str> = (&mut fields).take(4).collect();
+    if fen_fields.len() < 4 {
+        return Err(ParseError::new(&format!(
+            "An EPD line needs at least 4 fields: {:?}",
+            epd_line
+        )));
+    }
+    let fen = fen_fields.join(" ");
+    // Catch a bad position now instead of when the suite is run
+    Board::from_fen(&fen)?;
+
+    let mut epd_position = EpdPosition {
+        fen,
+        best_moves: Vec::new(),
+        avoid_moves: Vec::new(),
+        id: None,
+        comment: None,
+        other_operations: HashMap::new(),
+    };
+
+    for (opcode, operands) in parse_operations(fields.next().unwrap_or(""))? {
+        match opcode.as_str() {
+            "bm" => epd_position.best_moves = operands,
+            "am" => epd_position.avoid_moves = operands,
+            "id" => epd_position.id = operands.into_iter().next(),
+            "c0" => epd_position.comment = operands.into_iter().next(),
+            _ => {
+                epd_position.other_operations.insert(opcode, operands);
+            }
+        }
+    }
+    Ok(epd_position)
+}
+
+/// Parses every position in an EPD file.  Blank lines and lines starting with # are skipped
+pub fn parse_epd_suite(suite_string: &str) -> Vec<Result<EpdPosition, ParseError>> {
+    suite_string
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_epd_line)
+        .collect()
+}
+
+/// Runs chess epd.  The engine searches every position of the suite and each one is printed as
+/// it passes or fails its bm and am tests.  Without a limit the player's own is used.  Returns how
+/// many positions passed and how many were tried
+pub fn run_suite(
+    path: &str,
+    player: &Player,
+    limit: Option<SearchLimit>,
+) -> Result<(usize, usize), String> {
+    let suite_string = fs::read_to_string(path)
+        .map_err(|error| format!("Could not read {:?}: {}", path, error))?;
+    let mut engine = match Engine::start(player).map_err(|error| error.to_string())? {
+        Some(engine) => engine,
+        None => {
+            return Err(String::from(
+                "An EPD suite needs an engine.  Use --engine engine or uci:<path to engine>",
+            ))
+        }
+    };
+    let mut passed = 0;
+    let mut tried = 0;
+    for (index, position) in parse_epd_suite(&suite_string).into_iter().enumerate() {
+        let position = match position {
+            Ok(position) => position,
+            Err(error) => {
+                println!("{}: skipped, {}", index + 1, error);
+                continue;
+            }
+        };
+        let name = position
+            .id
+            .clone()
+            .unwrap_or_else(|| (index + 1).to_string());
+        match run_position(&position, &mut engine, player, limit) {
+            Ok((move_san, true)) => {
+                passed += 1;
+                println!(
+                    "{}: passed with {}{}",
+                    name,
+                    move_san,
+                    tests_string(&position)
+                );
+            }
+            Ok((move_san, false)) => {
+                println!(
+                    "{}: failed with {}{}",
+                    name,
+                    move_san,
+                    tests_string(&position)
+                )
+            }
+            Err(error) => println!("{}: failed, {}", name, error),
+        }
+        tried += 1;
+    }
+    println!("Passed {} of {}", passed, tried);
+    Ok((passed, tried))
+}
+
+// Gets the engine's move for the position in SAN and whether it passes
+fn run_position(
+    position: &EpdPosition,
+    engine: &mut Engine,
+    player: &Player,
+    limit: Option<SearchLimit>,
+) -> Result<(String, bool), String> {
+    let board = position.board().map_err(|error| error.to_string())?;
+    engine.can_play(&board).map_err(|error| error.to_string())?;
+    let color = PieceColor::opposite_color(&board.last_move_color);
+    let limit = limit.unwrap_or_else(|| player.search_limit(None, &color));
+    let (start, end, promotion_piece) = engine
+        .best_move(&board, &board, &limit)
+        .map_err(|error| error.to_string())?;
+    let move_san = san::move_to_san(&board, start, end, promotion_piece.as_deref());
+    let is_solved = position
+        .is_solved_by(&move_san)
+        .map_err(|error| format!("{} isn't a legal move: {}", move_san, error))?;
+    Ok((move_san, is_solved))
+}
+
+// " (bm Qg6 am Qxb2)", or nothing when the position has no tests
+fn tests_string(position: &EpdPosition) -> String {
+    let mut tests = Vec::new();
+    if !position.best_moves.is_empty() {
+        tests.push(format!("bm {}", position.best_moves.join(" ")));
+    }
+    if !position.avoid_moves.is_empty() {
+        tests.push(format!("am {}", position.avoid_moves.join(" ")));
+    }
+    if tests.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", tests.join(", "))
+    }
+}
@@ -1,22 +1,34 @@
 use crate::board::{Board, MoveError};
+use crate::clock::{Clock, TimeControl};
 use crate::gui_runner::GuiRunner;
-use crate::parser::parse_move;
+use crate::parser::{self, parse_move, GameResult, MoveTypes, ParseError};
 use crate::piece_types::{PieceColor, QuickPiece};
 use crate::pieces::{AnyPiece, PieceMove};
-use iced::{Sandbox, Settings};
-use std::io;
+use crate::player::Players;
+use crate::variant::Variant;
+use iced::{Application, Settings};
 
 // This function only checks the color opposing the last move.  This is because one cannot make a
 // move that would put the player in check.  That means for this function to run it has already
 // been checked that the king of the last_move color is not in check.
 pub fn is_board_in_check(last_move: &PieceColor, board: &Board) -> bool {
-    let opposing_king = match last_move {
-        PieceColor::WHITE => board.black_king_position,
-        PieceColor::BLACK => board.white_king_position,
+    // Antichess has no check and a blown up Atomic king isn't in check
+    let opposing_king = match board.king_position(&PieceColor::opposite_color(last_move)) {
+        Some(king_position) => king_position,
+        None => return false,
     };
+    // Nor is an Atomic king touching the other king, and kings can't give check since they can't
+    // take
+    let atomic = board.variant == Variant::Atomic;
+    if atomic && board.kings_touch() {
+        return false;
+    }
     match last_move {
         PieceColor::WHITE => {
             for piece in &board.live_white_pieces {
+                if atomic && matches!(piece, AnyPiece::King(_)) {
+                    continue;
+                }
                 if piece.can_move(opposing_king.0, opposing_king.1, &board.position_board) {
                     return true;
                 }
@@ -24,6 +36,9 @@ pub fn is_board_in_check(last_move: &PieceColor, board: &Board) -> bool {
         }
         PieceColor::BLACK => {
             for piece in &board.live_black_pieces {
+                if atomic && matches!(piece, AnyPiece::King(_)) {
+                    continue;
+                }
                 if piece.can_move(opposing_king.0, opposing_king.1, &board.position_board) {
                     return true;
                 }
@@ -72,56 +87,191 @@ pub fn is_board_check_mate(last_move: &PieceColor, board: &mut Board) -> bool {
     false
 }
 
-fn can_any_piece_move(piece_color: &PieceColor, game_board: &mut Board) -> bool {
-    let all_possible_moves: Vec<((usize, usize), Vec<(usize, usize)>)> = match piece_color {
-        PieceColor::WHITE => {
-            let mut all_movable_moves = Vec::new();
-            for piece in &game_board.live_white_pieces {
-                let possible_moves = piece.moves_on_board();
-                let mut movable_moves = Vec::with_capacity(possible_moves.capacity());
-
-                for (x, y) in possible_moves {
-                    if piece.can_move(x, y, &game_board.position_board) {
-                        let (px, py) = piece.get_pos();
-                        if let AnyPiece::Pawn(_) = piece {
-                            if game_board.valid_en_passant("P", px, py, x, y).is_ok() {
-                                movable_moves.push((x, y));
-                            }
-                        } else {
-                            movable_moves.push((x, y));
-                        }
+type PieceMoves = ((usize, usize), Vec<(usize, usize)>);
+/// A move as (start, end, promotion piece).  A Crazyhouse drop is the square twice with the
+/// dropped piece in place of the promotion piece
+pub type ResolvedMove = ((usize, usize), (usize, usize), Option<String>);
+
+/// A color can only checkmate if it has more than a lone king or a king and a single minor piece
+pub fn has_mating_material(piece_color: &PieceColor, board: &Board) -> bool {
+    let live_pieces = match piece_color {
+        PieceColor::WHITE => &board.live_white_pieces,
+        PieceColor::BLACK => &board.live_black_pieces,
+    };
+    let non_king_pieces: Vec<&AnyPiece> = live_pieces
+        .iter()
+        .filter(|piece| !matches!(piece, AnyPiece::King(_)))
+        .collect();
+
+    !matches!(
+        non_king_pieces.as_slice(),
+        [] | [AnyPiece::Bishop(_)] | [AnyPiece::Knight(_)]
+    )
+}
+
+/// Returns the result of the game if a player has run out of time.
+/// Flagging is a loss unless the opponent could never checkmate, in which case it's a draw
+pub fn result_on_time(clock: &Clock, board: &Board) -> Option<GameResult> {
+    let flagged_color = clock.flagged()?;
+    let opposing_color = PieceColor::opposite_color(&flagged_color);
+    if !has_mating_material(&opposing_color, board) {
+        return Some(GameResult::Draw);
+    }
+    match opposing_color {
+        PieceColor::WHITE => Some(GameResult::WhiteWin),
+        PieceColor::BLACK => Some(GameResult::BlackWin),
+    }
+}
+
+// Every square each piece of the color can reach without looking at check
+fn pseudo_legal_moves(piece_color: &PieceColor, game_board: &Board) -> Vec<PieceMoves> {
+    let live_pieces = match piece_color {
+        PieceColor::WHITE => &game_board.live_white_pieces,
+        PieceColor::BLACK => &game_board.live_black_pieces,
+    };
+
+    let mut all_movable_moves = Vec::new();
+    for piece in live_pieces {
+        let possible_moves = piece.moves_on_board(game_board.width(), game_board.height());
+        let mut movable_moves = Vec::with_capacity(possible_moves.capacity());
+
+        for (x, y) in possible_moves {
+            if piece.can_move(x, y, &game_board.position_board) {
+                let (px, py) = piece.get_pos();
+                if let AnyPiece::Pawn(_) = piece {
+                    if game_board.valid_en_passant("P", px, py, x, y).is_ok() {
+                        movable_moves.push((x, y));
                     }
+                } else {
+                    movable_moves.push((x, y));
                 }
-                all_movable_moves.push((piece.get_pos(), movable_moves));
             }
-            all_movable_moves
         }
-        PieceColor::BLACK => {
-            let mut all_movable_moves = Vec::new();
-            for piece in &game_board.live_black_pieces {
-                let possible_moves = piece.moves_on_board();
-                let mut movable_moves = Vec::with_capacity(possible_moves.capacity());
-
-                for (x, y) in possible_moves {
-                    if piece.can_move(x, y, &game_board.position_board) {
-                        let (px, py) = piece.get_pos();
-                        if let AnyPiece::Pawn(_) = piece {
-                            if game_board.valid_en_passant("P", px, py, x, y).is_ok() {
-                                movable_moves.push((x, y));
-                            }
-                        } else {
-                            movable_moves.push((x, y));
-                        }
-                    }
+        all_movable_moves.push((piece.get_pos(), movable_moves));
+    }
+    all_movable_moves
+}
+
+/// Every legal move for the color as (start, end) coordinates.  Castling is the king moving to
+/// the file it castles to
+pub fn legal_moves(
+    piece_color: &PieceColor,
+    game_board: &mut Board,
+) -> Vec<((usize, usize), (usize, usize))> {
+    let mut legal_moves = Vec::new();
+    for (start_position, moves) in pseudo_legal_moves(piece_color, game_board) {
+        for end_position in moves {
+            if !will_move_be_in_check(
+                start_position.0,
+                start_position.1,
+                end_position.0,
+                end_position.1,
+                piece_color,
+                piece_color,
+                game_board,
+            ) {
+                legal_moves.push((start_position, end_position));
+            }
+        }
+    }
+
+    if let Some(king_position) = game_board.king_position(piece_color) {
+        for king_end_x in [
+            game_board.castle_end_x(true),
+            game_board.castle_end_x(false),
+        ] {
+            if game_board.can_castle_king(piece_color, king_end_x) {
+                legal_moves.push((king_position, (king_end_x, king_position.1)));
+            }
+        }
+    }
+    // Captures are compulsory in Antichess
+    if game_board.variant == Variant::Antichess
+        && legal_moves
+            .iter()
+            .any(|(start, end)| is_capture(game_board, *start, *end))
+    {
+        legal_moves.retain(|(start, end)| is_capture(game_board, *start, *end));
+    }
+    legal_moves
+}
+
+/// True when the move takes a piece, counting a pawn taking en passant
+pub fn is_capture(board: &Board, start: (usize, usize), end: (usize, usize)) -> bool {
+    if board.position_board[end.0][end.1] != QuickPiece::EMPTY {
+        return true;
+    }
+    let live_pieces = match board.position_board[start.0][start.1] {
+        QuickPiece::PIECE(PieceColor::WHITE) => &board.live_white_pieces,
+        QuickPiece::PIECE(PieceColor::BLACK) => &board.live_black_pieces,
+        _ => return false,
+    };
+    let pawn_moving = live_pieces
+        .iter()
+        .any(|piece| piece.get_pos() == start && matches!(piece, AnyPiece::Pawn(_)));
+    pawn_moving && start.0 != end.0
+}
+
+/// True when the color has a capture it could make
+pub fn can_capture(piece_color: &PieceColor, game_board: &Board) -> bool {
+    pseudo_legal_moves(piece_color, game_board)
+        .into_iter()
+        .any(|(start_position, moves)| {
+            moves
+                .into_iter()
+                .any(|end_position| is_capture(game_board, start_position, end_position))
+        })
+}
+
+/// Every piece the color can drop from its pocket and where.  Empty unless the game is Crazyhouse
+pub fn legal_drops(
+    piece_color: &PieceColor,
+    game_board: &mut Board,
+) -> Vec<(String, (usize, usize))> {
+    let mut pocket_pieces: Vec<String> = match &game_board.pockets {
+        Some(pockets) => pockets.pieces(piece_color).to_vec(),
+        None => return Vec::new(),
+    };
+    pocket_pieces.dedup();
+
+    let mut legal_drops = Vec::new();
+    for x_coord in 0..game_board.width() {
+        for y_coord in 0..game_board.height() {
+            if game_board.position_board[x_coord][y_coord] != QuickPiece::EMPTY
+                || game_board.drop_leaves_king_in_check(piece_color, x_coord, y_coord)
+            {
+                continue;
+            }
+            for piece in &pocket_pieces {
+                if piece != "P" || (y_coord != 0 && y_coord != game_board.height() - 1) {
+                    legal_drops.push((piece.clone(), (x_coord, y_coord)));
                 }
-                all_movable_moves.push((piece.get_pos(), movable_moves));
             }
-            all_movable_moves
-            //game_board.live_black_pieces.iter().map(|x| (x.get_pos(),x.moves_on_board())).collect()
         }
+    }
+    legal_drops
+}
+
+/// The squares the piece at the given location can legally move to
+pub fn legal_moves_from(
+    x_coord: usize,
+    y_coord: usize,
+    game_board: &mut Board,
+) -> Vec<(usize, usize)> {
+    let piece_color = match game_board.position_board[x_coord][y_coord] {
+        QuickPiece::PIECE(color) | QuickPiece::KING(color) => color,
+        QuickPiece::EMPTY => return Vec::new(),
     };
+    legal_moves(&piece_color, game_board)
+        .into_iter()
+        .filter(|(start_position, _)| *start_position == (x_coord, y_coord))
+        .map(|(_, end_position)| end_position)
+        .collect()
+}
+
+fn can_any_piece_move(piece_color: &PieceColor, game_board: &mut Board) -> bool {
+    let all_possible_moves = pseudo_legal_moves(piece_color, game_board);
 
-    //let all_possible_moves:Vec<((usize,usize),Vec<(usize,usize)>)> = live_pieces.iter().map(|x| (x.get_pos(),x.moves_on_board())).collect();
     for ((piece_pos_x, piece_pos_y), moves) in all_possible_moves {
         for possible_move in moves {
             if !will_move_be_in_check(
@@ -138,11 +288,17 @@ fn can_any_piece_move(piece_color: &PieceColor, game_board: &mut Board) -> bool
         }
     }
 
-    if game_board.can_castle_king(piece_color, 6) || game_board.can_castle_king(piece_color, 2) {
+    let (kingside, queenside) = (
+        game_board.castle_end_x(true),
+        game_board.castle_end_x(false),
+    );
+    if game_board.can_castle_king(piece_color, kingside)
+        || game_board.can_castle_king(piece_color, queenside)
+    {
         return true;
     }
 
-    false
+    !legal_drops(piece_color, game_board).is_empty()
 }
 
 /// While this function takes in a mutable board it will not return until the board is back to the original state
@@ -159,6 +315,58 @@ pub fn will_move_be_in_check(
     king_color_being_checked: &PieceColor,
     board: &mut Board,
 ) -> bool {
+    // Racing Kings doesn't allow giving check either
+    if board.variant == Variant::RacingKings
+        && moving_piece_color == king_color_being_checked
+        && will_move_be_in_check(
+            x_start,
+            y_start,
+            x_end,
+            y_end,
+            moving_piece_color,
+            &PieceColor::opposite_color(moving_piece_color),
+            board,
+        )
+    {
+        return true;
+    }
+    // Without a king to check there is nothing to look for
+    if board.king_position(king_color_being_checked).is_none() {
+        return false;
+    }
+    // Captures blow up pieces around them so the move is played out on a copy instead
+    if board.variant == Variant::Atomic {
+        return board.atomic_move_exposes_king(
+            (x_start, y_start),
+            (x_end, y_end),
+            moving_piece_color,
+        );
+    }
+    // En passant takes a pawn that isn't on the end square.  That pawn is taken off a copy first
+    // so a pin along the rank through both pawns is seen
+    let is_pawn = matches!(
+        board.find_piece_color(x_start, y_start, moving_piece_color),
+        Some(AnyPiece::Pawn(_))
+    );
+    if is_pawn && x_start != x_end && board.position_board[x_end][y_end] == QuickPiece::EMPTY {
+        let mut board = board.clone();
+        board.position_board[x_end][y_start] = QuickPiece::EMPTY;
+        let taken_pieces = match moving_piece_color {
+            PieceColor::WHITE => &mut board.live_black_pieces,
+            PieceColor::BLACK => &mut board.live_white_pieces,
+        };
+        taken_pieces.retain(|piece| piece.get_pos() != (x_end, y_start));
+        board.position_board[x_end][y_end] = QuickPiece::PIECE(*moving_piece_color);
+        board.position_board[x_start][y_start] = QuickPiece::EMPTY;
+        if let Some(pawn) = board.find_piece_color(x_start, y_start, moving_piece_color) {
+            pawn.set_pos(x_end, y_end);
+        }
+        let (king_x, king_y) = match king_color_being_checked {
+            PieceColor::WHITE => board.white_king_position,
+            PieceColor::BLACK => board.black_king_position,
+        };
+        return board.can_any_piece_check_king(king_x, king_y, king_color_being_checked);
+    }
     // Remove the starting piece from quick board
     let start_piece = board
         .position_board
@@ -274,23 +482,110 @@ pub fn will_move_be_in_check(
     found_check
 }
 
-fn get_input_string_from_user() -> String {
-    println!("Please input a move");
-    let mut player_input_string = String::new();
-    io::stdin()
-        .read_line(&mut player_input_string)
-        .expect("Failed to retrieve the player's move from the standard input");
-    player_input_string
+/// Finds the squares a SAN move uses on the given board as (start, end, promotion piece)
+pub fn resolve_move(board: &mut Board, move_string: &str) -> Result<ResolvedMove, ParseError> {
+    let move_string = move_string.trim_end_matches(['!', '?']);
+    if move_string.len() < 2 {
+        return Err(ParseError::new(&format!("{:?} is not a move", move_string)));
+    }
+    let parsed_move = parser::parse_move(move_string)?;
+
+    let moving_color = PieceColor::opposite_color(&board.last_move_color);
+    match &parsed_move.move_type {
+        MoveTypes::Castle(king_end_x) => {
+            let king_position = match moving_color {
+                PieceColor::WHITE => board.white_king_position,
+                PieceColor::BLACK => board.black_king_position,
+            };
+            let king_end_x = board.castle_end_x(*king_end_x > king_position.0);
+            Ok((king_position, (king_end_x, king_position.1), None))
+        }
+        // A drop is the square twice with the piece that is dropped
+        MoveTypes::Drop => {
+            let square = (
+                parser::parse_coordinate(&parsed_move.end_coords.0),
+                parser::parse_coordinate(&parsed_move.end_coords.1),
+            );
+            Ok((square, square, Some(parsed_move.piece_char.clone())))
+        }
+        MoveTypes::FinalResult(_) => Err(ParseError::new(&format!(
+            "{:?} is a result and not a move",
+            move_string
+        ))),
+        _ => {
+            let promotion_piece = match &parsed_move.move_type {
+                MoveTypes::Promote(promotion_piece) => Some(promotion_piece.clone()),
+                _ => None,
+            };
+            let end_position = (
+                parser::parse_coordinate(&parsed_move.end_coords.0),
+                parser::parse_coordinate(&parsed_move.end_coords.1),
+            );
+            match board.find_start_piece_from_move(&parsed_move) {
+                Some(piece) => Ok((piece.get_pos(), end_position, promotion_piece)),
+                None => Err(ParseError::new(&format!(
+                    "No piece can play {:?} in this position",
+                    move_string
+                ))),
+            }
+        }
+    }
+}
+
+/// Every legal ply for the side on move, with a move for each promotion piece and the drops
+pub fn legal_plies(board: &mut Board) -> Vec<ResolvedMove> {
+    let piece_color = PieceColor::opposite_color(&board.last_move_color);
+    let mut moves: Vec<ResolvedMove> = Vec::new();
+    for (start_position, end_position) in legal_moves(&piece_color, board) {
+        let promotes = match board.parsed_move_from_coords(start_position, end_position, None) {
+            Ok(parsed_move) => board.needs_promotion_piece(&parsed_move),
+            Err(_) => false,
+        };
+        if promotes {
+            for promotion_piece in board.promotion_pieces() {
+                moves.push((
+                    start_position,
+                    end_position,
+                    Some(String::from(promotion_piece)),
+                ));
+            }
+        } else {
+            moves.push((start_position, end_position, None));
+        }
+    }
+    for (piece, square) in legal_drops(&piece_color, board) {
+        moves.push((square, square, Some(piece)));
+    }
+    moves
 }
 
-fn get_player_draw_response() -> bool {
-    let mut draw_response = String::new();
-    println!("The other player is asking for a draw.  Would you like to accept?(Y/N)");
-    io::stdin()
-        .read_line(&mut draw_response)
-        .expect("Failed to retrieve the player's move from the standard input");
+/// Counts the positions reached after playing every legal move to the given depth.  The numbers
+/// are known for many positions so they show up mistakes in the move generation.  A position the
+/// variant says is won has no moves
+pub fn perft(board: &Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    if board.variant.winner(board).is_some() || board.variant.is_draw(board) {
+        return 0;
+    }
+    let mut board = board.clone();
+    let moves = legal_plies(&mut board);
 
-    draw_response.to_ascii_lowercase().contains('y')
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    let mut nodes = 0;
+    for (start_position, end_position, promotion_piece) in moves {
+        let mut next_board = board.clone();
+        if next_board
+            .play_move_from_coords(start_position, end_position, promotion_piece.as_deref())
+            .is_ok()
+        {
+            nodes += perft(&next_board, depth - 1);
+        }
+    }
+    nodes
 }
 
 // @TODO figure out how to maintain the parsed error
@@ -305,62 +600,8 @@ pub fn player_move(game_board: &mut Board, player_input: &str) -> Result<(), Mov
     }
 }
 
-pub fn play_game_cli() {
-    // Create the game
-    let mut game_board = Board::new();
-    let mut game_continue_status = true;
-
-    while game_continue_status {
-        // We need to keep alternating pieces until checkmate, stalemate, or draw
-        let mut valid_move = false;
-
-        while !valid_move {
-            println!(
-                "CurrentMoveColor:{:?}",
-                PieceColor::opposite_color(&game_board.last_move_color)
-            );
-            let player_input = get_input_string_from_user();
-            if player_input.contains("print") {
-                println!(
-                    "CheckMate?:{:?}",
-                    is_board_check_mate(&game_board.last_move_color.clone(), &mut game_board)
-                );
-                println!("currentQuickBoard:{:?}", game_board.position_board);
-                println!("LiveWhite:{:?}", game_board.live_white_pieces);
-                println!("LiveBlack:{:?}", game_board.live_black_pieces);
-                println!("PastMoves:{:?}", game_board.played_moves);
-            } else if player_input.contains("draw") {
-                if get_player_draw_response() {
-                    // End the game with a score of draw
-                    println!("The players have agreed to a draw!");
-                    game_continue_status = false;
-                    valid_move = true;
-                } else {
-                    println!("The players failed to agree to a draw!");
-                }
-            } else {
-                let move_result = player_move(&mut game_board, &player_input);
-                match move_result {
-                    Ok(_) => valid_move = true,
-                    Err(error) => println!("{}", error),
-                };
-            }
-        }
-
-        // The current player has finished their move.
-        // check for checkmate or stalemate
-        let check_mate_status =
-            is_board_check_mate(&game_board.last_move_color.clone(), &mut game_board);
-        if check_mate_status {
-            // Do something for a win for that last move color
-            println!("{:?} Wins with checkmate", game_board.last_move_color);
-            game_continue_status = false;
-        }
-    }
-}
-
-pub fn play_game_gui() {
-    GuiRunner::run(Settings::default()).unwrap();
+pub fn play_game_gui(time_control: Option<TimeControl>, players: Players, variant: Variant) {
+    GuiRunner::run(Settings::with_flags((time_control, players, variant))).unwrap();
 }
 
 pub fn file_name_from_piece(any_piece: &AnyPiece, piece_color: &PieceColor) -> String {
@@ -372,6 +613,7 @@ pub fn file_name_from_piece(any_piece: &AnyPiece, piece_color: &PieceColor) -> S
             AnyPiece::Knight(_) => "GreenKnight.png",
             AnyPiece::Rook(_) => "GreenRook.png",
             AnyPiece::Pawn(_) => "GreenPawn.png",
+            AnyPiece::Fairy(fairy) => fairy.piece_type().image(piece_color),
         },
         PieceColor::BLACK => match any_piece {
             AnyPiece::King(_) => "BlackKing.png",
@@ -380,6 +622,7 @@ pub fn file_name_from_piece(any_piece: &AnyPiece, piece_color: &PieceColor) -> S
             AnyPiece::Knight(_) => "BlackKnight.png",
             AnyPiece::Rook(_) => "BlackRook.png",
             AnyPiece::Pawn(_) => "BlackPawn.png",
+            AnyPiece::Fairy(fairy) => fairy.piece_type().image(piece_color),
         },
     })
 }
@@ -1,29 +1,262 @@
-use iced::{text_input, Column, Container, Element, Image, Length, Row, Sandbox, Text, TextInput};
+use iced::{
+    button, executor, keyboard, scrollable, text_input, time, Application, Background, Button,
+    Checkbox, Clipboard, Color, Column, Command, Container, Element, Image, Length, Row,
+    Scrollable, Subscription, Text, TextInput,
+};
+use iced_native::{event, subscription, Event};
 
 use crate::board::Board;
-use crate::game;
-use crate::piece_types::PieceColor;
+use crate::board_widget::{BoardWidget, DragState, SquareView};
+use crate::chess_game::{Game, GameEvent, GameMove};
+use crate::clock::{format_duration, Clock, TimeControl};
+use crate::engine::Engine;
+use crate::game::{self, ResolvedMove};
+use crate::parser::{self, ParseError};
+use crate::pgn;
+use crate::piece_types::{PieceColor, QuickPiece};
 use crate::pieces::PieceMove;
+use crate::player::{Player, Players};
+use crate::setup::PositionSetup;
+use crate::uci::EngineError;
+use crate::variant::Variant;
 
+use iced::futures::channel::oneshot;
 use std::collections::HashMap;
-use Message::{InputChanged, PlayMove};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use Message::{AutoOrientToggled, FlipBoard, InputChanged, PlayMove};
+
+// How often the clocks on screen are updated
+const CLOCK_TICK_RATE: Duration = Duration::from_millis(100);
+const SQUARE_SIZE: u16 = 100;
+// Width of the strip the coordinates are drawn in
+const COORDINATE_SIZE: u16 = 30;
+// Room around the piece so the move markers drawn on the border aren't hidden by the image
+const SQUARE_PADDING: u16 = 4;
+
+const LIGHT_SQUARE: Color = Color::from_rgb(0.93, 0.93, 0.82);
+const DARK_SQUARE: Color = Color::from_rgb(0.46, 0.59, 0.34);
+const SELECTED_SQUARE: Color = Color::from_rgb(0.96, 0.96, 0.41);
+const LAST_MOVE_SQUARE: Color = Color::from_rgb(0.80, 0.82, 0.42);
+const CHECK_SQUARE: Color = Color::from_rgb(0.91, 0.30, 0.30);
+const MOVE_MARKER: Color = Color::from_rgba(0.1, 0.1, 0.1, 0.45);
+const CAPTURE_MARKER: Color = Color::from_rgb(0.75, 0.1, 0.1);
+const CURRENT_MOVE: Color = Color::from_rgb(0.80, 0.82, 0.42);
+const MOVE_LIST_WIDTH: u16 = 240;
+// The pieces on the setup palette, white first
+const SETUP_PIECES: [char; 12] = ['K', 'Q', 'R', 'B', 'N', 'P', 'k', 'q', 'r', 'b', 'n', 'p'];
+// In the order of the castling rights of a FEN, KQkq
+const CASTLING_LABELS: [&str; 4] = ["White O-O", "White O-O-O", "Black O-O", "Black O-O-O"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SquareHighlight {
+    Plain,
+    Selected,
+    LastMove,
+    Check,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TargetMarker {
+    Move,
+    Capture,
+}
+
+/// What clicking a square does while a position is set up
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SetupTool {
+    /// Puts the piece with the FEN letter on the square
+    Piece(char),
+    #[default]
+    Erase,
+    /// Marks the square a pawn skipped over, or clears it
+    EnPassant,
+}
+
+struct SquareStyle {
+    light_square: bool,
+    highlight: SquareHighlight,
+    target: Option<TargetMarker>,
+}
+
+impl SquareStyle {
+    // The square with the piece image on it, if there is one
+    fn square_view(&self, piece_image: Option<PathBuf>, draggable: bool) -> SquareView {
+        let background = match self.highlight {
+            SquareHighlight::Selected => SELECTED_SQUARE,
+            SquareHighlight::LastMove => LAST_MOVE_SQUARE,
+            SquareHighlight::Check => CHECK_SQUARE,
+            SquareHighlight::Plain if self.light_square => LIGHT_SQUARE,
+            SquareHighlight::Plain => DARK_SQUARE,
+        };
+        let (border_width, border_color) = match self.target {
+            Some(TargetMarker::Move) => (4.0, MOVE_MARKER),
+            Some(TargetMarker::Capture) => (f32::from(SQUARE_PADDING), CAPTURE_MARKER),
+            None => (0.0, Color::TRANSPARENT),
+        };
+        SquareView {
+            background,
+            border_color,
+            border_width,
+            piece_image,
+            draggable,
+        }
+    }
+}
+
+// The move in the list that matches the position on the board stands out
+struct MoveListStyle {
+    current: bool,
+}
+
+impl button::StyleSheet for MoveListStyle {
+    fn active(&self) -> button::Style {
+        button::Style {
+            background: if self.current {
+                Some(Background::Color(CURRENT_MOVE))
+            } else {
+                None
+            },
+            ..button::Style::default()
+        }
+    }
+}
+
+/// What to mark on the board besides the pieces
+#[derive(Default)]
+pub struct BoardHighlights {
+    selected_square: Option<(usize, usize)>,
+    move_targets: Vec<(usize, usize)>,
+    capture_targets: Vec<(usize, usize)>,
+    last_move: Option<((usize, usize), (usize, usize))>,
+    checked_king: Option<(usize, usize)>,
+}
+
+impl BoardHighlights {
+    fn square_style(&self, x_coord: usize, y_coord: usize) -> SquareStyle {
+        let square = (x_coord, y_coord);
+        let highlight = if self.checked_king == Some(square) {
+            SquareHighlight::Check
+        } else if self.selected_square == Some(square) {
+            SquareHighlight::Selected
+        } else if matches!(self.last_move, Some((start, end)) if start == square || end == square) {
+            SquareHighlight::LastMove
+        } else {
+            SquareHighlight::Plain
+        };
+        let target = if self.capture_targets.contains(&square) {
+            Some(TargetMarker::Capture)
+        } else if self.move_targets.contains(&square) {
+            Some(TargetMarker::Move)
+        } else {
+            None
+        };
+        SquareStyle {
+            // a1 is a dark square
+            light_square: (x_coord + y_coord) % 2 == 1,
+            highlight,
+            target,
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct GuiRunner {
-    game_continue: bool,
     wrong_move_string: String,
     text_state: text_input::State,
     text_value: String,
-    board: Board,
+    // The board, clock, moves and outcome of the game being played
+    game: Game,
     value: i32,
+    last_tick: Option<Instant>,
+    // The piece being dragged on the board, if any
+    drag_state: DragState,
+    selected_square: Option<(usize, usize)>,
+    selected_targets: Vec<(usize, usize)>,
+    // The move waiting on the promotion picker
+    pending_promotion: Option<((usize, usize), (usize, usize))>,
+    promotion_states: Vec<button::State>,
+    cancel_promotion_state: button::State,
+    flipped: bool,
+    // Turn the board to whoever is on move
+    auto_orient: bool,
+    flip_state: button::State,
+    // Some when looking at an earlier position instead of the live game
+    viewing_ply: Option<usize>,
+    view_board: Option<Board>,
+    move_button_states: Vec<button::State>,
+    move_list_state: scrollable::State,
+    branch_state: button::State,
+    white_engine: Option<Arc<Mutex<Engine>>>,
+    black_engine: Option<Arc<Mutex<Engine>>>,
+    engine_thinking: bool,
+    // Lets a move from an engine that was still thinking when a new game started be thrown away
+    game_number: u32,
+    new_game_open: bool,
+    new_game_state: button::State,
+    new_white_state: text_input::State,
+    new_white_value: String,
+    new_black_state: text_input::State,
+    new_black_value: String,
+    new_time_state: text_input::State,
+    new_time_value: String,
+    new_variant_state: text_input::State,
+    new_variant_value: String,
+    start_game_state: button::State,
+    cancel_new_game_state: button::State,
+    // Names written to a saved game.  A loaded game keeps the names it was saved with
+    white_name: String,
+    black_name: String,
+    file_path_state: text_input::State,
+    file_path_value: String,
+    save_state: button::State,
+    open_state: button::State,
+    resign_state: button::State,
+    draw_state: button::State,
+    decline_draw_state: button::State,
+    // Some while a position is being set up.  The game is left alone until the setup is played
+    setup: Option<PositionSetup>,
+    setup_tool: SetupTool,
+    setup_state: button::State,
+    setup_tool_states: Vec<button::State>,
+    setup_side_state: button::State,
+    setup_clear_state: button::State,
+    setup_standard_state: button::State,
+    setup_fen_state: text_input::State,
+    setup_fen_value: String,
+    setup_play_state: button::State,
+    setup_cancel_state: button::State,
 }
 
 impl GuiRunner {
-    pub fn create_container_from_board(board: &Board) -> Row<Message> {
+    fn clock_display(clock: &Clock, color: &PieceColor) -> Text {
+        let running_marker = if clock.running() == Some(*color) {
+            " <"
+        } else {
+            ""
+        };
+        Text::new(format!(
+            "{:?}: {}{}",
+            color,
+            format_duration(clock.remaining(color)),
+            running_marker
+        ))
+        .size(30)
+    }
+
+    /// The board with its coordinates.  Pieces of the draggable color can be dragged to a square
+    pub fn create_container_from_board<'a>(
+        board: &Board,
+        drag_state: &'a mut DragState,
+        highlights: &BoardHighlights,
+        white_at_bottom: bool,
+        draggable: Option<PieceColor>,
+    ) -> Column<'a, Message> {
         let mut file_name_hash_map = HashMap::new();
         for piece in &board.live_white_pieces {
             let (x, y) = piece.get_pos();
-            //println!("FILENAME:{}", game::file_name_from_piece(&piece, &PieceColor::WHITE));
             file_name_hash_map.insert(
                 (x, y),
                 game::file_name_from_piece(piece, &PieceColor::WHITE),
@@ -31,639 +264,877 @@ impl GuiRunner {
         }
         for piece in &board.live_black_pieces {
             let (x, y) = piece.get_pos();
-            //println!("FILENAME:{}", game::file_name_from_piece(&piece, &PieceColor::BLACK));
             file_name_hash_map.insert(
                 (x, y),
                 game::file_name_from_piece(piece, &PieceColor::BLACK),
             );
         }
 
-        for x in 0..8 {
-            for y in 0..8 {
-                file_name_hash_map
-                    .entry((x, y))
-                    .or_insert_with(|| String::from("Empty.png"));
+        let squares: Vec<Vec<SquareView>> = (0..board.width())
+            .map(|x| {
+                (0..board.height())
+                    .map(|y| {
+                        let draggable = match board.position_board[x][y] {
+                            QuickPiece::PIECE(color) | QuickPiece::KING(color) => {
+                                Some(color) == draggable
+                            }
+                            QuickPiece::EMPTY => false,
+                        };
+                        let piece_image = file_name_hash_map
+                            .remove(&(x, y))
+                            .map(|file_name| GuiRunner::image_path(&file_name));
+                        highlights
+                            .square_style(x, y)
+                            .square_view(piece_image, draggable)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // The rank numbers go down the left edge
+        let mut rank_labels = Column::new();
+        let mut ranks: Vec<usize> = (0..board.height()).collect();
+        if white_at_bottom {
+            ranks.reverse();
+        }
+        for &y in &ranks {
+            rank_labels = rank_labels.push(GuiRunner::coordinate_label(
+                parser::rank_from_coordinate(y),
+                COORDINATE_SIZE,
+                SQUARE_SIZE,
+            ));
+        }
+        let board_widget = BoardWidget::new(squares, drag_state, Message::SquareClicked)
+            .on_drop(Message::PieceDropped)
+            .square_size(SQUARE_SIZE)
+            .padding(SQUARE_PADDING)
+            .white_at_bottom(white_at_bottom);
+        let row = Row::new().push(rank_labels).push(board_widget);
+
+        let mut files: Vec<usize> = (0..board.width()).collect();
+        if !white_at_bottom {
+            files.reverse();
+        }
+        // And the file letters along the bottom
+        let mut file_labels = Row::new().push(GuiRunner::coordinate_label(
+            String::new(),
+            COORDINATE_SIZE,
+            COORDINATE_SIZE,
+        ));
+        for x in files {
+            file_labels = file_labels.push(GuiRunner::coordinate_label(
+                parser::file_from_coordinate(x),
+                SQUARE_SIZE,
+                COORDINATE_SIZE,
+            ));
+        }
+        Column::new().push(row).push(file_labels)
+    }
+
+    fn coordinate_label<'a>(label: String, width: u16, height: u16) -> Container<'a, Message> {
+        Container::new(Text::new(label).size(20))
+            .width(Length::from(width))
+            .height(Length::from(height))
+            .center_x()
+            .center_y()
+    }
+
+    // Which way up the board is drawn
+    fn white_at_bottom(&self) -> bool {
+        let bottom_color = if self.auto_orient {
+            self.game.side_to_move()
+        } else {
+            PieceColor::WHITE
+        };
+        (bottom_color == PieceColor::WHITE) != self.flipped
+    }
+
+    fn board_highlights(&self) -> BoardHighlights {
+        let board = self.shown_board();
+        let mut highlights = BoardHighlights {
+            selected_square: self.selected_square,
+            last_move: board.last_move_squares(),
+            ..Default::default()
+        };
+        if let Some(selected_square) = self.selected_square {
+            for &target in &self.selected_targets {
+                if game::is_capture(board, selected_square, target) {
+                    highlights.capture_targets.push(target);
+                } else {
+                    highlights.move_targets.push(target);
+                }
             }
         }
+        if game::is_board_in_check(&board.last_move_color, board) {
+            highlights.checked_king =
+                board.king_position(&PieceColor::opposite_color(&board.last_move_color));
+        }
+        highlights
+    }
 
-        let row = Row::new()
-            // Start of column
-            .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(0, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    ),
+    fn shown_board(&self) -> &Board {
+        self.view_board.as_ref().unwrap_or(self.game.board())
+    }
+
+    fn current_ply(&self) -> usize {
+        self.viewing_ply.unwrap_or(self.game.moves().len())
+    }
+
+    // Replays the game up to the ply.  The live game isn't touched
+    fn show_ply(&mut self, ply: usize) {
+        self.selected_square = None;
+        self.selected_targets.clear();
+        self.pending_promotion = None;
+        if ply >= self.game.moves().len() {
+            self.viewing_ply = None;
+            self.view_board = None;
+            return;
+        }
+        self.viewing_ply = Some(ply);
+        self.view_board = Some(self.game.board_after(ply));
+    }
+
+    // Throws away the moves after the position being looked at so play carries on from there
+    fn branch_from_shown_ply(&mut self) {
+        if let Some(ply) = self.viewing_ply.take() {
+            self.view_board = None;
+            let plies = self.game.moves().len() - ply;
+            self.wrong_move_string = match self.game.take_back(plies) {
+                Ok(_) => "".to_string(),
+                Err(error) => error.to_string(),
+            };
+            self.move_button_states.truncate(self.game.moves().len());
+            self.show_events();
+        }
+    }
+
+    fn keyboard_message(event: Event, status: event::Status) -> Option<Message> {
+        // Keys typed into the move box shouldn't move around the game
+        if status == event::Status::Captured {
+            return None;
+        }
+        match event {
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) => match key_code {
+                keyboard::KeyCode::Left => Some(Message::HistoryBack),
+                keyboard::KeyCode::Right => Some(Message::HistoryForward),
+                keyboard::KeyCode::Home | keyboard::KeyCode::Up => Some(Message::HistoryStart),
+                keyboard::KeyCode::End | keyboard::KeyCode::Down => Some(Message::HistoryEnd),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn move_list<'a>(
+        history: &[GameMove],
+        current_ply: usize,
+        move_button_states: &'a mut [button::State],
+        move_list_state: &'a mut scrollable::State,
+    ) -> Scrollable<'a, Message> {
+        let mut move_list = Scrollable::new(move_list_state)
+            .width(Length::from(MOVE_LIST_WIDTH))
+            .height(Length::Fill)
+            .spacing(2);
+        let mut move_row = Row::new();
+        for (index, (history_move, state)) in history
+            .iter()
+            .zip(move_button_states.iter_mut())
+            .enumerate()
+        {
+            if index % 2 == 0 {
+                move_row = Row::new()
+                    .spacing(4)
+                    .push(Text::new(format!("{}.", index / 2 + 1)).width(Length::from(40)));
+            }
+            move_row = move_row.push(
+                Button::new(state, Text::new(history_move.san.as_str()))
+                    .width(Length::from(90))
+                    .style(MoveListStyle {
+                        current: index + 1 == current_ply,
+                    })
+                    .on_press(Message::ShowPly(index + 1)),
+            );
+            if index % 2 == 1 {
+                move_list = move_list.push(move_row);
+                move_row = Row::new();
+            }
+        }
+        if history.len() % 2 == 1 {
+            move_list = move_list.push(move_row);
+        }
+        move_list
+    }
+
+    // Shows what the game says happened since the last call
+    fn show_events(&mut self) {
+        for event in self.game.drain_events() {
+            match event {
+                GameEvent::MovePlayed { .. } => {
+                    self.selected_square = None;
+                    self.selected_targets.clear();
+                    self.text_value = "".to_string();
+                    self.wrong_move_string = "".to_string();
+                    self.move_button_states.push(button::State::new());
+                }
+                GameEvent::DrawOffered(color) => {
+                    self.wrong_move_string = format!("{:?} offers a draw", color)
+                }
+                GameEvent::DrawDeclined(color) => {
+                    self.wrong_move_string = format!("{:?} declines the draw", color)
+                }
+                GameEvent::DrawWithdrawn(color) => {
+                    self.wrong_move_string = format!("{:?} takes back the draw offer", color)
+                }
+                GameEvent::GameOver(outcome) => {
+                    // An engine that is thinking has its move thrown away
+                    self.engine_thinking = false;
+                    self.game_number += 1;
+                    self.wrong_move_string = outcome.to_string();
+                }
+                // The board and clocks are drawn from the game itself
+                GameEvent::Check(_) | GameEvent::ClockUpdate { .. } => (),
+            }
+        }
+    }
+
+    // Which human the draw and resign buttons act for.  The one on move, or the one waiting on
+    // an engine
+    fn acting_color(&self) -> Option<PieceColor> {
+        let side_to_move = self.game.side_to_move();
+        [side_to_move, PieceColor::opposite_color(&side_to_move)]
+            .into_iter()
+            .find(|color| self.game.players().get(color).is_human())
+    }
+
+    // Resign and the draw button that fits the state of the offer
+    fn draw_and_resign_row<'a>(
+        acting_color: PieceColor,
+        draw_offer: Option<PieceColor>,
+        resign_state: &'a mut button::State,
+        draw_state: &'a mut button::State,
+        decline_draw_state: &'a mut button::State,
+    ) -> Row<'a, Message> {
+        let mut row = Row::new().spacing(10).push(
+            Button::new(
+                resign_state,
+                Text::new(format!("{:?} resigns", acting_color)),
             )
-            //End of Column
-            // Start of column
+            .on_press(Message::Resign),
+        );
+        row = match draw_offer {
+            None => row.push(
+                Button::new(draw_state, Text::new("Offer draw")).on_press(Message::OfferDraw),
+            ),
+            Some(offered_by) if offered_by == acting_color => row.push(
+                Button::new(draw_state, Text::new("Withdraw draw offer"))
+                    .on_press(Message::WithdrawDraw),
+            ),
+            Some(_) => row
+                .push(
+                    Button::new(draw_state, Text::new("Accept draw")).on_press(Message::AcceptDraw),
+                )
+                .push(
+                    Button::new(decline_draw_state, Text::new("Decline draw"))
+                        .on_press(Message::DeclineDraw),
+                ),
+        };
+        row
+    }
+
+    fn play_clicked_move(&mut self, start: (usize, usize), end: (usize, usize)) {
+        if self.game.needs_promotion_piece(start, end) {
+            // Wait for the picker before playing anything
+            self.pending_promotion = Some((start, end));
+            return;
+        }
+        self.play_move_from_coords(start, end, None);
+    }
+
+    fn play_move_from_coords(
+        &mut self,
+        start: (usize, usize),
+        end: (usize, usize),
+        promotion_piece: Option<&str>,
+    ) {
+        match self.game.submit_move(start, end, promotion_piece) {
+            Ok(_) => self.show_events(),
+            Err(error) => self.wrong_move_string = error.to_string(),
+        }
+    }
+
+    // Piece images live in the resources folder.  A fairy piece can give a full path instead
+    fn image_path(file_name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("resources")
+            .join(file_name)
+    }
+
+    // Antichess pawns can promote to a king as well, and any board can promote to its fairy pieces
+    fn promotion_picker<'a>(
+        board: &Board,
+        promotion_states: &'a mut Vec<button::State>,
+        cancel_state: &'a mut button::State,
+    ) -> Row<'a, Message> {
+        let color = PieceColor::opposite_color(&board.last_move_color);
+        let promotion_pieces = board.promotion_pieces();
+        promotion_states.resize_with(promotion_pieces.len(), button::State::default);
+        let mut row = Row::new().push(Text::new("Promote to:").size(30));
+        for (promotion_piece, state) in promotion_pieces
+            .into_iter()
+            .zip(promotion_states.iter_mut())
+        {
+            let piece = board
+                .piece_from_character(promotion_piece, 0, 0, color)
+                .unwrap();
+            let image = Image::new(GuiRunner::image_path(&game::file_name_from_piece(
+                &piece, &color,
+            )))
+            .width(Length::from(SQUARE_SIZE / 2))
+            .height(Length::from(SQUARE_SIZE / 2));
+            row = row.push(
+                Button::new(state, image).on_press(Message::PromotionChosen(promotion_piece)),
+            );
+        }
+        row.push(
+            Button::new(cancel_state, Text::new("Cancel")).on_press(Message::PromotionCancelled),
+        )
+    }
+
+    /// Throws away the current game and starts a new one from the variant's starting position
+    fn start_game(
+        &mut self,
+        time_control: Option<TimeControl>,
+        players: Players,
+        variant: Variant,
+    ) -> Result<(), EngineError> {
+        self.start_game_from_board(time_control, players, Board::new_variant(variant))
+    }
+
+    fn start_game_from_board(
+        &mut self,
+        time_control: Option<TimeControl>,
+        players: Players,
+        board: Board,
+    ) -> Result<(), EngineError> {
+        // Start the engines first so a bad engine leaves the current game alone
+        let white_engine = GuiRunner::start_engine(&players.white)?;
+        let black_engine = GuiRunner::start_engine(&players.black)?;
+        GuiRunner::check_engines(&[&white_engine, &black_engine], &board)?;
+
+        self.last_tick = None;
+        self.move_button_states.clear();
+        self.viewing_ply = None;
+        self.view_board = None;
+        self.selected_square = None;
+        self.selected_targets.clear();
+        self.pending_promotion = None;
+        self.wrong_move_string = "".to_string();
+        self.engine_thinking = false;
+        self.game_number += 1;
+        // Play from the human's side when only one side is human
+        self.flipped = !players.white.is_human() && players.black.is_human();
+        self.white_name = GuiRunner::engine_name(&white_engine);
+        self.black_name = GuiRunner::engine_name(&black_engine);
+        self.white_engine = white_engine;
+        self.black_engine = black_engine;
+        self.game = Game::from_board(board, players, time_control);
+        Ok(())
+    }
+
+    fn start_engine(player: &Player) -> Result<Option<Arc<Mutex<Engine>>>, EngineError> {
+        Ok(Engine::start(player)?.map(|engine| Arc::new(Mutex::new(engine))))
+    }
+
+    // Engines are only given games they can be told the position of
+    fn check_engines(
+        engines: &[&Option<Arc<Mutex<Engine>>>],
+        board: &Board,
+    ) -> Result<(), EngineError> {
+        for engine in engines.iter().filter_map(|engine| engine.as_ref()) {
+            if let Ok(engine) = engine.lock() {
+                engine.can_play(board)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn engine_name(engine: &Option<Arc<Mutex<Engine>>>) -> String {
+        match engine.as_ref().and_then(|engine| engine.lock().ok()) {
+            Some(engine) => String::from(engine.name()),
+            None => String::from("?"),
+        }
+    }
+
+    fn save_game(&self, path: &str) -> Result<(), ParseError> {
+        let pgn_game = self.game.to_pgn(&self.white_name, &self.black_name);
+        pgn::save_pgn_file(path, &pgn_game)
+    }
+
+    /// Replaces the game on the board with a saved one.  The players stay the same so a saved
+    /// game can be carried on against a different engine
+    fn open_game(&mut self, path: &str) -> Result<(), ParseError> {
+        let pgn_game = pgn::load_pgn_file(path)?;
+        let game = Game::from_pgn(&pgn_game, self.game.players().clone())?;
+        GuiRunner::check_engines(
+            &[&self.white_engine, &self.black_engine],
+            game.start_board(),
+        )
+        .map_err(|error| ParseError::new(&error.to_string()))?;
+
+        self.move_button_states = game.moves().iter().map(|_| button::State::new()).collect();
+        self.game = game;
+        self.last_tick = None;
+        self.viewing_ply = None;
+        self.view_board = None;
+        self.selected_square = None;
+        self.selected_targets.clear();
+        self.pending_promotion = None;
+        // An engine still thinking about the old game has its move thrown away
+        self.engine_thinking = false;
+        self.game_number += 1;
+        self.white_name = String::from(pgn_game.tag("White").unwrap_or("?"));
+        self.black_name = String::from(pgn_game.tag("Black").unwrap_or("?"));
+        self.wrong_move_string = match self.game.outcome() {
+            Some(outcome) => outcome.to_string(),
+            None => format!("Opened {}", path),
+        };
+        Ok(())
+    }
+
+    fn file_row<'a>(
+        path_state: &'a mut text_input::State,
+        path_value: &str,
+        save_state: &'a mut button::State,
+        open_state: &'a mut button::State,
+    ) -> Row<'a, Message> {
+        Row::new()
+            .spacing(5)
+            .width(Length::from(MOVE_LIST_WIDTH))
             .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(1, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    ),
+                TextInput::new(path_state, "game.pgn", path_value, Message::FilePathChanged)
+                    .padding(5),
             )
-            //End of Column
-            // Start of column
-            .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(2, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    ),
+            .push(Button::new(save_state, Text::new("Save")).on_press(Message::SaveGame))
+            .push(Button::new(open_state, Text::new("Open")).on_press(Message::OpenGame))
+    }
+
+    fn new_game_settings(&self) -> Result<(Option<TimeControl>, Players, Variant), String> {
+        let player = |value: &str| {
+            if value.trim().is_empty() {
+                Ok(Player::Human)
+            } else {
+                Player::from_string(value).map_err(|error| error.to_string())
+            }
+        };
+        let players = Players {
+            white: player(&self.new_white_value)?,
+            black: player(&self.new_black_value)?,
+        };
+        let time_control = if self.new_time_value.trim().is_empty() {
+            None
+        } else {
+            Some(
+                TimeControl::from_string(self.new_time_value.trim())
+                    .map_err(|error| error.to_string())?,
             )
-            //End of Column
-            // Start of column
+        };
+        let variant = if self.new_variant_value.trim().is_empty() {
+            Variant::Standard
+        } else {
+            Variant::from_string(&self.new_variant_value).map_err(|error| error.to_string())?
+        };
+        Ok((time_control, players, variant))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_game_dialog<'a>(
+        white_state: &'a mut text_input::State,
+        white_value: &str,
+        black_state: &'a mut text_input::State,
+        black_value: &str,
+        time_state: &'a mut text_input::State,
+        time_value: &str,
+        variant_state: &'a mut text_input::State,
+        variant_value: &str,
+        start_state: &'a mut button::State,
+        cancel_state: &'a mut button::State,
+    ) -> Column<'a, Message> {
+        Column::new()
+            .spacing(5)
+            .width(Length::from(MOVE_LIST_WIDTH))
+            .push(Text::new("New game").size(30))
+            .push(Text::new("White (human, engine or uci:<engine>)"))
+            .push(TextInput::new(
+                white_state,
+                "human",
+                white_value,
+                Message::NewGameWhiteChanged,
+            ))
+            .push(Text::new("Black (human, engine or uci:<engine>)"))
+            .push(TextInput::new(
+                black_state,
+                "human",
+                black_value,
+                Message::NewGameBlackChanged,
+            ))
+            .push(Text::new("Time control"))
+            .push(TextInput::new(
+                time_state,
+                "none, 5+3, 40/90+30",
+                time_value,
+                Message::NewGameTimeChanged,
+            ))
+            .push(Text::new("Variant"))
+            .push(TextInput::new(
+                variant_state,
+                "standard, crazyhouse, threecheck, kingofthehill, atomic, antichess, horde, racingkings",
+                variant_value,
+                Message::NewGameVariantChanged,
+            ))
             .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
+                Row::new()
+                    .spacing(10)
                     .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
+                        Button::new(start_state, Text::new("Start"))
+                            .on_press(Message::StartNewGame),
                     )
                     .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(3, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
+                        Button::new(cancel_state, Text::new("Cancel"))
+                            .on_press(Message::HideNewGame),
                     ),
             )
-            //End of Column
-            // Start of column
+    }
+
+    // The picker has to be answered before the board can be used again
+    fn board_accepts_moves(&self) -> bool {
+        !self.game.is_over()
+            && self.pending_promotion.is_none()
+            && self.viewing_ply.is_none()
+            && self.human_to_move()
+    }
+
+    fn human_to_move(&self) -> bool {
+        self.game
+            .players()
+            .get(&self.game.side_to_move())
+            .is_human()
+    }
+
+    // Sets the engine thinking on its own thread when it is the engine's turn so the window
+    // keeps responding
+    fn engine_turn_command(&mut self) -> Command<Message> {
+        if self.game.is_over() || self.engine_thinking {
+            return Command::none();
+        }
+        let current_move_color = self.game.side_to_move();
+        let engine = match current_move_color {
+            PieceColor::WHITE => self.white_engine.clone(),
+            PieceColor::BLACK => self.black_engine.clone(),
+        };
+        let engine = match engine {
+            Some(engine) => engine,
+            None => return Command::none(),
+        };
+        let limit = self
+            .game
+            .players()
+            .get(&current_move_color)
+            .search_limit(self.game.clock(), &current_move_color);
+        let start_board = self.game.start_board().clone();
+        let board = self.game.board().clone();
+        let game_number = self.game_number;
+        self.engine_thinking = true;
+
+        let (sender, receiver) = oneshot::channel();
+        thread::spawn(move || {
+            let engine_move = match engine.lock() {
+                Ok(mut engine) => engine
+                    .best_move(&start_board, &board, &limit)
+                    .map_err(|error| error.to_string()),
+                Err(_) => Err(String::from("The engine stopped working")),
+            };
+            let _ = sender.send(engine_move);
+        });
+        Command::perform(
+            async move {
+                receiver
+                    .await
+                    .unwrap_or_else(|_| Err(String::from("The engine stopped working")))
+            },
+            move |engine_move| Message::EngineMoved(game_number, engine_move),
+        )
+    }
+
+    fn square_clicked(&mut self, x_coord: usize, y_coord: usize) {
+        if let Some(selected_square) = self.selected_square {
+            if self.selected_targets.contains(&(x_coord, y_coord)) {
+                self.play_clicked_move(selected_square, (x_coord, y_coord));
+                return;
+            }
+        }
+
+        let current_move_color = self.game.side_to_move();
+        let mut board = self.game.board().clone();
+        self.selected_square = match board.position_board[x_coord][y_coord] {
+            QuickPiece::PIECE(color) | QuickPiece::KING(color)
+                if color == current_move_color
+                    && self.selected_square != Some((x_coord, y_coord)) =>
+            {
+                Some((x_coord, y_coord))
+            }
+            _ => None,
+        };
+        self.selected_targets = match self.selected_square {
+            Some((x, y)) => game::legal_moves_from(x, y, &mut board),
+            None => Vec::new(),
+        };
+    }
+
+    // A piece dragged from start and let go on end.  Anything but a legal move puts it back
+    fn piece_dropped(&mut self, start: (usize, usize), end: (usize, usize)) {
+        let mut board = self.game.board().clone();
+        let is_legal = game::legal_moves_from(start.0, start.1, &mut board).contains(&end);
+        self.selected_square = None;
+        self.selected_targets.clear();
+        if is_legal {
+            self.play_clicked_move(start, end);
+        }
+    }
+
+    // Clicking the piece that is already there takes it off again
+    fn setup_square_clicked(&mut self, x_coord: usize, y_coord: usize) {
+        let setup = match &mut self.setup {
+            Some(setup) => setup,
+            None => return,
+        };
+        let square = (x_coord, y_coord);
+        let edited = match self.setup_tool {
+            SetupTool::Piece(letter) if setup.piece_at(x_coord, y_coord) == Some(letter) => {
+                setup.set_piece(x_coord, y_coord, None);
+                Ok(())
+            }
+            SetupTool::Piece(letter) => {
+                setup.set_piece(x_coord, y_coord, Some(letter));
+                Ok(())
+            }
+            SetupTool::Erase => {
+                setup.set_piece(x_coord, y_coord, None);
+                Ok(())
+            }
+            SetupTool::EnPassant if setup.en_passant() == Some(square) => {
+                setup.set_en_passant(None)
+            }
+            SetupTool::EnPassant => setup.set_en_passant(Some(square)),
+        };
+        match edited {
+            Ok(_) => self.setup_changed(),
+            Err(error) => self.wrong_move_string = error.to_string(),
+        }
+    }
+
+    // Keeps the FEN box in step with the position being set up
+    fn setup_changed(&mut self) {
+        if let Some(setup) = &self.setup {
+            self.setup_fen_value = setup.to_fen();
+        }
+        self.wrong_move_string = "".to_string();
+    }
+
+    // The set up position is played with the players and time control of the new game settings
+    fn play_setup(&mut self) -> Result<(), String> {
+        let board = match &self.setup {
+            Some(setup) => setup.to_board().map_err(|error| error.to_string())?,
+            None => return Ok(()),
+        };
+        let (time_control, players, _) = self.new_game_settings()?;
+        self.start_game_from_board(time_control, players, board)
+            .map_err(|error| error.to_string())?;
+        self.setup = None;
+        Ok(())
+    }
+
+    fn orientation_row<'a>(
+        flip_state: &'a mut button::State,
+        auto_orient: bool,
+    ) -> Row<'a, Message> {
+        Row::new()
+            .spacing(20)
+            .push(Button::new(flip_state, Text::new("Flip board")).on_press(FlipBoard))
+            .push(Checkbox::new(
+                auto_orient,
+                "Turn the board to the side on move",
+                AutoOrientToggled,
+            ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn setup_panel<'a>(
+        setup: &PositionSetup,
+        board: &Board,
+        setup_tool: SetupTool,
+        tool_states: &'a mut Vec<button::State>,
+        side_state: &'a mut button::State,
+        clear_state: &'a mut button::State,
+        standard_state: &'a mut button::State,
+        fen_state: &'a mut text_input::State,
+        fen_value: &str,
+        play_state: &'a mut button::State,
+        cancel_state: &'a mut button::State,
+    ) -> Column<'a, Message> {
+        // The fairy pieces the board knows go after the standard ones
+        let fairy_letters: Vec<char> = board
+            .fairy_pieces
+            .iter()
+            .filter_map(|piece_type| piece_type.letter().chars().next())
+            .collect();
+        let white_letters = SETUP_PIECES[..6]
+            .iter()
+            .copied()
+            .chain(fairy_letters.clone());
+        let black_letters = SETUP_PIECES[6..].iter().copied().chain(
+            fairy_letters
+                .iter()
+                .map(|letter| letter.to_ascii_lowercase()),
+        );
+        let tools: Vec<SetupTool> = white_letters
+            .chain(black_letters)
+            .map(SetupTool::Piece)
+            .chain([SetupTool::Erase, SetupTool::EnPassant])
+            .collect();
+        tool_states.resize_with(tools.len(), button::State::default);
+        // White pieces, black pieces and then the other tools
+        let mut palette_rows: Vec<Vec<Element<'a, Message>>> = (0..3).map(|_| Vec::new()).collect();
+        for (tool, state) in tools.into_iter().zip(tool_states.iter_mut()) {
+            let palette_row = match tool {
+                SetupTool::Piece(letter) if letter.is_ascii_uppercase() => 0,
+                SetupTool::Piece(_) => 1,
+                _ => 2,
+            };
+            let content: Element<'a, Message> = match tool {
+                SetupTool::Piece(letter) => {
+                    let color = if letter.is_ascii_uppercase() {
+                        PieceColor::WHITE
+                    } else {
+                        PieceColor::BLACK
+                    };
+                    let piece = board
+                        .piece_from_character(&letter.to_ascii_uppercase().to_string(), 0, 0, color)
+                        .unwrap();
+                    Image::new(GuiRunner::image_path(&game::file_name_from_piece(
+                        &piece, &color,
+                    )))
+                    .width(Length::from(SQUARE_SIZE / 3))
+                    .height(Length::from(SQUARE_SIZE / 3))
+                    .into()
+                }
+                SetupTool::Erase => Text::new("Erase").into(),
+                SetupTool::EnPassant => Text::new("En passant").into(),
+            };
+            palette_rows[palette_row].push(
+                Button::new(state, content)
+                    .style(MoveListStyle {
+                        current: tool == setup_tool,
+                    })
+                    .on_press(Message::SetupToolChosen(tool))
+                    .into(),
+            );
+        }
+
+        let mut panel = Column::new()
+            .spacing(5)
+            .width(Length::from(MOVE_LIST_WIDTH))
+            .push(Text::new("Set up position").size(30));
+        for palette_row in palette_rows {
+            panel = panel.push(Row::with_children(palette_row).spacing(2));
+        }
+        panel = panel.push(
+            Button::new(
+                side_state,
+                Text::new(format!("{:?} to move", setup.side_to_move)),
+            )
+            .on_press(Message::SetupSideToggled),
+        );
+        for (index, label) in CASTLING_LABELS.iter().enumerate() {
+            panel = panel.push(Checkbox::new(setup.castling[index], *label, move |given| {
+                Message::SetupCastlingToggled(index, given)
+            }));
+        }
+        panel
             .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
+                Row::new()
+                    .spacing(10)
                     .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
+                        Button::new(clear_state, Text::new("Clear")).on_press(Message::SetupClear),
                     )
                     .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(4, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
+                        Button::new(standard_state, Text::new("Standard"))
+                            .on_press(Message::SetupStandard),
                     ),
             )
-            //End of Column
-            // Start of column
+            .push(Text::new("FEN"))
             .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(5, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    ),
+                TextInput::new(fen_state, "FEN", fen_value, Message::SetupFenChanged)
+                    .padding(5)
+                    .on_submit(Message::SetupLoadFen),
             )
-            //End of Column
-            // Start of column
             .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
+                Row::new()
+                    .spacing(10)
+                    .push(Button::new(play_state, Text::new("Play")).on_press(Message::PlaySetup))
                     .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(6, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
+                        Button::new(cancel_state, Text::new("Cancel")).on_press(Message::HideSetup),
                     ),
             )
-            //End of Column
-            // Start of column
+    }
+
+    // The board being set up with the setup panel beside it
+    fn setup_view(&mut self) -> Element<'_, Message> {
+        let white_at_bottom = self.white_at_bottom();
+        let setup = match &self.setup {
+            Some(setup) => setup,
+            None => return Row::new().into(),
+        };
+        // Only a piece the board doesn't know can stop it being read, so draw it empty then
+        let board = setup
+            .board()
+            .or_else(|_| setup.empty_board())
+            .unwrap_or_else(|_| Board::empty(setup.width(), setup.height()).unwrap());
+        let highlights = BoardHighlights {
+            selected_square: setup.en_passant(),
+            ..Default::default()
+        };
+        let board_view = GuiRunner::create_container_from_board(
+            &board,
+            &mut self.drag_state,
+            &highlights,
+            white_at_bottom,
+            None,
+        );
+        let col = Column::new()
             .push(
-                Column::new()
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 7)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 6)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 5)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 4)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 3)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 2)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 1)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    )
-                    .push(
-                        Image::new(format!(
-                            "{}/resources/{}",
-                            env!("CARGO_MANIFEST_DIR"),
-                            file_name_hash_map.get(&(7, 0)).unwrap()
-                        ))
-                        .height(Length::from(100))
-                        .height(Length::from(100)),
-                    ),
-            );
-        //End of Column
-        row
+                Container::new(board_view)
+                    .width(Length::Fill)
+                    .height(Length::Fill),
+            )
+            .push(GuiRunner::orientation_row(
+                &mut self.flip_state,
+                self.auto_orient,
+            ))
+            .push(Text::new(&self.wrong_move_string));
+        let panel = GuiRunner::setup_panel(
+            setup,
+            &board,
+            self.setup_tool,
+            &mut self.setup_tool_states,
+            &mut self.setup_side_state,
+            &mut self.setup_clear_state,
+            &mut self.setup_standard_state,
+            &mut self.setup_fen_state,
+            &self.setup_fen_value,
+            &mut self.setup_play_state,
+            &mut self.setup_cancel_state,
+        );
+        Row::new().push(col).push(panel).into()
     }
 }
 
@@ -673,23 +1144,78 @@ pub enum Message {
     DecrementPressed,
     InputChanged(String),
     PlayMove,
+    SquareClicked(usize, usize),
+    PieceDropped((usize, usize), (usize, usize)),
+    PromotionChosen(&'static str),
+    PromotionCancelled,
+    FlipBoard,
+    AutoOrientToggled(bool),
+    ShowPly(usize),
+    HistoryBack,
+    HistoryForward,
+    HistoryStart,
+    HistoryEnd,
+    BranchFromHere,
+    EngineMoved(u32, Result<ResolvedMove, String>),
+    ShowNewGame,
+    HideNewGame,
+    NewGameWhiteChanged(String),
+    NewGameBlackChanged(String),
+    NewGameTimeChanged(String),
+    NewGameVariantChanged(String),
+    StartNewGame,
+    Resign,
+    OfferDraw,
+    AcceptDraw,
+    DeclineDraw,
+    WithdrawDraw,
+    FilePathChanged(String),
+    SaveGame,
+    OpenGame,
+    ShowSetup,
+    HideSetup,
+    SetupToolChosen(SetupTool),
+    SetupSideToggled,
+    SetupCastlingToggled(usize, bool),
+    SetupClear,
+    SetupStandard,
+    SetupFenChanged(String),
+    SetupLoadFen,
+    PlaySetup,
+    Tick(Instant),
 }
 
-impl Sandbox for GuiRunner {
+impl Application for GuiRunner {
+    type Executor = executor::Default;
     type Message = Message;
+    type Flags = (Option<TimeControl>, Players, Variant);
 
-    fn new() -> Self {
-        GuiRunner {
-            game_continue: true,
-            ..Default::default()
+    fn new(
+        (time_control, players, variant): (Option<TimeControl>, Players, Variant),
+    ) -> (Self, Command<Message>) {
+        let mut gui_runner = GuiRunner::default();
+        if let Err(error) = gui_runner.start_game(time_control, players, variant) {
+            gui_runner.wrong_move_string = error.to_string();
         }
+        let command = gui_runner.engine_turn_command();
+        (gui_runner, command)
     }
 
     fn title(&self) -> String {
         String::from("Counter - Iced")
     }
 
-    fn update(&mut self, message: Message) {
+    fn subscription(&self) -> Subscription<Message> {
+        let keys = subscription::events_with(GuiRunner::keyboard_message);
+        match self.game.clock() {
+            Some(clock) if clock.running().is_some() => {
+                Subscription::batch(vec![time::every(CLOCK_TICK_RATE).map(Message::Tick), keys])
+            }
+            _ => keys,
+        }
+    }
+
+    fn update(&mut self, message: Message, _clipboard: &mut Clipboard) -> Command<Message> {
         match message {
             Message::IncrementPressed => {
                 self.value += 1;
@@ -700,59 +1226,264 @@ impl Sandbox for GuiRunner {
             Message::InputChanged(string_value) => {
                 self.text_value = string_value;
             }
+            Message::PromotionChosen(promotion_piece) => {
+                if let Some((start, end)) = self.pending_promotion.take() {
+                    self.play_move_from_coords(start, end, Some(promotion_piece));
+                }
+            }
+            Message::PromotionCancelled => {
+                self.pending_promotion = None;
+            }
+            Message::EngineMoved(game_number, engine_move) => {
+                if game_number == self.game_number {
+                    self.engine_thinking = false;
+                    match engine_move {
+                        Ok((start, end, promotion_piece)) => {
+                            self.play_move_from_coords(start, end, promotion_piece.as_deref())
+                        }
+                        Err(error) => {
+                            // Nobody is left to move for that side so it loses
+                            let side_to_move = self.game.side_to_move();
+                            self.game.abandon(side_to_move);
+                            self.show_events();
+                            self.wrong_move_string = error;
+                        }
+                    }
+                }
+            }
+            Message::ShowNewGame => self.new_game_open = true,
+            Message::HideNewGame => self.new_game_open = false,
+            Message::NewGameWhiteChanged(value) => self.new_white_value = value,
+            Message::NewGameBlackChanged(value) => self.new_black_value = value,
+            Message::NewGameTimeChanged(value) => self.new_time_value = value,
+            Message::NewGameVariantChanged(value) => self.new_variant_value = value,
+            Message::StartNewGame => match self.new_game_settings() {
+                Ok((time_control, players, variant)) => {
+                    match self.start_game(time_control, players, variant) {
+                        Ok(_) => self.new_game_open = false,
+                        Err(error) => self.wrong_move_string = error.to_string(),
+                    }
+                }
+                Err(error) => self.wrong_move_string = error,
+            },
+            Message::Resign
+            | Message::OfferDraw
+            | Message::AcceptDraw
+            | Message::DeclineDraw
+            | Message::WithdrawDraw => {
+                let acting_color = match self.acting_color() {
+                    Some(acting_color) if !self.game.is_over() => acting_color,
+                    _ => return Command::none(),
+                };
+                let answered = match message {
+                    Message::Resign => self.game.resign(acting_color),
+                    Message::OfferDraw => self.game.offer_draw(acting_color),
+                    Message::AcceptDraw => self.game.accept_draw(acting_color),
+                    Message::DeclineDraw => self.game.decline_draw(acting_color),
+                    _ => self.game.withdraw_draw(acting_color),
+                };
+                match answered {
+                    Ok(_) => self.show_events(),
+                    Err(error) => self.wrong_move_string = error.to_string(),
+                }
+            }
+            Message::FilePathChanged(value) => self.file_path_value = value,
+            Message::SaveGame => {
+                let path = self.file_path_value.trim().to_string();
+                self.wrong_move_string = match self.save_game(&path) {
+                    Ok(_) => format!("Saved the game to {}", path),
+                    Err(error) => error.to_string(),
+                };
+            }
+            Message::OpenGame => {
+                let path = self.file_path_value.trim().to_string();
+                if let Err(error) = self.open_game(&path) {
+                    self.wrong_move_string = error.to_string();
+                }
+            }
+            Message::ShowSetup => {
+                self.setup = Some(PositionSetup::from_board(self.shown_board()));
+                self.new_game_open = false;
+                self.setup_changed();
+            }
+            Message::HideSetup => self.setup = None,
+            Message::SetupToolChosen(tool) => self.setup_tool = tool,
+            Message::SetupSideToggled => {
+                if let Some(setup) = &mut self.setup {
+                    setup.side_to_move = PieceColor::opposite_color(&setup.side_to_move);
+                }
+                self.setup_changed();
+            }
+            Message::SetupCastlingToggled(index, given) => {
+                if let Some(setup) = &mut self.setup {
+                    setup.castling[index] = given;
+                }
+                self.setup_changed();
+            }
+            Message::SetupClear => {
+                if let Some(setup) = &mut self.setup {
+                    setup.clear();
+                }
+                self.setup_changed();
+            }
+            Message::SetupStandard => {
+                self.setup = Some(PositionSetup::standard());
+                self.setup_changed();
+            }
+            Message::SetupFenChanged(value) => self.setup_fen_value = value,
+            Message::SetupLoadFen => {
+                if let Some(setup) = &mut self.setup {
+                    match setup.load_fen(self.setup_fen_value.trim()) {
+                        Ok(()) => self.setup_changed(),
+                        Err(error) => self.wrong_move_string = error.to_string(),
+                    }
+                }
+            }
+            Message::PlaySetup => {
+                if let Err(error) = self.play_setup() {
+                    self.wrong_move_string = error;
+                }
+            }
+            Message::ShowPly(ply) => self.show_ply(ply),
+            Message::HistoryBack => self.show_ply(self.current_ply().saturating_sub(1)),
+            Message::HistoryForward => self.show_ply(self.current_ply() + 1),
+            Message::HistoryStart => self.show_ply(0),
+            Message::HistoryEnd => self.show_ply(self.game.moves().len()),
+            Message::BranchFromHere => self.branch_from_shown_ply(),
+            Message::FlipBoard => {
+                self.flipped = !self.flipped;
+            }
+            Message::AutoOrientToggled(auto_orient) => {
+                self.auto_orient = auto_orient;
+            }
+            Message::SquareClicked(x_coord, y_coord) if self.setup.is_some() => {
+                self.setup_square_clicked(x_coord, y_coord)
+            }
+            Message::SquareClicked(x_coord, y_coord) => {
+                if self.board_accepts_moves() {
+                    self.square_clicked(x_coord, y_coord);
+                }
+            }
+            Message::PieceDropped(start, end) if self.setup.is_none() => {
+                if self.board_accepts_moves() {
+                    self.piece_dropped(start, end);
+                }
+            }
+            Message::PieceDropped(..) => {}
+            Message::Tick(now) => {
+                if let Some(last_tick) = self.last_tick {
+                    self.game.tick(now - last_tick);
+                    self.show_events();
+                }
+                self.last_tick = Some(now);
+            }
             Message::PlayMove => {
-                println!("This is working");
-                println!("CONT:{}", self.game_continue);
-                if !self.game_continue {
-                    return;
+                if self.game.is_over() || self.viewing_ply.is_some() || !self.human_to_move() {
+                    return Command::none();
                 }
                 let string_value = &self.text_value;
                 // First is this a valid move.  If it's not then I want to update the second line
                 // saying it's invalid
 
-                // I will need to add a draw response
-                if string_value == &"draw".to_string() {
-                    // Do draw things
+                if string_value.trim() == "draw" {
+                    return self.update(Message::OfferDraw, _clipboard);
                 } else {
-                    match game::player_move(&mut self.board, string_value) {
-                        Ok(_) => {
-                            if game::is_board_check_mate(
-                                &self.board.last_move_color.clone(),
-                                &mut self.board,
-                            ) {
-                                self.game_continue = false;
-                                self.wrong_move_string =
-                                    format!("{:?} Wins with checkmate", self.board.last_move_color);
-                            // This is where we would change to the next screen if I had one.
-                            } else {
-                                self.text_value = "".to_string();
-                                let _ = self.wrong_move_string.is_empty();
-                            }
-                        } // Do valid move things,
+                    // Typed moves go through the same path as clicked ones so they end up in the move list
+                    match self.game.resolve_move(string_value.trim()) {
+                        Ok((start, end, Some(promotion_piece))) => {
+                            self.play_move_from_coords(start, end, Some(&promotion_piece))
+                        }
+                        Ok((start, end, None)) => self.play_clicked_move(start, end),
                         Err(error) => self.wrong_move_string = error.to_string(),
                     }
                 }
             }
         }
+        self.engine_turn_command()
     }
 
-    fn view(&mut self) -> Element<Message> {
-        let row = GuiRunner::create_container_from_board(&self.board);
-        let _text_input = TextInput::new(
-            &mut self.text_state,
-            "Type something to continue...",
-            &self.text_value,
-            Message::InputChanged,
+    fn view(&mut self) -> Element<'_, Message> {
+        if self.setup.is_some() {
+            return self.setup_view();
+        }
+        let acting_color = self.acting_color();
+        let highlights = self.board_highlights();
+        let white_at_bottom = self.white_at_bottom();
+        let draggable = if self.board_accepts_moves() {
+            Some(self.game.side_to_move())
+        } else {
+            None
+        };
+        let board_view = GuiRunner::create_container_from_board(
+            self.view_board.as_ref().unwrap_or(self.game.board()),
+            &mut self.drag_state,
+            &highlights,
+            white_at_bottom,
+            draggable,
         );
 
-        let container = Container::new(row).width(Length::Fill).height(Length::Fill);
+        let container = Container::new(board_view)
+            .width(Length::Fill)
+            .height(Length::Fill);
 
-        let col = Column::new()
-            .push(container)
+        // The clock of whoever is at the bottom goes under the board
+        let (top_color, bottom_color) = if white_at_bottom {
+            (PieceColor::BLACK, PieceColor::WHITE)
+        } else {
+            (PieceColor::WHITE, PieceColor::BLACK)
+        };
+        let mut col = Column::new();
+        if let Some(clock) = self.game.clock() {
+            col = col.push(GuiRunner::clock_display(clock, &top_color));
+        }
+        col = col.push(container);
+        if let Some(clock) = self.game.clock() {
+            col = col.push(GuiRunner::clock_display(clock, &bottom_color));
+        }
+        let col = col.push(GuiRunner::orientation_row(
+            &mut self.flip_state,
+            self.auto_orient,
+        ));
+        let col = match (acting_color, self.game.is_over()) {
+            (Some(acting_color), false) => col.push(GuiRunner::draw_and_resign_row(
+                acting_color,
+                self.game.draw_offer(),
+                &mut self.resign_state,
+                &mut self.draw_state,
+                &mut self.decline_draw_state,
+            )),
+            _ => col,
+        };
+        // Crazyhouse pieces are dropped by typing them in the move box, N@f3
+        let col = match &self.game.board().pockets {
+            Some(pockets) => col.push(Text::new(format!(
+                "Pockets  White: {}  Black: {}",
+                pockets.pieces(&PieceColor::WHITE).join(" "),
+                pockets.pieces(&PieceColor::BLACK).join(" ")
+            ))),
+            None => col,
+        };
+        let col = match self.game.board().variant {
+            Variant::ThreeCheck => col.push(Text::new(format!(
+                "Checks  White: {}  Black: {}",
+                self.game.board().checks_given(&PieceColor::WHITE),
+                self.game.board().checks_given(&PieceColor::BLACK)
+            ))),
+            _ => col,
+        };
+        let col = col
             .push(Text::new(format!(
                 "CurrentMoveColor:{:?}",
-                PieceColor::opposite_color(&self.board.last_move_color)
+                self.game.side_to_move()
             )))
+            .push(match self.pending_promotion {
+                Some(_) => GuiRunner::promotion_picker(
+                    self.game.board(),
+                    &mut self.promotion_states,
+                    &mut self.cancel_promotion_state,
+                ),
+                None => Row::new(),
+            })
             .push(Text::new(if !self.wrong_move_string.is_empty() {
                 &self.wrong_move_string
             } else {
@@ -768,6 +1499,56 @@ impl Sandbox for GuiRunner {
                 .on_submit(PlayMove),
             );
         //.push(Row::new().push(text_input).push(Button::new(&mut self.increment_button, IncrementPressed)));
-        col.into()
+
+        let current_ply = self.viewing_ply.unwrap_or(self.game.moves().len());
+        let mut move_panel = Column::new().spacing(10);
+        move_panel = if self.new_game_open {
+            move_panel.push(GuiRunner::new_game_dialog(
+                &mut self.new_white_state,
+                &self.new_white_value,
+                &mut self.new_black_state,
+                &self.new_black_value,
+                &mut self.new_time_state,
+                &self.new_time_value,
+                &mut self.new_variant_state,
+                &self.new_variant_value,
+                &mut self.start_game_state,
+                &mut self.cancel_new_game_state,
+            ))
+        } else {
+            move_panel.push(
+                Row::new()
+                    .spacing(10)
+                    .push(
+                        Button::new(&mut self.new_game_state, Text::new("New game"))
+                            .on_press(Message::ShowNewGame),
+                    )
+                    .push(
+                        Button::new(&mut self.setup_state, Text::new("Set up position"))
+                            .on_press(Message::ShowSetup),
+                    ),
+            )
+        };
+        move_panel = move_panel
+            .push(GuiRunner::file_row(
+                &mut self.file_path_state,
+                &self.file_path_value,
+                &mut self.save_state,
+                &mut self.open_state,
+            ))
+            .push(Text::new("Moves").size(30))
+            .push(GuiRunner::move_list(
+                self.game.moves(),
+                current_ply,
+                &mut self.move_button_states,
+                &mut self.move_list_state,
+            ));
+        if self.viewing_ply.is_some() {
+            move_panel = move_panel.push(
+                Button::new(&mut self.branch_state, Text::new("Play from here"))
+                    .on_press(Message::BranchFromHere),
+            );
+        }
+        Row::new().push(col).push(move_panel).into()
     }
 }
@@ -1,6 +1,25 @@
 pub mod board;
+pub mod board_display;
+pub mod board_widget;
+pub mod chess_game;
+pub mod cli;
+pub mod clock;
+pub mod controller;
+pub mod engine;
+pub mod engine_match;
+pub mod epd;
 pub mod game;
 pub mod gui_runner;
 pub mod parser;
+pub mod pgn;
 pub mod piece_types;
 pub mod pieces;
+pub mod player;
+pub mod pocket;
+pub mod san;
+pub mod setup;
+pub mod sprt;
+pub mod uci;
+pub mod validation;
+pub mod variant;
+pub mod xboard;
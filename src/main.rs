@@ -1,20 +1,280 @@
+use board_display::DisplayOptions;
+use clock::TimeControl;
+use engine_match::{DrawRule, MatchOptions, ResignRule};
+use player::{Player, Players};
+use sprt::Sprt;
 use std::env;
+use std::io;
+use std::time::Duration;
+use uci::SearchLimit;
+use variant::Variant;
 
 pub mod board;
+pub mod board_display;
+pub mod board_widget;
+pub mod chess_game;
+pub mod cli;
+pub mod clock;
+pub mod controller;
+pub mod engine;
+pub mod engine_match;
+pub mod epd;
 pub mod game;
 pub mod gui_runner;
 pub mod parser;
+pub mod pgn;
 pub mod piece_types;
 pub mod pieces;
+pub mod player;
+pub mod pocket;
+pub mod san;
+pub mod setup;
+pub mod sprt;
+pub mod uci;
+pub mod validation;
+pub mod variant;
+pub mod xboard;
+
+// The options of chess play
+struct PlayArguments {
+    time_control: Option<TimeControl>,
+    players: Players,
+    variant: Variant,
+    use_gui: bool,
+    display_options: DisplayOptions,
+    script: Option<String>,
+}
+
+// chess play [--white <player>] [--black <player>] [--time <time control>] [--variant <name>]
+// [--gui] [--unicode] [--color] [--script <file>]
+fn parse_play_arguments(arguments: &[String]) -> Result<PlayArguments, String> {
+    let mut time_control = None;
+    let mut players = Players::default();
+    let mut variant = Variant::default();
+    let mut use_gui = false;
+    let mut display_options = DisplayOptions::default();
+    let mut script = None;
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--gui" => use_gui = true,
+            "--unicode" => display_options.unicode = true,
+            "--color" => display_options.colors = true,
+            "--white" | "--black" | "--time" | "--variant" | "--script" => {
+                let value = arguments
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", argument))?;
+                match argument.as_str() {
+                    "--white" => {
+                        players.white = Player::from_string(value).map_err(|e| e.to_string())?
+                    }
+                    "--black" => {
+                        players.black = Player::from_string(value).map_err(|e| e.to_string())?
+                    }
+                    "--variant" => {
+                        variant = Variant::from_string(value).map_err(|e| e.to_string())?
+                    }
+                    "--script" => script = Some(value.clone()),
+                    _ => {
+                        time_control =
+                            Some(TimeControl::from_string(value).map_err(|e| e.to_string())?)
+                    }
+                }
+            }
+            _ => return Err(format!("Unknown option {:?}", argument)),
+        }
+    }
+    Ok(PlayArguments {
+        time_control,
+        players,
+        variant,
+        use_gui,
+        display_options,
+        script,
+    })
+}
+
+// How long a losing score has to last before an engine resigns, and a level one before a draw
+const DEFAULT_RESIGN_MOVES: u32 = 3;
+const DEFAULT_DRAW_MOVES: u32 = 8;
+const DEFAULT_DRAW_AFTER: u32 = 40;
+// An SPRT runs until it is decided unless --games says otherwise
+const DEFAULT_SPRT_GAMES: usize = 100_000;
+
+// chess match --engine <player> --engine <player> [--games N] [--openings <epd or pgn file>]
+// [--pgn <file>] [--time <time control>] [--resign-score <cp>] [--resign-moves N]
+// [--draw-score <cp>] [--draw-moves N] [--draw-after <move number>]
+// [--sprt elo0=<elo>,elo1=<elo>[,alpha=<p>][,beta=<p>]]
+fn parse_match_arguments(arguments: &[String]) -> Result<MatchOptions, String> {
+    let mut options = MatchOptions::default();
+    let mut resign_score = None;
+    let mut resign_moves = DEFAULT_RESIGN_MOVES;
+    let mut draw_score = None;
+    let mut draw_moves = DEFAULT_DRAW_MOVES;
+    let mut draw_after = DEFAULT_DRAW_AFTER;
+    let mut games = None;
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        let value = arguments
+            .next()
+            .ok_or_else(|| format!("{} needs a value", argument))?;
+        let number = || {
+            value
+                .parse::<u32>()
+                .map_err(|_| format!("{} needs a number, not {:?}", argument, value))
+        };
+        match argument.as_str() {
+            "--engine" => options
+                .engines
+                .push(Player::from_string(value).map_err(|e| e.to_string())?),
+            "--games" => games = Some(number()? as usize),
+            "--openings" => options.openings = Some(value.clone()),
+            "--pgn" => options.pgn = Some(value.clone()),
+            "--time" => {
+                options.settings.time_control =
+                    Some(TimeControl::from_string(value).map_err(|e| e.to_string())?)
+            }
+            "--resign-score" => resign_score = Some(number()? as i32),
+            "--resign-moves" => resign_moves = number()?,
+            "--draw-score" => draw_score = Some(number()? as i32),
+            "--draw-moves" => draw_moves = number()?,
+            "--draw-after" => draw_after = number()?,
+            "--sprt" => options.sprt = Some(Sprt::from_string(value).map_err(|e| e.to_string())?),
+            _ => return Err(format!("Unknown option {:?}", argument)),
+        }
+    }
+    if options.engines.len() != 2 {
+        return Err(String::from("A match needs two --engine options"));
+    }
+    options.games = match (games, options.sprt) {
+        (Some(games), _) => games,
+        (None, Some(_)) => DEFAULT_SPRT_GAMES,
+        (None, None) => options.games,
+    };
+    options.settings.resign = resign_score.map(|score| ResignRule {
+        score,
+        moves: resign_moves,
+    });
+    options.settings.draw = draw_score.map(|score| DrawRule {
+        after_move: draw_after,
+        score,
+        moves: draw_moves,
+    });
+    Ok(options)
+}
+
+// chess epd <suite> --engine <player> [--movetime <milliseconds>]
+fn parse_epd_arguments(
+    arguments: &[String],
+) -> Result<(String, Player, Option<SearchLimit>), String> {
+    let (suite, arguments) = arguments
+        .split_first()
+        .ok_or_else(|| String::from("chess epd needs an EPD file"))?;
+    let mut player = None;
+    let mut limit = None;
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        let value = arguments
+            .next()
+            .ok_or_else(|| format!("{} needs a value", argument))?;
+        match argument.as_str() {
+            "--engine" => player = Some(Player::from_string(value).map_err(|e| e.to_string())?),
+            "--movetime" => {
+                let milliseconds = value
+                    .parse()
+                    .map_err(|_| format!("--movetime needs a number, not {:?}", value))?;
+                limit = Some(SearchLimit::MoveTime(Duration::from_millis(milliseconds)))
+            }
+            _ => return Err(format!("Unknown option {:?}", argument)),
+        }
+    }
+    let player =
+        player.ok_or_else(|| String::from("chess epd needs --engine engine or uci:<path>"))?;
+    Ok((suite.clone(), player, limit))
+}
 
 fn main() {
     let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("play") {
+        match parse_play_arguments(&args[2..]) {
+            Ok(PlayArguments {
+                time_control,
+                players,
+                variant,
+                use_gui: true,
+                ..
+            }) => game::play_game_gui(time_control, players, variant),
+            Ok(PlayArguments {
+                time_control,
+                players,
+                variant,
+                display_options,
+                script,
+                ..
+            }) => cli::play_game_cli(
+                time_control,
+                players,
+                variant,
+                display_options,
+                script.as_deref(),
+            ),
+            Err(error) => println!("{}", error),
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("epd") {
+        let result = parse_epd_arguments(&args[2..])
+            .and_then(|(suite, player, limit)| epd::run_suite(&suite, &player, limit));
+        if let Err(error) = result {
+            println!("{}", error);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("xboard") {
+        if let Err(error) = xboard::run_xboard(io::stdin().lock(), &mut io::stdout()) {
+            println!("{}", error);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("match") {
+        let result =
+            parse_match_arguments(&args[2..]).and_then(|options| engine_match::run_match(&options));
+        if let Err(error) = result {
+            println!("{}", error);
+        }
+        return;
+    }
+
+    // An optional time control such as 5+3 or 40/90+30 can follow the mode
+    let time_control = match args.get(2) {
+        Some(time_control_string) => match TimeControl::from_string(time_control_string) {
+            Ok(time_control) => Some(time_control),
+            Err(error) => {
+                println!("{}", error);
+                return;
+            }
+        },
+        None => None,
+    };
+
     if args.len() > 1 {
         match args.get(1).unwrap().as_str() {
-            "gui" => game::play_game_gui(),
-            _ => game::play_game_cli(),
+            "gui" => game::play_game_gui(time_control, Players::default(), Variant::default()),
+            _ => cli::play_game_cli(
+                time_control,
+                Players::default(),
+                Variant::default(),
+                DisplayOptions::default(),
+                None,
+            ),
         };
     } else {
-        game::play_game_cli();
+        cli::play_game_cli(
+            time_control,
+            Players::default(),
+            Variant::default(),
+            DisplayOptions::default(),
+            None,
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crate::piece_types::PieceColor;
 use std::error::Error;
 use std::fmt;
 
@@ -34,7 +35,7 @@ pub struct ParseError {
 }
 
 impl ParseError {
-    fn new(msg: &str) -> ParseError {
+    pub(crate) fn new(msg: &str) -> ParseError {
         ParseError {
             details: msg.to_string(),
         }
@@ -57,12 +58,14 @@ pub enum MoveTypes {
     Move,
     Take,
     Promote(String),
-    Castle(usize), // This usize is going to be the end x position for the king
+    Castle(usize), // This usize is going to be the end x position for the king.  O-O reads as 6 and O-O-O as 2, the board works out the file from the side
+    /// A piece from the pocket put on the board, only in Crazyhouse
+    Drop,
     FinalResult(GameResult),
 }
 
 // @TODO add stalemate eventually
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum GameResult {
     WhiteWin,
     BlackWin,
@@ -78,6 +81,23 @@ impl GameResult {
             _ => panic!("Error parsing gameResults from string"), // @TODO will need a way to remove the panic in the future.
         }
     }
+
+    /// The win for the given color
+    pub fn win_for(color: &PieceColor) -> GameResult {
+        match color {
+            PieceColor::WHITE => GameResult::WhiteWin,
+            PieceColor::BLACK => GameResult::BlackWin,
+        }
+    }
+
+    /// The result the way PGN writes it
+    pub fn to_pgn_string(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWin => "1-0",
+            GameResult::BlackWin => "0-1",
+            GameResult::Draw => "1/2-1/2",
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -87,19 +107,16 @@ pub enum CheckOrCheckMate {
     Neither,
 }
 
-pub fn parse_game_moves(
-    game_string: String,
-) -> Vec<(
+pub type ParsedTurn = (
     String,
     Result<ParsedMove, ParseError>,
     Result<ParsedMove, ParseError>,
-)> {
+);
+
+pub fn parse_game_moves(game_string: String) -> Vec<ParsedTurn> {
     // Then I want to move in sets of 3
     let mut moves_vector = Vec::new();
-    let moves: Vec<String> = game_string
-        .split_whitespace()
-        .map(String::from)
-        .collect();
+    let moves: Vec<String> = game_string.split_whitespace().map(String::from).collect();
 
     for index in (0..moves.len() / 3)
         .map(|x| x * 3)
@@ -120,18 +137,114 @@ pub fn parse_game_moves(
     moves_vector
 }
 
+/// The most files or ranks a board can have.  Files stop at p so one is never mixed up with the x
+/// of a capture
+pub const MAX_BOARD_SIZE: usize = 16;
+
+// Splits a move into its characters but keeps the digits of a rank together, so e10 is e and 10
+fn split_move(move_string: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    for character in move_string.chars() {
+        match tokens.last_mut() {
+            Some(token)
+                if character.is_ascii_digit() && token.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                token.push(character)
+            }
+            _ => tokens.push(character.to_string()),
+        }
+    }
+    tokens
+}
+
+fn is_file(token: &str) -> bool {
+    matches!(token.as_bytes(), [file] if (b'a'..b'a' + MAX_BOARD_SIZE as u8).contains(file))
+}
+
+fn is_rank(token: &str) -> bool {
+    token.bytes().all(|digit| digit.is_ascii_digit())
+        && !token.starts_with('0')
+        && matches!(token.parse::<usize>(), Ok(1..=MAX_BOARD_SIZE))
+}
+
+// Checks the shape of a SAN move before it is picked apart so a typo is an error instead of a
+// panic.  Promotion letters are checked later so they get their own error
+fn is_well_formed_move(move_string: &str) -> bool {
+    let move_string = move_string.trim_end_matches(['+', '#']);
+    if matches!(move_string, "O-O" | "O-O-O" | "1-0" | "0-1" | "1/2-1/2") {
+        return true;
+    }
+    let tokens = split_move(move_string);
+    let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    match tokens.as_slice() {
+        // Drops, N@f3.  A pawn can leave off its letter
+        [piece, "@", file, rank] => {
+            (is_piece_token(piece) || *piece == "P")
+                && *piece != "K"
+                && is_file(file)
+                && is_rank(rank)
+        }
+        ["@", file, rank] => is_file(file) && is_rank(rank),
+        [piece, rest @ ..] if is_piece_token(piece) => match rest {
+            [start @ .., end_file, end_rank] if is_file(end_file) && is_rank(end_rank) => {
+                let start = start.strip_suffix(&["x"]).unwrap_or(start);
+                match start {
+                    [] => true,
+                    [square] => is_file(square) || is_rank(square),
+                    [file, rank] => is_file(file) && is_rank(rank),
+                    _ => false,
+                }
+            }
+            _ => false,
+        },
+        [file, rank, rest @ ..] if is_file(file) && is_rank(rank) => {
+            rest.is_empty() || rest[0] == "="
+        }
+        [file, "x", end_file, end_rank, rest @ ..]
+            if is_file(file) && is_file(end_file) && is_rank(end_rank) =>
+        {
+            rest.is_empty() || rest[0] == "="
+        }
+        _ => false,
+    }
+}
+
 pub fn parse_move(move_string: &str) -> Result<ParsedMove, ParseError> {
+    if !move_string.is_empty() && !is_well_formed_move(move_string) {
+        return Err(ParseError::new(&format!("{:?} is not a move", move_string)));
+    }
     let move_string = String::from(move_string);
+    if move_string.contains('@') {
+        return parse_drop(move_string);
+    }
     let mut characters = move_string.chars();
-    match characters.next().unwrap() {
-        'a' | 'b' | 'c' | 'd' | 'e' | 'f' | 'g' | 'h' => parse_pawn_move(move_string),
-        'K' | 'Q' | 'R' | 'B' | 'N' => parse_piece_move(move_string),
-        'O' => parse_castle(move_string),
-        '1' | '0' => parse_final_score(move_string),
-        _ => Err(ParseError::new("Move did not start with a piece")),
+    match characters.next() {
+        Some(file) if is_file(&file.to_string()) => parse_pawn_move(move_string),
+        Some(piece) if is_piece_letter(piece) => parse_piece_move(move_string),
+        Some('O') => parse_castle(move_string),
+        Some('1' | '0') => parse_final_score(move_string),
+        Some(_) => Err(ParseError::new("Move did not start with a piece")),
+        None => Err(ParseError::new("There is no move")),
     }
 }
 
+fn parse_drop(move_string: String) -> Result<ParsedMove, ParseError> {
+    let tokens = split_move(move_string.trim_end_matches(['+', '#']));
+    let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    let (piece_char, file, rank) = match tokens.as_slice() {
+        ["@", file, rank] => ("P", file, rank),
+        [piece_char, "@", file, rank] => (*piece_char, file, rank),
+        _ => return Err(ParseError::new(&format!("{:?} is not a drop", move_string))),
+    };
+    Ok(ParsedMove::new(
+        piece_char.to_string(),
+        (None, None),
+        (file.to_string(), rank.to_string()),
+        MoveTypes::Drop,
+        check_for_check_or_mate(&move_string),
+    ))
+}
+
 fn parse_final_score(move_string: String) -> Result<ParsedMove, ParseError> {
     Ok(ParsedMove::new(
         String::from(""),
@@ -184,178 +297,121 @@ fn parse_castle(move_string: String) -> Result<ParsedMove, ParseError> {
 }
 
 fn parse_piece_move(move_string: String) -> Result<ParsedMove, ParseError> {
-    let characters: Vec<char> = move_string.chars().collect();
+    let tokens = split_move(move_string.trim_end_matches(['+', '#']));
+    let (piece_char, start, end_coords) = match tokens.as_slice() {
+        [piece_char, start @ .., end_file, end_rank] => {
+            (piece_char, start, (end_file.clone(), end_rank.clone()))
+        }
+        _ => return Err(ParseError::new(&format!("{:?} is not a move", move_string))),
+    };
+    let (start, move_type) = match start.split_last() {
+        Some((take, start)) if take == "x" => (start, MoveTypes::Take),
+        _ => (start, MoveTypes::Move),
+    };
+    // The file, the rank or both can be given when two pieces could make the move
+    let starting_coords = match start {
+        [] => (None, None),
+        [rank] if is_rank(rank) => (None, Some(rank.clone())),
+        [file] => (Some(file.clone()), None),
+        [file, rank] => (Some(file.clone()), Some(rank.clone())),
+        _ => return Err(ParseError::new(&format!("{:?} is not a move", move_string))),
+    };
+    Ok(ParsedMove::new(
+        piece_char.clone(),
+        starting_coords,
+        end_coords,
+        move_type,
+        check_for_check_or_mate(&move_string),
+    ))
+}
 
-    match characters.get(1).unwrap() {
-        'x' => Ok(ParsedMove::new(
-            characters.get(0).unwrap().to_string(),
-            (None, None),
-            (
-                characters.get(2).unwrap().to_string(),
-                characters.get(3).unwrap().to_string(),
-            ),
-            MoveTypes::Take,
-            check_for_check_or_mate(&move_string),
-        )),
-        'a' | 'b' | 'c' | 'd' | 'e' | 'f' | 'g' | 'h' => match characters.get(2).unwrap() {
-            'a' | 'b' | 'c' | 'd' | 'e' | 'f' | 'g' | 'h' => Ok(ParsedMove::new(
-                characters.get(0).unwrap().to_string(),
-                (Some(characters.get(1).unwrap().to_string()), None),
-                (
-                    characters.get(2).unwrap().to_string(),
-                    characters.get(3).unwrap().to_string(),
-                ),
-                MoveTypes::Move,
-                check_for_check_or_mate(&move_string),
-            )),
-            '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' => match characters.get(3) {
-                Some('x') => Ok(ParsedMove::new(
-                    characters.get(0).unwrap().to_string(),
-                    (
-                        Some(characters.get(1).unwrap().to_string()),
-                        Some(characters.get(2).unwrap().to_string()),
-                    ),
-                    (
-                        characters.get(4).unwrap().to_string(),
-                        characters.get(5).unwrap().to_string(),
-                    ),
-                    MoveTypes::Take,
-                    check_for_check_or_mate(&move_string),
-                )),
-                Some('a') | Some('b') | Some('c') | Some('d') | Some('e') | Some('f')
-                | Some('g') | Some('h') => Ok(ParsedMove::new(
-                    characters.get(0).unwrap().to_string(),
-                    (
-                        Some(characters.get(1).unwrap().to_string()),
-                        Some(characters.get(2).unwrap().to_string()),
-                    ),
-                    (
-                        characters.get(3).unwrap().to_string(),
-                        characters.get(4).unwrap().to_string(),
-                    ),
-                    MoveTypes::Move,
-                    check_for_check_or_mate(&move_string),
-                )),
-                Some('#') | Some('+') | None => Ok(ParsedMove::new(
-                    characters.get(0).unwrap().to_string(),
-                    (None, None),
-                    (
-                        characters.get(1).unwrap().to_string(),
-                        characters.get(2).unwrap().to_string(),
-                    ),
-                    MoveTypes::Move,
-                    check_for_check_or_mate(&move_string),
-                )),
-                _ => Ok(ParsedMove::new(
-                    characters.get(0).unwrap().to_string(),
-                    (None, None),
-                    (
-                        characters.get(1).unwrap().to_string(),
-                        characters.get(2).unwrap().to_string(),
-                    ),
-                    MoveTypes::Move,
-                    check_for_check_or_mate(&move_string),
-                )),
-            },
-            'x' => Ok(ParsedMove::new(
-                characters.get(0).unwrap().to_string(),
-                (Some(characters.get(1).unwrap().to_string()), None),
-                (
-                    characters.get(3).unwrap().to_string(),
-                    characters.get(4).unwrap().to_string(),
-                ),
-                MoveTypes::Take,
-                check_for_check_or_mate(&move_string),
-            )),
-            _ => Err(ParseError::new("no")),
+/// Turns a file letter or rank number into its index, a is 0 and so is 1
+pub fn parse_coordinate(coordinate: &str) -> usize {
+    match coordinate.as_bytes() {
+        [file] if file.is_ascii_lowercase() => (file - b'a') as usize,
+        _ => match coordinate.parse::<usize>() {
+            Ok(rank) if rank > 0 => rank - 1,
+            _ => panic!("Could not parse coordinate {}", coordinate),
         },
-        _ => Err(ParseError::new("oh no")),
     }
 }
 
-pub fn parse_coordinate(coordinate: &str) -> usize {
-    match coordinate {
-        "a" => 0,
-        "b" => 1,
-        "c" => 2,
-        "d" => 3,
-        "e" => 4,
-        "f" => 5,
-        "g" => 6,
-        "h" => 7,
-        "1" => 0,
-        "2" => 1,
-        "3" => 2,
-        "4" => 3,
-        "5" => 4,
-        "6" => 5,
-        "7" => 6,
-        "8" => 7,
-        _ => panic!("Could not parse coordinate {}", coordinate),
+/// Reads a square like e4 or b10.  None when it isn't one
+pub fn parse_square(square: &str) -> Option<(usize, usize)> {
+    match split_move(square).as_slice() {
+        [file, rank] if is_file(file) && is_rank(rank) => {
+            Some((parse_coordinate(file), parse_coordinate(rank)))
+        }
+        _ => None,
     }
 }
 
+pub fn file_from_coordinate(x_coord: usize) -> String {
+    ((b'a' + x_coord as u8) as char).to_string()
+}
+
+pub fn rank_from_coordinate(y_coord: usize) -> String {
+    (y_coord + 1).to_string()
+}
+
+/// The name of the square, e4 for (4, 3)
+pub fn square_name(position: (usize, usize)) -> String {
+    format!(
+        "{}{}",
+        file_from_coordinate(position.0),
+        rank_from_coordinate(position.1)
+    )
+}
+
 fn parse_pawn_move(move_string: String) -> Result<ParsedMove, ParseError> {
-    let characters: Vec<char> = move_string.chars().collect();
-    match characters.get(1).unwrap() {
-        '2' | '3' | '4' | '5' | '6' | '7' => Ok(ParsedMove::new(
-            String::from("P"),
-            (Some(characters.get(0).unwrap().to_string()), None),
-            (
-                characters.get(0).unwrap().to_string(),
-                characters.get(1).unwrap().to_string(),
-            ),
-            MoveTypes::Move,
-            check_for_check_or_mate(&move_string),
+    let tokens = split_move(move_string.trim_end_matches(['+', '#']));
+    let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    let (start_file, end_coords, move_type, rest) = match tokens.as_slice() {
+        [file, "x", end_file, end_rank, rest @ ..] => {
+            (file, (end_file, end_rank), MoveTypes::Take, rest)
+        }
+        [file, rank, rest @ ..] => (file, (file, rank), MoveTypes::Move, rest),
+        _ => return Err(ParseError::new("This is not okay")),
+    };
+    // A pawn reaching the last rank with no piece is left to the board, which will ask for one
+    // or refuse the move
+    let move_type = match rest {
+        [] => move_type,
+        ["=", promotion_piece @ ..] => MoveTypes::Promote(parse_promotion_piece(promotion_piece)?),
+        _ => return Err(ParseError::new("This is not okay")),
+    };
+    Ok(ParsedMove::new(
+        String::from("P"),
+        (Some(start_file.to_string()), None),
+        (end_coords.0.to_string(), end_coords.1.to_string()),
+        move_type,
+        check_for_check_or_mate(&move_string),
+    ))
+}
+// Capital letters other than P and the O of castling.  Fairy pieces can use any of them
+fn is_piece_letter(character: char) -> bool {
+    character.is_ascii_uppercase() && !matches!(character, 'O' | 'P')
+}
+
+fn is_piece_token(token: &str) -> bool {
+    let mut characters = token.chars();
+    matches!((characters.next(), characters.next()), (Some(piece), None) if is_piece_letter(piece))
+}
+
+fn parse_promotion_piece(promotion_piece: &[&str]) -> Result<String, ParseError> {
+    match promotion_piece {
+        // The board turns down a king outside of Antichess and letters it has no fairy piece for
+        [piece] if is_piece_token(piece) => Ok(piece.to_string()),
+        [] => Err(ParseError::new(
+            "The promotion piece is missing after the =",
         )),
-        'x' => match characters.get(4) {
-            Some('=') => Ok(ParsedMove::new(
-                String::from("P"),
-                (Some(characters.get(0).unwrap().to_string()), None),
-                (
-                    characters.get(2).unwrap().to_string(),
-                    characters.get(3).unwrap().to_string(),
-                ),
-                MoveTypes::Promote(characters.get(5).unwrap().to_string()),
-                check_for_check_or_mate(&move_string),
-            )),
-            _ => Ok(ParsedMove::new(
-                String::from("P"),
-                (Some(characters.get(0).unwrap().to_string()), None),
-                (
-                    characters.get(2).unwrap().to_string(),
-                    characters.get(3).unwrap().to_string(),
-                ),
-                MoveTypes::Take,
-                check_for_check_or_mate(&move_string),
-            )),
-        },
-        '1' | '8' => match characters.get(2).unwrap() {
-            'Q' | 'R' | 'B' | 'N' => Ok(ParsedMove::new(
-                String::from("P"),
-                (None, None),
-                (
-                    characters.get(0).unwrap().to_string(),
-                    characters.get(1).unwrap().to_string(),
-                ),
-                MoveTypes::Promote(characters.get(2).unwrap().to_string()),
-                check_for_check_or_mate(&move_string),
-            )),
-            '=' => Ok(ParsedMove::new(
-                String::from("P"),
-                (None, None),
-                (
-                    characters.get(0).unwrap().to_string(),
-                    characters.get(1).unwrap().to_string(),
-                ),
-                MoveTypes::Promote(characters.get(3).unwrap().to_string()),
-                check_for_check_or_mate(&move_string),
-            )),
-            _ => Err(ParseError::new("This is not okay")),
-        },
-        _ => Err(ParseError::new("This is not okay")),
+        _ => Err(ParseError::new(&format!(
+            "A pawn can't promote to {:?}.  It has to be a piece letter like Q, R, B or N",
+            promotion_piece.concat()
+        ))),
     }
 }
+
 fn check_for_check_or_mate(move_string: &str) -> CheckOrCheckMate {
     match String::from(move_string).pop().unwrap() {
         '+' => CheckOrCheckMate::Check,
@@ -365,7 +421,9 @@ fn check_for_check_or_mate(move_string: &str) -> CheckOrCheckMate {
 }
 #[cfg(test)]
 mod tests {
-    use crate::parser::{parse_game_moves, parse_move, CheckOrCheckMate, MoveTypes, ParsedMove};
+    use crate::parser::{
+        parse_game_moves, parse_move, parse_square, CheckOrCheckMate, MoveTypes, ParsedMove,
+    };
 
     // Pawn moves
 
@@ -416,6 +474,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pawn_to_last_rank_without_piece() {
+        let result_move = parse_move("e8").unwrap();
+        assert_eq!(
+            result_move,
+            ParsedMove::new(
+                String::from("P"),
+                (Some("e".to_string()), None),
+                ("e".to_string(), "8".to_string()),
+                MoveTypes::Move,
+                CheckOrCheckMate::Neither
+            ),
+            "The board decides what to do with a promotion that has no piece"
+        );
+    }
+
+    #[test]
+    fn test_invalid_promotion_piece() {
+        // A king is only turned down by the board since Antichess allows it
+        assert!(parse_move("e8=K").is_ok());
+        assert!(parse_move("e8=q").is_err());
+        assert!(parse_move("e8=").is_err());
+        assert!(parse_move("exd1=P").is_err());
+    }
+
+    #[test]
+    fn test_malformed_moves_are_errors() {
+        for move_string in [
+            "Bxprint", "B", "Nx", "e0", "e17", "exd", "O-O-O-O", "1-", "Qh4h",
+        ] {
+            assert!(parse_move(move_string).is_err(), "{}", move_string);
+        }
+    }
+
+    #[test]
+    fn test_big_boards() {
+        assert_eq!(
+            parse_move("Rj10xb10+").unwrap(),
+            ParsedMove::new(
+                String::from("R"),
+                (Some("j".to_string()), Some("10".to_string())),
+                ("b".to_string(), "10".to_string()),
+                MoveTypes::Take,
+                CheckOrCheckMate::Check
+            )
+        );
+        assert_eq!(
+            parse_move("ixj10=Q").unwrap().move_type,
+            MoveTypes::Promote("Q".to_string())
+        );
+        assert_eq!(parse_square("i10"), Some((8, 9)));
+        assert_eq!(parse_square("e01"), None);
+    }
+
+    #[test]
+    fn test_drops() {
+        assert_eq!(
+            parse_move("N@f3+").unwrap(),
+            ParsedMove::new(
+                String::from("N"),
+                (None, None),
+                ("f".to_string(), "3".to_string()),
+                MoveTypes::Drop,
+                CheckOrCheckMate::Check
+            )
+        );
+        assert_eq!(parse_move("@e4").unwrap().piece_char, "P");
+        for move_string in ["K@e4", "N@", "N@q9"] {
+            assert!(parse_move(move_string).is_err(), "{}", move_string);
+        }
+    }
+
     #[test]
     fn test_single_piece_move() {
         let result_move = parse_move("Nd3").unwrap();
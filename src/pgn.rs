@@ -0,0 +1,425 @@
+use crate::board::Board;
+use crate::clock::{Clock, TimeControl};
+use crate::game::{self, ResolvedMove};
+use crate::parser::ParseError;
+use crate::piece_types::PieceColor;
+use crate::san;
+use crate::variant::Variant;
+use std::fs;
+use std::time::Duration;
+
+// The tags every PGN game has, in the order they have to be written
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+// Our own tags for the clock so a timed game can be picked up where it was left
+const CLOCK_TIME_CONTROL_TAG: &str = "ClockTimeControl";
+const WHITE_CLOCK_TAG: &str = "WhiteClock";
+const BLACK_CLOCK_TAG: &str = "BlackClock";
+
+const MAX_LINE_LENGTH: usize = 79;
+
+/// A game read from or about to be written to PGN
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnGame {
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<String>,
+}
+
+impl Default for PgnGame {
+    fn default() -> Self {
+        PgnGame::new()
+    }
+}
+
+impl PgnGame {
+    pub fn new() -> PgnGame {
+        PgnGame {
+            tags: SEVEN_TAG_ROSTER
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            moves: Vec::new(),
+        }
+    }
+
+    /// Builds the game that was played on the board.  The start board is the position the game
+    /// began from, the board has to be one that was played on from it
+    pub fn from_board(
+        start_board: &Board,
+        board: &Board,
+        white_name: &str,
+        black_name: &str,
+        clock: Option<&Clock>,
+    ) -> PgnGame {
+        let mut pgn_game = PgnGame::new();
+        pgn_game.set_tag("White", white_name);
+        pgn_game.set_tag("Black", black_name);
+
+        if start_board.variant != Variant::Standard {
+            pgn_game.set_tag("Variant", start_board.variant.pgn_name());
+        }
+        let start_fen = start_board.to_fen();
+        if start_fen != Board::new_variant(start_board.variant).to_fen() {
+            pgn_game.set_tag("SetUp", "1");
+            pgn_game.set_tag("FEN", &start_fen);
+        }
+
+        // Replay the moves to get the SAN for each one
+        let mut replay_board = start_board.clone();
+        let start_plies = start_board.played_plies().len();
+        for (start, end, promotion_piece) in board.played_plies().into_iter().skip(start_plies) {
+            pgn_game.moves.push(san::move_to_san(
+                &replay_board,
+                start,
+                end,
+                promotion_piece.as_deref(),
+            ));
+            if replay_board
+                .play_move_from_coords(start, end, promotion_piece.as_deref())
+                .is_err()
+            {
+                break;
+            }
+        }
+
+        if let Some(clock) = clock {
+            pgn_game.set_tag(
+                CLOCK_TIME_CONTROL_TAG,
+                &clock.get_time_control().to_string(),
+            );
+            pgn_game.set_tag(
+                WHITE_CLOCK_TAG,
+                &format_clock_tag(clock.remaining(&PieceColor::WHITE)),
+            );
+            pgn_game.set_tag(
+                BLACK_CLOCK_TAG,
+                &format_clock_tag(clock.remaining(&PieceColor::BLACK)),
+            );
+        }
+        pgn_game
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag_name, _)| tag_name == name) {
+            Some((_, tag_value)) => *tag_value = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn result(&self) -> &str {
+        self.tag("Result").unwrap_or("*")
+    }
+
+    /// The position the game started from
+    pub fn start_board(&self) -> Result<Board, ParseError> {
+        let variant = match self.tag("Variant") {
+            Some(variant_name) => Variant::from_string(variant_name)?,
+            None => Variant::Standard,
+        };
+        match (self.tag("SetUp"), self.tag("FEN")) {
+            (Some("0"), _) | (_, None) => Ok(Board::new_variant(variant)),
+            (_, Some(fen)) => {
+                let mut board = Board::from_fen(fen)?;
+                // A King of the Hill FEN looks like any other so the tag has the last word
+                if variant != Variant::Standard {
+                    board.set_variant(variant);
+                }
+                Ok(board)
+            }
+        }
+    }
+
+    /// Plays the moves from the start position.  Returns the final board and the squares of every move
+    pub fn replay(&self) -> Result<(Board, Vec<ResolvedMove>), ParseError> {
+        let mut board = self.start_board()?;
+        let mut resolved_moves = Vec::new();
+        for (index, move_string) in self.moves.iter().enumerate() {
+            let (start, end, promotion_piece) = game::resolve_move(&mut board, move_string)?;
+            board
+                .play_move_from_coords(start, end, promotion_piece.as_deref())
+                .map_err(|error| {
+                    ParseError::new(&format!(
+                        "Move {} {} can't be played: {}",
+                        index / 2 + 1,
+                        move_string,
+                        error
+                    ))
+                })?;
+            resolved_moves.push((start, end, promotion_piece));
+        }
+        Ok((board, resolved_moves))
+    }
+
+    /// Sets the clock back up from the clock tags.  None when the game wasn't timed.  The clock is
+    /// left running for the side to move
+    pub fn restore_clock(&self) -> Result<Option<Clock>, ParseError> {
+        let time_control = match self.tag(CLOCK_TIME_CONTROL_TAG) {
+            Some(time_control) => TimeControl::from_string(time_control)
+                .map_err(|error| ParseError::new(&error.to_string()))?,
+            None => return Ok(None),
+        };
+
+        // Run the moves through the clock so it is in the right stage of the time control
+        let mut clock = Clock::new(time_control);
+        let start_board = self.start_board()?;
+        clock.start(PieceColor::opposite_color(&start_board.last_move_color));
+        for _ in &self.moves {
+            clock.end_turn();
+        }
+        for (color, tag_name) in [
+            (PieceColor::WHITE, WHITE_CLOCK_TAG),
+            (PieceColor::BLACK, BLACK_CLOCK_TAG),
+        ] {
+            if let Some(remaining) = self.tag(tag_name) {
+                clock.set_remaining(&color, parse_clock_tag(remaining)?);
+            }
+        }
+        Ok(Some(clock))
+    }
+
+    /// Writes the game as PGN text
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+        for (name, value) in &self.tags {
+            pgn.push_str(&format!(
+                "[{} \"{}\"]\n",
+                name,
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            ));
+        }
+        pgn.push('\n');
+
+        let first_move_color = match self.start_board() {
+            Ok(start_board) => PieceColor::opposite_color(&start_board.last_move_color),
+            Err(_) => PieceColor::WHITE,
+        };
+        let mut movetext = san::numbered_move_list(&self.moves, first_move_color);
+        if !movetext.is_empty() {
+            movetext.push(' ');
+        }
+        movetext.push_str(self.result());
+
+        // Keep the lines short like the export format asks
+        let mut line_length = 0;
+        for token in movetext.split(' ') {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                pgn.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                pgn.push(' ');
+                line_length += 1;
+            }
+            pgn.push_str(token);
+            line_length += token.len();
+        }
+        pgn.push('\n');
+        pgn
+    }
+}
+
+/// Reads the first game of a PGN file
+pub fn load_pgn_file(path: &str) -> Result<PgnGame, ParseError> {
+    let pgn_text = fs::read_to_string(path)
+        .map_err(|error| ParseError::new(&format!("Could not read {:?}: {}", path, error)))?;
+    parse_pgn(&pgn_text)
+}
+
+pub fn save_pgn_file(path: &str, pgn_game: &PgnGame) -> Result<(), ParseError> {
+    fs::write(path, pgn_game.to_pgn())
+        .map_err(|error| ParseError::new(&format!("Could not write {:?}: {}", path, error)))
+}
+
+/// Formats the time left on a clock as H:MM:SS.s
+fn format_clock_tag(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    format!(
+        "{}:{:02}:{:02}.{}",
+        total_seconds / 3600,
+        (total_seconds % 3600) / 60,
+        total_seconds % 60,
+        duration.subsec_millis() / 100
+    )
+}
+
+fn parse_clock_tag(clock_string: &str) -> Result<Duration, ParseError> {
+    let mut seconds = 0.0;
+    for part in clock_string.split(':') {
+        match part.parse::<f64>() {
+            Ok(value) if value >= 0.0 => seconds = seconds * 60.0 + value,
+            _ => {
+                return Err(ParseError::new(&format!(
+                    "Could not read the clock time {:?}",
+                    clock_string
+                )))
+            }
+        }
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn is_result(token: &str) -> bool {
+    matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*")
+}
+
+/// Reads the first game in the PGN text.  Comments, variations and NAGs are skipped
+pub fn parse_pgn(pgn_text: &str) -> Result<PgnGame, ParseError> {
+    let mut pgn_game = PgnGame::new();
+    let mut characters = pgn_text.chars().peekable();
+    let mut token = String::new();
+    let mut tokens = Vec::new();
+    let mut variation_depth = 0;
+
+    while let Some(character) = characters.next() {
+        match character {
+            '{' => {
+                if !characters.any(|character| character == '}') {
+                    return Err(ParseError::new("A comment in the PGN is never closed"));
+                }
+            }
+            ';' => while characters.next_if(|character| *character != '\n').is_some() {},
+            '(' => variation_depth += 1,
+            ')' if variation_depth > 0 => {
+                variation_depth -= 1;
+                token.clear();
+            }
+            _ if variation_depth > 0 => (),
+            '[' if token.is_empty() && tokens.is_empty() => {
+                let mut tag_string = String::new();
+                let mut in_quotes = false;
+                let mut closed = false;
+                while let Some(character) = characters.next() {
+                    match character {
+                        '\\' if in_quotes => {
+                            if let Some(escaped) = characters.next() {
+                                tag_string.push(escaped);
+                            }
+                        }
+                        '"' => in_quotes = !in_quotes,
+                        ']' if !in_quotes => {
+                            closed = true;
+                            break;
+                        }
+                        _ if in_quotes => tag_string.push(character),
+                        _ if tag_string.is_empty() && character.is_whitespace() => (),
+                        _ => tag_string.push(character),
+                    }
+                }
+                let (name, value) = match (closed, tag_string.split_once(' ')) {
+                    (true, Some((name, value))) => (name, value),
+                    _ => {
+                        return Err(ParseError::new(&format!(
+                            "Could not read the PGN tag [{}",
+                            tag_string
+                        )))
+                    }
+                };
+                pgn_game.set_tag(name, value);
+            }
+            _ if character.is_whitespace() => {
+                if !token.is_empty() {
+                    tokens.push(token.clone());
+                    token.clear();
+                }
+            }
+            _ => token.push(character),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    for token in tokens {
+        if is_result(&token) {
+            pgn_game.set_tag("Result", &token);
+            break;
+        }
+        if token.starts_with('$') {
+            continue;
+        }
+        // Move numbers can be stuck to the move like 1.e4
+        let move_string = match token.rsplit_once('.') {
+            Some((_, move_string)) => move_string,
+            None => &token,
+        };
+        if !move_string.is_empty() {
+            pgn_game.moves.push(move_string.to_string());
+        }
+    }
+    Ok(pgn_game)
+}
+
+/// Reads every game in the PGN text.  A game starts at the first tag after the moves of the one
+/// before
+pub fn parse_pgn_games(pgn_text: &str) -> Result<Vec<PgnGame>, ParseError> {
+    let mut game_texts: Vec<String> = Vec::new();
+    let mut in_moves = false;
+    for line in pgn_text.lines() {
+        let is_tag = line.trim_start().starts_with('[');
+        if game_texts.is_empty() || (is_tag && in_moves) {
+            game_texts.push(String::new());
+            in_moves = false;
+        }
+        if !is_tag && !line.trim().is_empty() {
+            in_moves = true;
+        }
+        let game_text = game_texts.last_mut().unwrap();
+        game_text.push_str(line);
+        game_text.push('\n');
+    }
+    game_texts
+        .iter()
+        .filter(|game_text| !game_text.trim().is_empty())
+        .map(|game_text| parse_pgn(game_text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pgn::{format_clock_tag, parse_clock_tag, parse_pgn, parse_pgn_games};
+    use std::time::Duration;
+
+    #[test]
+    fn test_clock_tag_round_trip() {
+        let duration = Duration::from_millis(3_725_400);
+        assert_eq!(format_clock_tag(duration), "1:02:05.4");
+        assert_eq!(parse_clock_tag("1:02:05.4").unwrap(), duration);
+        assert!(parse_clock_tag("soon").is_err());
+    }
+
+    #[test]
+    fn test_skips_comments_variations_and_nags() {
+        let pgn_game = parse_pgn(
+            "[White \"Morphy, Paul\"]\n\n1.e4 {best by test} e5 (1... c5 2. Nf3) 2. Nf3 $1 ; the rest\nNc6 1-0",
+        )
+        .unwrap();
+        assert_eq!(pgn_game.tag("White"), Some("Morphy, Paul"));
+        assert_eq!(pgn_game.moves, vec!["e4", "e5", "Nf3", "Nc6"]);
+        assert_eq!(pgn_game.result(), "1-0");
+    }
+
+    #[test]
+    fn test_several_games() {
+        let pgn_text = "[White \"A\"]\n\n1. e4 e5 1-0\n\n[White \"B\"]\n[Black \"C\"]\n\n1. d4 *\n";
+        let games = parse_pgn_games(pgn_text).unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].moves, ["e4", "e5"]);
+        assert_eq!(games[1].tag("White"), Some("B"));
+        assert_eq!(games[1].moves, ["d4"]);
+        assert!(parse_pgn_games("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::parser::ParseError;
+use crate::piece_types::{PieceColor, QuickPiece};
+use crate::pieces::{coord_on_board, PieceMove};
+use std::sync::Arc;
+
+// A ride with no limit goes until it leaves the board
+const MAX_RANGE: usize = usize::MAX;
+
+/// Whether a move can go to an empty square, take, or both
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MoveKind {
+    Both,
+    MoveOnly,
+    CaptureOnly,
+}
+
+/// One kind of step a Betza piece can take, in all eight directions
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BetzaMove {
+    step: (isize, isize),
+    range: usize,
+    kind: MoveKind,
+    hops: bool,
+}
+
+/// A piece that moves the way a Betza description says.  Only the plain atoms and the m, c and g
+/// modifiers are understood so every move goes the same way in every direction:
+///
+/// * W F D N A H C Z G are leapers, W is one square straight and N is the knight
+/// * K is W and F, R rides W, B rides F and Q rides both
+/// * A doubled atom rides, NN is the nightrider, and a number after an atom limits the ride, W2
+/// * m only moves, c only takes, and g hops the first piece in the way landing just behind it
+#[derive(Clone, Debug)]
+pub struct BetzaPiece {
+    pos_x: usize,
+    pos_y: usize,
+    piece_color: PieceColor,
+    moves: Arc<Vec<BetzaMove>>,
+}
+
+impl BetzaPiece {
+    pub fn new(
+        pos_x: usize,
+        pos_y: usize,
+        piece_color: PieceColor,
+        moves: Arc<Vec<BetzaMove>>,
+    ) -> BetzaPiece {
+        BetzaPiece {
+            pos_x,
+            pos_y,
+            piece_color,
+            moves,
+        }
+    }
+
+    /// Reads a Betza description like BN or gQ into the moves it allows
+    pub fn parse_moves(betza: &str) -> Result<Vec<BetzaMove>, ParseError> {
+        let characters: Vec<char> = betza.chars().collect();
+        let mut moves = Vec::new();
+        let mut index = 0;
+        while index < characters.len() {
+            let mut kind = MoveKind::Both;
+            let mut hops = false;
+            while let Some(modifier) = characters.get(index) {
+                match modifier {
+                    'm' => kind = MoveKind::MoveOnly,
+                    'c' => kind = MoveKind::CaptureOnly,
+                    'g' => hops = true,
+                    _ => break,
+                }
+                index += 1;
+            }
+            let atom = *characters.get(index).ok_or_else(|| {
+                ParseError::new(&format!("{:?} ends without a piece letter", betza))
+            })?;
+            index += 1;
+
+            // Riders written as one letter
+            let (atom_steps, mut range): (&[(isize, isize)], usize) = match atom {
+                'K' => (&[(1, 0), (1, 1)], 1),
+                'R' => (&[(1, 0)], MAX_RANGE),
+                'B' => (&[(1, 1)], MAX_RANGE),
+                'Q' => (&[(1, 0), (1, 1)], MAX_RANGE),
+                _ => (std::slice::from_ref(BetzaPiece::leap(atom, betza)?), 1),
+            };
+            if characters.get(index) == Some(&atom) {
+                range = MAX_RANGE;
+                index += 1;
+            } else {
+                let digits: String = characters[index..]
+                    .iter()
+                    .take_while(|character| character.is_ascii_digit())
+                    .collect();
+                if !digits.is_empty() {
+                    index += digits.len();
+                    range = match digits.parse::<usize>() {
+                        Ok(0) => MAX_RANGE,
+                        Ok(range) => range,
+                        Err(_) => {
+                            return Err(ParseError::new(&format!(
+                                "{:?} is too far for a ride",
+                                digits
+                            )))
+                        }
+                    };
+                }
+            }
+            for step in atom_steps {
+                moves.push(BetzaMove {
+                    step: *step,
+                    range,
+                    kind,
+                    hops,
+                });
+            }
+        }
+        if moves.is_empty() {
+            return Err(ParseError::new(
+                "A Betza description needs at least one move",
+            ));
+        }
+        Ok(moves)
+    }
+
+    fn leap(atom: char, betza: &str) -> Result<&'static (isize, isize), ParseError> {
+        match atom {
+            'W' => Ok(&(1, 0)),
+            'F' => Ok(&(1, 1)),
+            'D' => Ok(&(2, 0)),
+            'N' => Ok(&(2, 1)),
+            'A' => Ok(&(2, 2)),
+            'H' => Ok(&(3, 0)),
+            'C' => Ok(&(3, 1)),
+            'Z' => Ok(&(3, 2)),
+            'G' => Ok(&(3, 3)),
+            _ => Err(ParseError::new(&format!(
+                "{:?} in {:?} isn't a Betza atom",
+                atom, betza
+            ))),
+        }
+    }
+
+    // The eight ways a step can be turned and flipped, without repeats
+    fn directions(step: (isize, isize)) -> Vec<(isize, isize)> {
+        let (a, b) = step;
+        let mut directions = Vec::with_capacity(8);
+        for direction in [
+            (a, b),
+            (-a, b),
+            (a, -b),
+            (-a, -b),
+            (b, a),
+            (-b, a),
+            (b, -a),
+            (-b, -a),
+        ] {
+            if !directions.contains(&direction) {
+                directions.push(direction);
+            }
+        }
+        directions
+    }
+
+    fn square_after(&self, direction: (isize, isize), steps: usize) -> Option<(usize, usize)> {
+        let x_coord = self.pos_x as isize + direction.0 * steps as isize;
+        let y_coord = self.pos_y as isize + direction.1 * steps as isize;
+        if x_coord < 0 || y_coord < 0 {
+            return None;
+        }
+        Some((x_coord as usize, y_coord as usize))
+    }
+
+    // Whether the piece can end on the square given what is on it
+    fn can_land(&self, kind: MoveKind, square: &QuickPiece) -> bool {
+        match square {
+            QuickPiece::EMPTY => kind != MoveKind::CaptureOnly,
+            QuickPiece::PIECE(color) | QuickPiece::KING(color) => {
+                kind != MoveKind::MoveOnly && *color != self.piece_color
+            }
+        }
+    }
+}
+
+impl PieceMove for BetzaPiece {
+    fn get_pos(&self) -> (usize, usize) {
+        (self.pos_x, self.pos_y)
+    }
+    fn set_pos(&mut self, x_coord: usize, y_coord: usize) {
+        self.pos_x = x_coord;
+        self.pos_y = y_coord;
+    }
+    fn can_move(&self, x_coord: usize, y_coord: usize, quick_board: &[Vec<QuickPiece>]) -> bool {
+        if !coord_on_board(x_coord, y_coord, quick_board) {
+            return false;
+        }
+        for betza_move in self.moves.iter() {
+            for direction in BetzaPiece::directions(betza_move.step) {
+                let mut hopped = false;
+                for steps in 1..=betza_move.range {
+                    let (square_x, square_y) = match self.square_after(direction, steps) {
+                        Some(square) if coord_on_board(square.0, square.1, quick_board) => square,
+                        _ => break,
+                    };
+                    let square = &quick_board[square_x][square_y];
+                    let occupied = *square != QuickPiece::EMPTY;
+                    // A hopper can only land right after the piece it jumps
+                    if betza_move.hops && !hopped {
+                        hopped = occupied;
+                        continue;
+                    }
+                    // Another move can still land here when this one can't
+                    if (square_x, square_y) == (x_coord, y_coord) {
+                        if self.can_land(betza_move.kind, square) {
+                            return true;
+                        }
+                        break;
+                    }
+                    if occupied || betza_move.hops {
+                        break;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let mut possible_moves = Vec::new();
+        for betza_move in self.moves.iter() {
+            for direction in BetzaPiece::directions(betza_move.step) {
+                for steps in 1..=betza_move.range {
+                    match self.square_after(direction, steps) {
+                        Some((x_coord, y_coord)) if x_coord < width && y_coord < height => {
+                            if !possible_moves.contains(&(x_coord, y_coord)) {
+                                possible_moves.push((x_coord, y_coord));
+                            }
+                        }
+                        _ => break,
+                    }
+                }
+            }
+        }
+        possible_moves
+    }
+}
@@ -59,11 +59,11 @@ impl PieceMove for Bishop {
     }
 
     // I'm sure there's a much better way to create this funciton
-    fn moves_on_board(&self) -> Vec<(usize, usize)> {
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
         let mut moves_vector: Vec<(usize, usize)> = Vec::new();
-        let upper_right = ((self.pos_x + 1)..8).zip((self.pos_y + 1)..8);
-        let lower_right = ((self.pos_x + 1)..8).zip((0..self.pos_y).rev());
-        let upper_left = (0..self.pos_x).rev().zip(self.pos_y + 1..8);
+        let upper_right = ((self.pos_x + 1)..width).zip((self.pos_y + 1)..height);
+        let lower_right = ((self.pos_x + 1)..width).zip((0..self.pos_y).rev());
+        let upper_left = (0..self.pos_x).rev().zip(self.pos_y + 1..height);
         let lower_left = (0..self.pos_x).rev().zip((0..self.pos_y).rev());
 
         for (left, right) in upper_right {
@@ -0,0 +1,197 @@
+use crate::parser::ParseError;
+use crate::piece_types::{PieceColor, QuickPiece};
+use crate::pieces::betza::BetzaPiece;
+use crate::pieces::PieceMove;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+// Fairy letters are stored as &'static str like the standard ones so notation code can't tell
+// them apart
+const LETTERS: [&str; 26] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+    "T", "U", "V", "W", "X", "Y", "Z",
+];
+
+// Letters the standard pieces and castling already use
+const TAKEN_LETTERS: [char; 7] = ['K', 'Q', 'R', 'B', 'N', 'P', 'O'];
+
+/// What a fairy piece needs on top of PieceMove so the board can copy it and print it
+pub trait CustomPiece: PieceMove + fmt::Debug + Send + Sync {
+    fn clone_box(&self) -> Box<dyn CustomPiece>;
+}
+
+impl<T> CustomPiece for T
+where
+    T: PieceMove + Clone + fmt::Debug + Send + Sync + 'static,
+{
+    fn clone_box(&self) -> Box<dyn CustomPiece> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn CustomPiece> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+type MakePiece = dyn Fn(usize, usize, PieceColor) -> Box<dyn CustomPiece> + Send + Sync;
+
+/// A kind of piece the crate doesn't know about, registered with a board under its own letter.
+/// The pieces themselves are made by a function so any type that implements PieceMove will do
+#[derive(Clone)]
+pub struct FairyPieceType {
+    letter: &'static str,
+    name: String,
+    images: [String; 2],
+    make_piece: Arc<MakePiece>,
+}
+
+impl FairyPieceType {
+    /// A piece type that moves the way the pieces `make_piece` returns do.  The GUI looks for
+    /// Green<name>.png and Black<name>.png unless other images are given
+    pub fn new<T, F>(letter: char, name: &str, make_piece: F) -> Result<FairyPieceType, ParseError>
+    where
+        T: PieceMove + Clone + fmt::Debug + Send + Sync + 'static,
+        F: Fn(usize, usize, PieceColor) -> T + Send + Sync + 'static,
+    {
+        if !letter.is_ascii_uppercase() || TAKEN_LETTERS.contains(&letter) {
+            return Err(ParseError::new(&format!(
+                "{:?} can't be used for a fairy piece.  It has to be a capital letter other than K, Q, R, B, N, P or O",
+                letter
+            )));
+        }
+        Ok(FairyPieceType {
+            letter: LETTERS[(letter as u8 - b'A') as usize],
+            name: String::from(name),
+            images: [format!("Green{}.png", name), format!("Black{}.png", name)],
+            make_piece: Arc::new(move |x_coord, y_coord, piece_color| {
+                Box::new(make_piece(x_coord, y_coord, piece_color))
+            }),
+        })
+    }
+
+    /// A piece type that moves the way a Betza description says, like BN for the Archbishop
+    pub fn from_betza(letter: char, name: &str, betza: &str) -> Result<FairyPieceType, ParseError> {
+        let moves = Arc::new(BetzaPiece::parse_moves(betza)?);
+        FairyPieceType::new(letter, name, move |x_coord, y_coord, piece_color| {
+            BetzaPiece::new(x_coord, y_coord, piece_color, moves.clone())
+        })
+    }
+
+    /// Bishop and knight
+    pub fn archbishop() -> FairyPieceType {
+        FairyPieceType::from_betza('A', "Archbishop", "BN").unwrap()
+    }
+
+    /// Rook and knight
+    pub fn chancellor() -> FairyPieceType {
+        FairyPieceType::from_betza('C', "Chancellor", "RN").unwrap()
+    }
+
+    /// Queen and knight
+    pub fn amazon() -> FairyPieceType {
+        FairyPieceType::from_betza('M', "Amazon", "QN").unwrap()
+    }
+
+    /// Leaps three squares one way and one the other
+    pub fn camel() -> FairyPieceType {
+        FairyPieceType::from_betza('L', "Camel", "C").unwrap()
+    }
+
+    /// Moves along queen lines but has to hop the first piece it meets, landing just behind it
+    pub fn grasshopper() -> FairyPieceType {
+        FairyPieceType::from_betza('G', "Grasshopper", "gQ").unwrap()
+    }
+
+    /// Uses other image files for the GUI.  Relative paths are looked for in the resources folder
+    pub fn with_images(mut self, white_image: &str, black_image: &str) -> FairyPieceType {
+        self.images = [String::from(white_image), String::from(black_image)];
+        self
+    }
+
+    pub fn letter(&self) -> &'static str {
+        self.letter
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn image(&self, piece_color: &PieceColor) -> &str {
+        match piece_color {
+            PieceColor::WHITE => &self.images[0],
+            PieceColor::BLACK => &self.images[1],
+        }
+    }
+
+    pub fn make_piece(
+        piece_type: &Arc<FairyPieceType>,
+        x_coord: usize,
+        y_coord: usize,
+        piece_color: PieceColor,
+    ) -> FairyPiece {
+        FairyPiece {
+            piece_type: piece_type.clone(),
+            piece: (piece_type.make_piece)(x_coord, y_coord, piece_color),
+        }
+    }
+}
+
+impl fmt::Debug for FairyPieceType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.letter)
+    }
+}
+
+// Two piece types are the same when they have the same letter and name, the way pieces are
+impl PartialEq for FairyPieceType {
+    fn eq(&self, other: &Self) -> bool {
+        self.letter == other.letter && self.name == other.name
+    }
+}
+
+/// A fairy piece on the board
+#[derive(Clone, Debug)]
+pub struct FairyPiece {
+    piece_type: Arc<FairyPieceType>,
+    piece: Box<dyn CustomPiece>,
+}
+
+impl FairyPiece {
+    pub fn piece_type(&self) -> &FairyPieceType {
+        &self.piece_type
+    }
+}
+
+impl PieceMove for FairyPiece {
+    fn can_move(&self, x_coord: usize, y_coord: usize, quick_board: &[Vec<QuickPiece>]) -> bool {
+        self.piece.can_move(x_coord, y_coord, quick_board)
+    }
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
+        self.piece.moves_on_board(width, height)
+    }
+    fn get_pos(&self) -> (usize, usize) {
+        self.piece.get_pos()
+    }
+    fn set_pos(&mut self, x_coord: usize, y_coord: usize) {
+        self.piece.set_pos(x_coord, y_coord)
+    }
+}
+
+// Two fairy pieces are the same when they are the same kind on the same square
+impl Hash for FairyPiece {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.piece_type.letter.hash(state);
+        self.get_pos().hash(state);
+    }
+}
+
+impl PartialEq for FairyPiece {
+    fn eq(&self, other: &Self) -> bool {
+        self.piece_type.letter == other.piece_type.letter && self.get_pos() == other.get_pos()
+    }
+}
+
+impl Eq for FairyPiece {}
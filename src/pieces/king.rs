@@ -64,10 +64,10 @@ impl PieceMove for King {
         piece_on_location_result
     }
 
-    fn moves_on_board(&self) -> Vec<(usize, usize)> {
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
         let mut possible_moves = Vec::new();
-        if self.pos_y < 7 {
-            if self.pos_x < 7 {
+        if self.pos_y + 1 < height {
+            if self.pos_x + 1 < width {
                 (possible_moves.push((self.pos_x + 1, self.pos_y + 1)));
             }
             possible_moves.push((self.pos_x, self.pos_y + 1));
@@ -76,7 +76,7 @@ impl PieceMove for King {
             }
         }
 
-        if self.pos_x < 7 {
+        if self.pos_x + 1 < width {
             possible_moves.push((self.pos_x + 1, self.pos_y));
         }
 
@@ -85,7 +85,7 @@ impl PieceMove for King {
         }
 
         if self.pos_y > 0 {
-            if self.pos_x < 7 {
+            if self.pos_x + 1 < width {
                 (possible_moves.push((self.pos_x + 1, self.pos_y - 1)));
             }
             possible_moves.push((self.pos_x, self.pos_y - 1));
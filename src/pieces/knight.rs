@@ -52,40 +52,40 @@ impl PieceMove for Knight {
         piece_on_location_result
     }
 
-    fn moves_on_board(&self) -> Vec<(usize, usize)> {
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
         let mut possible_moves = Vec::new();
 
-        if let 2 | 3 | 4 | 5 | 6 | 7 = self.pos_x + 2 {
-            if let 2 | 3 | 4 | 5 | 6 | 7 = self.pos_y + 1 {
+        if self.pos_x + 2 < width {
+            if self.pos_y + 1 < height {
                 possible_moves.push((self.pos_x + 2, self.pos_y + 1));
             }
-            if let 1 | 2 | 3 | 4 | 5 | 6 | 7 = self.pos_y {
+            if self.pos_y >= 1 {
                 possible_moves.push((self.pos_x + 2, self.pos_y - 1));
             }
         }
 
-        if let 2 | 3 | 4 | 5 | 6 | 7 = self.pos_x {
-            if let 2 | 3 | 4 | 5 | 6 | 7 = self.pos_y + 1 {
+        if self.pos_x >= 2 {
+            if self.pos_y + 1 < height {
                 possible_moves.push((self.pos_x - 2, self.pos_y + 1));
             }
-            if let 1 | 2 | 3 | 4 | 5 | 6 | 7 = self.pos_y {
+            if self.pos_y >= 1 {
                 possible_moves.push((self.pos_x - 2, self.pos_y - 1));
             }
         }
 
-        if let 2 | 3 | 4 | 5 | 6 | 7 = self.pos_y {
-            if let 2 | 3 | 4 | 5 | 6 | 7 = self.pos_x + 1 {
+        if self.pos_y >= 2 {
+            if self.pos_x + 1 < width {
                 possible_moves.push((self.pos_x + 1, self.pos_y - 2));
             }
-            if let 1 | 2 | 3 | 4 | 5 | 6 | 7 = self.pos_x {
+            if self.pos_x >= 1 {
                 possible_moves.push((self.pos_x - 1, self.pos_y - 2));
             }
         }
-        if let 0 | 1 | 2 | 3 | 4 | 5 = self.pos_y + 2 {
-            if let 2 | 3 | 4 | 5 | 6 | 7 = self.pos_x + 1 {
+        if self.pos_y + 2 < height {
+            if self.pos_x + 1 < width {
                 possible_moves.push((self.pos_x + 1, self.pos_y + 2));
             }
-            if let 1 | 2 | 3 | 4 | 5 | 6 | 7 = self.pos_x {
+            if self.pos_x >= 1 {
                 possible_moves.push((self.pos_x - 1, self.pos_y + 2));
             }
         }
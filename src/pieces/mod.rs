@@ -1,4 +1,6 @@
+pub mod betza;
 pub mod bishop;
+pub mod fairy;
 pub mod king;
 pub mod knight;
 mod movement;
@@ -9,6 +11,7 @@ pub mod rook;
 use super::piece_types;
 use crate::piece_types::{PieceColor, QuickPiece};
 use crate::pieces::bishop::Bishop;
+use crate::pieces::fairy::FairyPiece;
 use crate::pieces::king::King;
 use crate::pieces::knight::Knight;
 use crate::pieces::pawn::Pawn;
@@ -25,12 +28,13 @@ pub trait PieceMove {
         y_coord: usize,
         quick_board: &[Vec<piece_types::QuickPiece>],
     ) -> bool;
-    fn moves_on_board(&self) -> Vec<(usize, usize)>;
+    /// Every square the piece could reach on an empty board of the given size
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)>;
     fn get_pos(&self) -> (usize, usize);
     fn set_pos(&mut self, x_coord: usize, y_coord: usize);
 }
 
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub enum AnyPiece {
     King(King),
     Queen(Queen),
@@ -38,6 +42,8 @@ pub enum AnyPiece {
     Knight(Knight),
     Rook(Rook),
     Pawn(Pawn),
+    /// A piece registered with the board, see FairyPieceType
+    Fairy(FairyPiece),
 }
 
 impl Ord for AnyPiece {
@@ -57,11 +63,40 @@ impl AnyPiece {
         x_coord: usize,
         y_coord: usize,
         new_piece_color: PieceColor,
-    ) -> AnyPiece {
-        let piece_symbol_char = piece_symbol_string.chars().next().unwrap();
-        match piece_symbol_char {
-            'Q' => AnyPiece::Queen(Queen::new(x_coord, y_coord, new_piece_color)),
-            _ => panic!("AAAAAAAAAAAAAA"),
+    ) -> Option<AnyPiece> {
+        match piece_symbol_string {
+            "K" => Some(AnyPiece::King(King::new(x_coord, y_coord, new_piece_color))),
+            "Q" => Some(AnyPiece::Queen(Queen::new(
+                x_coord,
+                y_coord,
+                new_piece_color,
+            ))),
+            "B" => Some(AnyPiece::Bishop(Bishop::new(
+                x_coord,
+                y_coord,
+                new_piece_color,
+            ))),
+            "N" => Some(AnyPiece::Knight(Knight::new(
+                x_coord,
+                y_coord,
+                new_piece_color,
+            ))),
+            "R" => Some(AnyPiece::Rook(Rook::new(x_coord, y_coord, new_piece_color))),
+            "P" => Some(AnyPiece::Pawn(Pawn::new(x_coord, y_coord, new_piece_color))),
+            _ => None,
+        }
+    }
+
+    /// The letter used for the piece in notation.  Pawns use P even though SAN leaves it out
+    pub fn get_piece_character(&self) -> &'static str {
+        match self {
+            AnyPiece::Fairy(fairy) => fairy.piece_type().letter(),
+            AnyPiece::King(_) => "K",
+            AnyPiece::Queen(_) => "Q",
+            AnyPiece::Bishop(_) => "B",
+            AnyPiece::Knight(_) => "N",
+            AnyPiece::Rook(_) => "R",
+            AnyPiece::Pawn(_) => "P",
         }
     }
 }
@@ -75,6 +110,7 @@ impl PieceMove for AnyPiece {
             AnyPiece::Knight(ref knight) => knight.can_move(x_coord, y_coord, quick_board),
             AnyPiece::Rook(ref rook) => rook.can_move(x_coord, y_coord, quick_board),
             AnyPiece::Pawn(ref pawn) => pawn.can_move(x_coord, y_coord, quick_board),
+            AnyPiece::Fairy(ref fairy) => fairy.can_move(x_coord, y_coord, quick_board),
         }
     }
 
@@ -86,6 +122,7 @@ impl PieceMove for AnyPiece {
             AnyPiece::Knight(ref mut knight) => knight.set_pos(x_coord, y_coord),
             AnyPiece::Rook(ref mut rook) => rook.set_pos(x_coord, y_coord),
             AnyPiece::Pawn(ref mut pawn) => pawn.set_pos(x_coord, y_coord),
+            AnyPiece::Fairy(ref mut fairy) => fairy.set_pos(x_coord, y_coord),
         }
     }
 
@@ -97,17 +134,19 @@ impl PieceMove for AnyPiece {
             AnyPiece::Knight(ref knight) => knight.get_pos(),
             AnyPiece::Rook(ref rook) => rook.get_pos(),
             AnyPiece::Pawn(ref pawn) => pawn.get_pos(),
+            AnyPiece::Fairy(ref fairy) => fairy.get_pos(),
         }
     }
 
-    fn moves_on_board(&self) -> Vec<(usize, usize)> {
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
         match self {
-            AnyPiece::King(ref king) => king.moves_on_board(),
-            AnyPiece::Queen(ref queen) => queen.moves_on_board(),
-            AnyPiece::Bishop(ref bishop) => bishop.moves_on_board(),
-            AnyPiece::Knight(ref knight) => knight.moves_on_board(),
-            AnyPiece::Rook(ref rook) => rook.moves_on_board(),
-            AnyPiece::Pawn(ref pawn) => pawn.moves_on_board(),
+            AnyPiece::King(ref king) => king.moves_on_board(width, height),
+            AnyPiece::Queen(ref queen) => queen.moves_on_board(width, height),
+            AnyPiece::Bishop(ref bishop) => bishop.moves_on_board(width, height),
+            AnyPiece::Knight(ref knight) => knight.moves_on_board(width, height),
+            AnyPiece::Rook(ref rook) => rook.moves_on_board(width, height),
+            AnyPiece::Pawn(ref pawn) => pawn.moves_on_board(width, height),
+            AnyPiece::Fairy(ref fairy) => fairy.moves_on_board(width, height),
         }
     }
 }
@@ -117,7 +156,7 @@ pub fn coord_on_board(
     y_coord: usize,
     quick_board: &[Vec<piece_types::QuickPiece>],
 ) -> bool {
-    let y_length = match quick_board.get(0) {
+    let y_length = match quick_board.first() {
         Some(row) => row.len(),
         None => return false, // @TODO Look into if this is rusty or crusty
     };
@@ -21,13 +21,37 @@ impl Pawn {
             starting_y_position,
         }
     }
-    fn has_moved(&self) -> bool {
-        match self.piece_color {
-            PieceColor::WHITE => self.pos_y != 1,
-            PieceColor::BLACK => self.pos_y != 6,
+
+    /// A pawn placed somewhere other than where it started, such as when a board is set up from a FEN
+    pub fn with_starting_y(
+        pos_x: usize,
+        pos_y: usize,
+        piece_color: PieceColor,
+        starting_y_position: usize,
+    ) -> Pawn {
+        Pawn {
+            pos_x,
+            pos_y,
+            piece_color,
+            starting_y_position,
         }
     }
 
+    fn has_moved(&self) -> bool {
+        self.pos_y != self.starting_y_position
+    }
+
+    // Small boards like Los Alamos and Gardner minichess leave out the first two square step.
+    // White pawns that start on the first rank in Horde go by rank, so they can still move two
+    // from the second rank after a single step
+    fn can_move_two(&self, height: usize) -> bool {
+        let can_move_two = match (self.piece_color, self.starting_y_position) {
+            (PieceColor::WHITE, 0) => self.pos_y <= 1,
+            _ => !self.has_moved(),
+        };
+        can_move_two && height >= 8
+    }
+
     fn is_moving_forward(&self, y_coord: usize) -> bool {
         let y_delta = y_coord as isize - self.pos_y as isize;
         match self.piece_color {
@@ -65,10 +89,7 @@ impl PieceMove for Pawn {
 
         let y_delta = max(self.pos_y, y_coord) - usize::min(self.pos_y, y_coord);
         if y_delta == 2 {
-            if usize::max(self.starting_y_position, y_coord)
-                - usize::min(self.starting_y_position, y_coord)
-                != 2
-            {
+            if !self.can_move_two(quick_board[0].len()) {
                 return false;
             }
             let piece = quick_board
@@ -107,9 +128,12 @@ impl PieceMove for Pawn {
         piece_on_location_result
     }
 
-    fn moves_on_board(&self) -> Vec<(usize, usize)> {
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
         let mut possible_moves = Vec::new();
         match self.piece_color {
+            // A pawn on its last rank has nowhere to go.  Boards set up by hand can have one
+            PieceColor::WHITE if self.pos_y + 1 >= height => (),
+            PieceColor::BLACK if self.pos_y == 0 => (),
             PieceColor::WHITE => {
                 possible_moves.push((self.pos_x, self.pos_y + 1));
 
@@ -117,11 +141,11 @@ impl PieceMove for Pawn {
                     possible_moves.push((self.pos_x - 1, self.pos_y + 1));
                 }
 
-                if self.pos_x < 7 {
+                if self.pos_x + 1 < width {
                     possible_moves.push((self.pos_x + 1, self.pos_y + 1));
                 }
 
-                if !self.has_moved() {
+                if self.can_move_two(height) {
                     possible_moves.push((self.pos_x, self.pos_y + 2));
                 }
             }
@@ -132,11 +156,11 @@ impl PieceMove for Pawn {
                     possible_moves.push((self.pos_x - 1, self.pos_y - 1));
                 }
 
-                if self.pos_x < 7 {
+                if self.pos_x + 1 < width {
                     possible_moves.push((self.pos_x + 1, self.pos_y - 1));
                 }
 
-                if !self.has_moved() {
+                if self.can_move_two(height) {
                     possible_moves.push((self.pos_x, self.pos_y - 2));
                 }
             }
@@ -81,15 +81,15 @@ impl PieceMove for Queen {
         piece_on_location_result
     }
 
-    fn moves_on_board(&self) -> Vec<(usize, usize)> {
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
         let mut moves_vector = Vec::new();
-        let upper_right = ((self.pos_x + 1)..8).zip((self.pos_y + 1)..8);
-        let lower_right = ((self.pos_x + 1)..8).zip((0..self.pos_y).rev());
-        let upper_left = (0..self.pos_x).rev().zip(self.pos_y + 1..8);
+        let upper_right = ((self.pos_x + 1)..width).zip((self.pos_y + 1)..height);
+        let lower_right = ((self.pos_x + 1)..width).zip((0..self.pos_y).rev());
+        let upper_left = (0..self.pos_x).rev().zip(self.pos_y + 1..height);
         let lower_left = (0..self.pos_x).rev().zip((0..self.pos_y).rev());
-        let right = (self.pos_x + 1)..8;
+        let right = (self.pos_x + 1)..width;
         let left = (0..self.pos_x).rev();
-        let up = (self.pos_y + 1)..8;
+        let up = (self.pos_y + 1)..height;
         let down = (0..self.pos_y).rev();
 
         for (left, right) in upper_right {
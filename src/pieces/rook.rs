@@ -79,12 +79,12 @@ impl PieceMove for Rook {
         piece_on_location_result
     }
 
-    fn moves_on_board(&self) -> Vec<(usize, usize)> {
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
         let mut moves_vector: Vec<(usize, usize)> = Vec::new();
 
-        let right = (self.pos_x + 1)..8;
+        let right = (self.pos_x + 1)..width;
         let left = (0..self.pos_x).rev();
-        let up = (self.pos_y + 1)..8;
+        let up = (self.pos_y + 1)..height;
         let down = (0..self.pos_y).rev();
 
         for coord in right {
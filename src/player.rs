@@ -0,0 +1,169 @@
+use crate::clock::Clock;
+use crate::piece_types::PieceColor;
+use crate::uci::{EngineError, SearchLimit};
+use std::time::Duration;
+
+// What an engine gets to think when there is no clock and no limit was given
+const DEFAULT_MOVE_TIME: Duration = Duration::from_secs(1);
+
+/// Who is making the moves for one side of the board
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Player {
+    #[default]
+    Human,
+    /// An external UCI engine.  Without a limit it thinks for a share of its clock
+    Uci {
+        path: String,
+        limit: Option<SearchLimit>,
+    },
+    /// The built-in engine, with the same limits as a UCI one
+    Engine { limit: Option<SearchLimit> },
+}
+
+impl Player {
+    /// Reads a player from the command line.  Either human, engine or uci:<path to engine>.  Either
+    /// engine can have :depth=N or :movetime=<milliseconds> on the end
+    pub fn from_string(player_string: &str) -> Result<Player, EngineError> {
+        let player_string = player_string.trim();
+        if player_string.eq_ignore_ascii_case("human") {
+            return Ok(Player::Human);
+        }
+        if let Some(options) = player_string.strip_prefix("engine") {
+            let limit = match options.strip_prefix(':') {
+                Some(option) => Some(read_search_limit(option)?),
+                None if options.is_empty() => None,
+                None => return Err(not_a_player(player_string)),
+            };
+            return Ok(Player::Engine { limit });
+        }
+        let engine_string = match player_string.strip_prefix("uci:") {
+            Some(engine_string) => engine_string,
+            None => return Err(not_a_player(player_string)),
+        };
+
+        // Options go on the end so a path with a : in it still works
+        let mut parts: Vec<&str> = engine_string.split(':').collect();
+        let mut limit = None;
+        while parts.len() > 1 && parts.last().unwrap().contains('=') {
+            limit = Some(read_search_limit(parts.pop().unwrap())?);
+        }
+        let path = parts.join(":");
+        if path.is_empty() {
+            return Err(EngineError::new("The engine path is missing after uci:"));
+        }
+        Ok(Player::Uci { path, limit })
+    }
+
+    pub fn is_human(&self) -> bool {
+        matches!(self, Player::Human)
+    }
+
+    /// How long the engine should think for its next move
+    pub fn search_limit(&self, clock: Option<&Clock>, color: &PieceColor) -> SearchLimit {
+        match (self, clock) {
+            (
+                Player::Uci {
+                    limit: Some(limit), ..
+                }
+                | Player::Engine { limit: Some(limit) },
+                _,
+            ) => *limit,
+            (_, Some(clock)) => SearchLimit::MoveTime(clock.allocate_think_time(color)),
+            _ => SearchLimit::MoveTime(DEFAULT_MOVE_TIME),
+        }
+    }
+}
+
+fn not_a_player(player_string: &str) -> EngineError {
+    EngineError::new(&format!(
+        "{:?} is not a player.  Use human, engine or uci:<path to engine>",
+        player_string
+    ))
+}
+
+// depth=N or movetime=<milliseconds>
+fn read_search_limit(option: &str) -> Result<SearchLimit, EngineError> {
+    let (name, value) = option
+        .split_once('=')
+        .ok_or_else(|| EngineError::new(&format!("{:?} is not name=value", option)))?;
+    let value: u64 = value
+        .parse()
+        .map_err(|_| EngineError::new(&format!("{:?} is not a number in {:?}", value, option)))?;
+    match name {
+        "depth" => Ok(SearchLimit::Depth(value as u32)),
+        "movetime" => Ok(SearchLimit::MoveTime(Duration::from_millis(value))),
+        _ => Err(EngineError::new(&format!(
+            "Unknown engine option {:?}.  Use depth or movetime",
+            name
+        ))),
+    }
+}
+
+/// The players of both sides
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Players {
+    pub white: Player,
+    pub black: Player,
+}
+
+impl Players {
+    pub fn get(&self, color: &PieceColor) -> &Player {
+        match color {
+            PieceColor::WHITE => &self.white,
+            PieceColor::BLACK => &self.black,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::player::Player;
+    use crate::uci::SearchLimit;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_players() {
+        assert_eq!(Player::from_string("human").unwrap(), Player::Human);
+        assert_eq!(
+            Player::from_string("uci:/usr/bin/stockfish:depth=6").unwrap(),
+            Player::Uci {
+                path: String::from("/usr/bin/stockfish"),
+                limit: Some(SearchLimit::Depth(6))
+            }
+        );
+        assert_eq!(
+            Player::from_string("uci:C:/engines/sf.exe:movetime=500").unwrap(),
+            Player::Uci {
+                path: String::from("C:/engines/sf.exe"),
+                limit: Some(SearchLimit::MoveTime(Duration::from_millis(500)))
+            }
+        );
+        assert_eq!(
+            Player::from_string("uci:stockfish").unwrap(),
+            Player::Uci {
+                path: String::from("stockfish"),
+                limit: None
+            }
+        );
+        assert_eq!(
+            Player::from_string("engine:depth=6").unwrap(),
+            Player::Engine {
+                limit: Some(SearchLimit::Depth(6))
+            }
+        );
+        assert_eq!(
+            Player::from_string("engine").unwrap(),
+            Player::Engine { limit: None }
+        );
+    }
+
+    #[test]
+    fn test_bad_players() {
+        assert!(Player::from_string("engines").is_err());
+        assert!(Player::from_string("engine:nodes=100").is_err());
+        assert!(Player::from_string("robot").is_err());
+        assert!(Player::from_string("uci:stockfish:depth=deep").is_err());
+        assert!(Player::from_string("uci:stockfish:nodes=100").is_err());
+        assert!(Player::from_string("uci:").is_err());
+    }
+}
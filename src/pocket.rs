@@ -0,0 +1,113 @@
+use crate::parser::ParseError;
+use crate::piece_types::PieceColor;
+
+// The order pieces are kept and written in
+const POCKET_ORDER: [&str; 5] = ["Q", "R", "B", "N", "P"];
+
+/// The pieces each side has captured in Crazyhouse and can drop back on the board
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Pockets {
+    white: Vec<String>,
+    black: Vec<String>,
+}
+
+impl Pockets {
+    pub fn new() -> Pockets {
+        Pockets::default()
+    }
+
+    fn pocket(&self, color: &PieceColor) -> &Vec<String> {
+        match color {
+            PieceColor::WHITE => &self.white,
+            PieceColor::BLACK => &self.black,
+        }
+    }
+
+    fn pocket_mut(&mut self, color: &PieceColor) -> &mut Vec<String> {
+        match color {
+            PieceColor::WHITE => &mut self.white,
+            PieceColor::BLACK => &mut self.black,
+        }
+    }
+
+    /// The pieces in the color's pocket, queens first
+    pub fn pieces(&self, color: &PieceColor) -> &[String] {
+        self.pocket(color)
+    }
+
+    pub fn count(&self, color: &PieceColor, piece: &str) -> usize {
+        self.pocket(color)
+            .iter()
+            .filter(|pocket_piece| *pocket_piece == piece)
+            .count()
+    }
+
+    pub fn is_empty(&self, color: &PieceColor) -> bool {
+        self.pocket(color).is_empty()
+    }
+
+    pub fn add(&mut self, color: &PieceColor, piece: &str) {
+        let pocket = self.pocket_mut(color);
+        pocket.push(String::from(piece));
+        pocket.sort_by_key(|pocket_piece| {
+            POCKET_ORDER.iter().position(|order| order == pocket_piece)
+        });
+    }
+
+    /// Takes a piece out of the pocket.  False when there isn't one
+    pub fn take(&mut self, color: &PieceColor, piece: &str) -> bool {
+        let pocket = self.pocket_mut(color);
+        match pocket.iter().position(|pocket_piece| pocket_piece == piece) {
+            Some(index) => {
+                pocket.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reads what is between the brackets of a Crazyhouse FEN, like QNp
+    pub fn from_fen(pocket_string: &str) -> Result<Pockets, ParseError> {
+        let mut pockets = Pockets::new();
+        for piece_char in pocket_string.chars() {
+            let color = match piece_char.is_ascii_uppercase() {
+                true => PieceColor::WHITE,
+                false => PieceColor::BLACK,
+            };
+            let piece = piece_char.to_ascii_uppercase().to_string();
+            if !POCKET_ORDER.contains(&piece.as_str()) {
+                return Err(ParseError::new(&format!(
+                    "{:?} can't be in a pocket",
+                    piece_char
+                )));
+            }
+            pockets.add(&color, &piece);
+        }
+        Ok(pockets)
+    }
+
+    /// The pockets the way a Crazyhouse FEN writes them between the brackets
+    pub fn to_fen(&self) -> String {
+        let white = self.white.concat();
+        let black = self.black.concat().to_ascii_lowercase();
+        white + &black
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::piece_types::PieceColor;
+    use crate::pocket::Pockets;
+
+    #[test]
+    fn test_pockets() {
+        let mut pockets = Pockets::from_fen("NpQ").unwrap();
+        assert_eq!(pockets.to_fen(), "QNp");
+        assert!(pockets.take(&PieceColor::BLACK, "P"));
+        assert!(!pockets.take(&PieceColor::BLACK, "P"));
+        pockets.add(&PieceColor::WHITE, "N");
+        assert_eq!(pockets.count(&PieceColor::WHITE, "N"), 2);
+        assert!(pockets.is_empty(&PieceColor::BLACK));
+        assert!(Pockets::from_fen("K").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::board::Board;
+use crate::game;
+use crate::parser;
+use crate::piece_types::{PieceColor, QuickPiece};
+use crate::pieces::{AnyPiece, PieceMove};
+
+fn piece_at(board: &Board, position: (usize, usize)) -> Option<&AnyPiece> {
+    board
+        .live_white_pieces
+        .iter()
+        .chain(board.live_black_pieces.iter())
+        .find(|piece| piece.get_pos() == position)
+}
+
+/// Writes a move in standard algebraic notation.  The move has to be legal on the board, the board
+/// isn't changed
+pub fn move_to_san(
+    board: &Board,
+    start_position: (usize, usize),
+    end_position: (usize, usize),
+    promotion_piece: Option<&str>,
+) -> String {
+    let moving_color = PieceColor::opposite_color(&board.last_move_color);
+    let mut san = String::new();
+    let moving_piece = match (piece_at(board, start_position), promotion_piece) {
+        // A drop is the square twice with the dropped piece, N@f3
+        (None, Some(drop_piece)) if start_position == end_position => {
+            san.push_str(&format!(
+                "{}@{}",
+                drop_piece,
+                parser::square_name(end_position)
+            ));
+            None
+        }
+        (Some(piece), _) => Some(piece),
+        (None, _) => return String::new(),
+    };
+    let piece_character = moving_piece
+        .map(AnyPiece::get_piece_character)
+        .unwrap_or("");
+    let delta_x =
+        usize::max(start_position.0, end_position.0) - usize::min(start_position.0, end_position.0);
+
+    match moving_piece {
+        None => (),
+        Some(AnyPiece::King(_)) if delta_x >= 2 => {
+            san.push_str(if end_position.0 > start_position.0 {
+                "O-O"
+            } else {
+                "O-O-O"
+            });
+        }
+        Some(moving_piece) => {
+            let is_pawn = matches!(moving_piece, AnyPiece::Pawn(_));
+            // A pawn moving sideways is always a capture, even en passant
+            let is_capture = board.position_board[end_position.0][end_position.1]
+                != QuickPiece::EMPTY
+                || (is_pawn && delta_x == 1);
+
+            if is_pawn {
+                if is_capture {
+                    san.push_str(&parser::file_from_coordinate(start_position.0));
+                }
+            } else {
+                san.push_str(piece_character);
+                // Only say where the piece came from when another one of the same type could go there too
+                let other_starts: Vec<(usize, usize)> =
+                    game::legal_moves(&moving_color, &mut board.clone())
+                        .into_iter()
+                        .filter(|(start, end)| {
+                            *end == end_position
+                                && *start != start_position
+                                && piece_at(board, *start).map(AnyPiece::get_piece_character)
+                                    == Some(piece_character)
+                        })
+                        .map(|(start, _)| start)
+                        .collect();
+                if !other_starts.is_empty() {
+                    let shares_file = other_starts.iter().any(|start| start.0 == start_position.0);
+                    let shares_rank = other_starts.iter().any(|start| start.1 == start_position.1);
+                    if !shares_file {
+                        san.push_str(&parser::file_from_coordinate(start_position.0));
+                    } else if !shares_rank {
+                        san.push_str(&parser::rank_from_coordinate(start_position.1));
+                    } else {
+                        san.push_str(&parser::square_name(start_position));
+                    }
+                }
+            }
+
+            if is_capture {
+                san.push('x');
+            }
+            san.push_str(&parser::square_name(end_position));
+            if let Some(promotion_piece) = promotion_piece {
+                san.push('=');
+                san.push_str(promotion_piece);
+            }
+        }
+    }
+
+    let mut board_after = board.clone();
+    if board_after
+        .play_move_from_coords(start_position, end_position, promotion_piece)
+        .is_ok()
+    {
+        if game::is_board_check_mate(&moving_color, &mut board_after) {
+            san.push('#');
+        } else if game::is_board_in_check(&moving_color, &board_after) {
+            san.push('+');
+        }
+    }
+    san
+}
+
+/// Numbers a list of SAN moves the way they are written in a game, 1. e4 e5 2. Nf3
+pub fn numbered_move_list(san_moves: &[String], first_move_color: PieceColor) -> String {
+    let mut move_list = String::new();
+    let mut move_number = 1;
+    let mut moves = san_moves.iter();
+    if first_move_color == PieceColor::BLACK {
+        if let Some(first_move) = moves.next() {
+            move_list.push_str(&format!("1... {}", first_move));
+            move_number = 2;
+        }
+    }
+    for (index, san_move) in moves.enumerate() {
+        if !move_list.is_empty() {
+            move_list.push(' ');
+        }
+        if index % 2 == 0 {
+            move_list.push_str(&format!("{}. ", move_number));
+            move_number += 1;
+        }
+        move_list.push_str(san_move);
+    }
+    move_list
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::piece_types::PieceColor;
+    use crate::san::{move_to_san, numbered_move_list};
+
+    #[test]
+    fn test_pawn_and_piece_moves() {
+        let board = Board::new();
+        assert_eq!(move_to_san(&board, (4, 1), (4, 3), None), "e4");
+        assert_eq!(move_to_san(&board, (6, 0), (5, 2), None), "Nf3");
+    }
+
+    #[test]
+    fn test_disambiguation() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4K3/R6R w - - 0 1").unwrap();
+        assert_eq!(move_to_san(&board, (0, 0), (3, 0), None), "Rad1");
+        let board = Board::from_fen("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1").unwrap();
+        assert_eq!(move_to_san(&board, (0, 0), (0, 2), None), "R1a3");
+    }
+
+    #[test]
+    fn test_castle_capture_promotion_and_mate() {
+        let board = Board::from_fen("r3k3/1P6/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert_eq!(move_to_san(&board, (4, 0), (6, 0), None), "O-O");
+        assert_eq!(move_to_san(&board, (1, 6), (0, 7), Some("Q")), "bxa8=Q+");
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        assert_eq!(move_to_san(&board, (0, 0), (0, 7), None), "Ra8#");
+    }
+
+    #[test]
+    fn test_numbered_move_list() {
+        let moves = vec![String::from("e4"), String::from("e5"), String::from("Nf3")];
+        assert_eq!(
+            numbered_move_list(&moves, PieceColor::WHITE),
+            "1. e4 e5 2. Nf3"
+        );
+        assert_eq!(
+            numbered_move_list(&moves, PieceColor::BLACK),
+            "1... e4 2. e5 Nf3"
+        );
+    }
+}
@@ -0,0 +1,392 @@
+use crate::board::Board;
+use crate::parser::{self, ParseError};
+use crate::piece_types::PieceColor;
+use crate::pieces::fairy::FairyPieceType;
+use crate::pieces::PieceMove;
+use crate::pocket::Pockets;
+use crate::variant::Variant;
+use std::sync::Arc;
+
+// The castling rights in the order they are written in a FEN
+pub const CASTLING_LETTERS: [char; 4] = ['K', 'Q', 'k', 'q'];
+
+/// A position being put together by hand.  The pieces are kept as their FEN letters, white ones
+/// in capitals, so anything can be put anywhere until it is turned into a board to play.  The
+/// variant, the Crazyhouse pockets and the fairy pieces the letters can stand for are kept from the
+/// board the setup started from
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionSetup {
+    squares: Vec<Vec<Option<char>>>,
+    pub side_to_move: PieceColor,
+    /// Whether each right in CASTLING_LETTERS is given
+    pub castling: [bool; 4],
+    en_passant: Option<(usize, usize)>,
+    pub variant: Variant,
+    pockets: Option<Pockets>,
+    check_counts: (u32, u32),
+    fairy_pieces: Vec<Arc<FairyPieceType>>,
+}
+
+impl PositionSetup {
+    /// A board of the size with nothing on it and White to move
+    pub fn empty(width: usize, height: usize) -> PositionSetup {
+        PositionSetup {
+            squares: vec![vec![None; height]; width],
+            side_to_move: PieceColor::WHITE,
+            castling: [false; 4],
+            en_passant: None,
+            variant: Variant::Standard,
+            pockets: None,
+            check_counts: (0, 0),
+            fairy_pieces: Vec::new(),
+        }
+    }
+
+    /// The normal starting position
+    pub fn standard() -> PositionSetup {
+        PositionSetup::from_board(&Board::new())
+    }
+
+    /// Starts from the position on the board
+    pub fn from_board(board: &Board) -> PositionSetup {
+        let mut setup = PositionSetup::empty(board.width(), board.height());
+        for piece in &board.live_white_pieces {
+            let (x_coord, y_coord) = piece.get_pos();
+            setup.squares[x_coord][y_coord] = piece.get_piece_character().chars().next();
+        }
+        for piece in &board.live_black_pieces {
+            let (x_coord, y_coord) = piece.get_pos();
+            setup.squares[x_coord][y_coord] = piece
+                .get_piece_character()
+                .chars()
+                .next()
+                .map(|letter| letter.to_ascii_lowercase());
+        }
+        setup.side_to_move = PieceColor::opposite_color(&board.last_move_color);
+        // The board already works out the castling rights and en passant square for its FEN
+        let fen = board.to_fen();
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        for (index, letter) in CASTLING_LETTERS.iter().enumerate() {
+            setup.castling[index] = fields[2].contains(*letter);
+        }
+        setup.en_passant = parser::parse_square(fields[3]);
+        setup.variant = board.variant;
+        setup.pockets = board.pockets.clone();
+        setup.check_counts = (
+            board.checks_given(&PieceColor::WHITE),
+            board.checks_given(&PieceColor::BLACK),
+        );
+        setup.fairy_pieces = board.fairy_pieces.clone();
+        setup
+    }
+
+    pub fn from_fen(fen: &str) -> Result<PositionSetup, ParseError> {
+        Ok(PositionSetup::from_board(&Board::from_fen(fen)?))
+    }
+
+    /// Replaces the position with the one in the FEN.  The variant and fairy pieces stay, unless
+    /// the FEN has pockets or checks given that say it is Crazyhouse or Three-check
+    pub fn load_fen(&mut self, fen: &str) -> Result<(), ParseError> {
+        let mut board = Board::from_fen_with_pieces(fen, &self.fairy_piece_types())?;
+        if board.variant == Variant::Standard {
+            board.set_variant(self.variant);
+        }
+        *self = PositionSetup::from_board(&board);
+        Ok(())
+    }
+
+    /// Takes every piece off, keeping the size, variant and fairy pieces
+    pub fn clear(&mut self) {
+        let mut empty = PositionSetup::empty(self.width(), self.height());
+        empty.variant = self.variant;
+        empty.pockets = self.pockets.as_ref().map(|_| Pockets::new());
+        empty.fairy_pieces = self.fairy_pieces.clone();
+        *self = empty;
+    }
+
+    pub fn width(&self) -> usize {
+        self.squares.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.squares.first().map_or(0, Vec::len)
+    }
+
+    pub fn piece_at(&self, x_coord: usize, y_coord: usize) -> Option<char> {
+        self.squares[x_coord][y_coord]
+    }
+
+    /// Puts the piece on the square, or clears it with None
+    pub fn set_piece(&mut self, x_coord: usize, y_coord: usize, piece: Option<char>) {
+        self.squares[x_coord][y_coord] = piece;
+    }
+
+    pub fn en_passant(&self) -> Option<(usize, usize)> {
+        self.en_passant
+    }
+
+    /// The square skipped by a pawn that just moved two.  It has to be on the third rank or the
+    /// third from the top
+    pub fn set_en_passant(&mut self, square: Option<(usize, usize)>) -> Result<(), ParseError> {
+        if let Some((x_coord, y_coord)) = square {
+            if y_coord != 2 && y_coord + 3 != self.height() {
+                return Err(ParseError::new(&format!(
+                    "{} can't be an en passant square",
+                    parser::square_name((x_coord, y_coord))
+                )));
+            }
+        }
+        self.en_passant = square;
+        Ok(())
+    }
+
+    /// The position as a FEN.  Crazyhouse pockets and Three-check checks are written the way
+    /// Board::to_fen writes them but the other variants look like standard chess
+    pub fn to_fen(&self) -> String {
+        let mut ranks = Vec::with_capacity(self.height());
+        for y_coord in (0..self.height()).rev() {
+            let mut rank = String::new();
+            let mut empty_squares = 0;
+            for x_coord in 0..self.width() {
+                match self.squares[x_coord][y_coord] {
+                    Some(letter) => {
+                        if empty_squares > 0 {
+                            rank.push_str(&empty_squares.to_string());
+                            empty_squares = 0;
+                        }
+                        rank.push(letter);
+                    }
+                    None => empty_squares += 1,
+                }
+            }
+            if empty_squares > 0 {
+                rank.push_str(&empty_squares.to_string());
+            }
+            ranks.push(rank);
+        }
+
+        let side_to_move = match self.side_to_move {
+            PieceColor::WHITE => "w",
+            PieceColor::BLACK => "b",
+        };
+        let mut castling_rights: String = CASTLING_LETTERS
+            .iter()
+            .zip(self.castling)
+            .filter(|(_, given)| *given)
+            .map(|(letter, _)| letter)
+            .collect();
+        if castling_rights.is_empty() {
+            castling_rights.push('-');
+        }
+        let en_passant = match self.en_passant {
+            Some(square) => parser::square_name(square),
+            None => String::from("-"),
+        };
+        let mut placement = ranks.join("/");
+        if let Some(pockets) = &self.pockets {
+            placement.push_str(&format!("[{}]", pockets.to_fen()));
+        }
+        let mut fen = format!(
+            "{} {} {} {} 0 1",
+            placement, side_to_move, castling_rights, en_passant
+        );
+        if self.variant == Variant::ThreeCheck {
+            fen.push_str(&format!(
+                " +{}+{}",
+                self.check_counts.0, self.check_counts.1
+            ));
+        }
+        fen
+    }
+
+    /// The board as it is set up, without checking it can be played
+    pub fn board(&self) -> Result<Board, ParseError> {
+        let mut board = Board::from_fen_with_pieces(&self.to_fen(), &self.fairy_piece_types())?;
+        board.set_variant(self.variant);
+        Ok(board)
+    }
+
+    /// A board of the setup's size, variant and fairy pieces with nothing on it
+    pub fn empty_board(&self) -> Result<Board, ParseError> {
+        let mut board = Board::empty(self.width(), self.height())?;
+        for piece_type in self.fairy_piece_types() {
+            board.register_piece(piece_type)?;
+        }
+        board.set_variant(self.variant);
+        Ok(board)
+    }
+
+    fn fairy_piece_types(&self) -> Vec<FairyPieceType> {
+        self.fairy_pieces
+            .iter()
+            .map(|piece_type| (**piece_type).clone())
+            .collect()
+    }
+
+    /// The board to play from.  On top of Board::validate castling rights need the king and rook
+    /// at home and an en passant square needs the pawn that skipped it
+    pub fn to_board(&self) -> Result<Board, ParseError> {
+        for (index, letter) in CASTLING_LETTERS.iter().enumerate() {
+            if self.castling[index] && !self.can_castle(*letter) {
+                return Err(ParseError::new(&format!(
+                    "Castling right {} needs the king and rook on their first squares",
+                    letter
+                )));
+            }
+        }
+
+        if let Some((x_coord, y_coord)) = self.en_passant {
+            if !self.valid_en_passant(x_coord, y_coord) {
+                return Err(ParseError::new(&format!(
+                    "No pawn can be taken en passant on {}",
+                    parser::square_name((x_coord, y_coord))
+                )));
+            }
+        }
+
+        let board = self.board()?;
+        if let Err(errors) = board.validate() {
+            let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+            return Err(ParseError::new(&errors.join(", ")));
+        }
+        Ok(board)
+    }
+
+    // The king somewhere on its first rank with the rook in the corner on the side castled to
+    fn can_castle(&self, castling_letter: char) -> bool {
+        let (king, rook, home_y) = if castling_letter.is_ascii_uppercase() {
+            ('K', 'R', 0)
+        } else {
+            ('k', 'r', self.height() - 1)
+        };
+        let kingside = castling_letter.eq_ignore_ascii_case(&'K');
+        let rook_x = if kingside { self.width() - 1 } else { 0 };
+        let king_x = match (0..self.width()).find(|&x| self.squares[x][home_y] == Some(king)) {
+            Some(king_x) => king_x,
+            None => return false,
+        };
+        self.squares[rook_x][home_y] == Some(rook)
+            && if kingside {
+                king_x < rook_x
+            } else {
+                king_x > rook_x
+            }
+    }
+
+    // The pawn that just moved two has to be in front of the square with nothing behind it
+    fn valid_en_passant(&self, x_coord: usize, y_coord: usize) -> bool {
+        let (pawn, pawn_y, start_y) = match self.side_to_move {
+            PieceColor::WHITE if y_coord + 3 == self.height() => ('p', y_coord - 1, y_coord + 1),
+            PieceColor::BLACK if y_coord == 2 => ('P', y_coord + 1, y_coord - 1),
+            _ => return false,
+        };
+        self.squares[x_coord][pawn_y] == Some(pawn)
+            && self.squares[x_coord][y_coord].is_none()
+            && self.squares[x_coord][start_y].is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::piece_types::PieceColor;
+    use crate::pieces::fairy::FairyPieceType;
+    use crate::setup::PositionSetup;
+    use crate::variant::Variant;
+
+    #[test]
+    fn test_standard_setup() {
+        let setup = PositionSetup::standard();
+        assert_eq!(setup.to_fen(), Board::new().to_fen());
+        assert_eq!(setup.to_board().unwrap().to_fen(), Board::new().to_fen());
+        assert_eq!(
+            PositionSetup::empty(8, 8).to_fen(),
+            "8/8/8/8/8/8/8/8 w - - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_setting_up_a_position() {
+        let mut setup = PositionSetup::empty(8, 8);
+        setup.set_piece(4, 0, Some('K'));
+        setup.set_piece(7, 0, Some('R'));
+        setup.set_piece(4, 7, Some('k'));
+        setup.set_piece(3, 3, Some('P'));
+        setup.set_piece(4, 3, Some('p'));
+        setup.side_to_move = PieceColor::BLACK;
+        setup.castling[0] = true;
+        assert!(setup.set_en_passant(Some((3, 3))).is_err());
+        setup.set_en_passant(Some((3, 2))).unwrap();
+
+        let fen = "4k3/8/8/8/3Pp3/8/8/4K2R b K d3 0 1";
+        assert_eq!(setup.to_fen(), fen);
+        assert_eq!(setup.to_board().unwrap().to_fen(), fen);
+        assert_eq!(PositionSetup::from_fen(fen).unwrap(), setup);
+    }
+
+    #[test]
+    fn test_setup_errors() {
+        let error = |setup: &PositionSetup| match setup.to_board() {
+            Ok(_) => String::from("no error"),
+            Err(error) => error.to_string(),
+        };
+        let setup = |fen: &str| PositionSetup::from_fen(fen).unwrap();
+        assert_eq!(
+            error(&setup("4k3/8/8/8/8/8/8/8 w - - 0 1")),
+            "White can't have 0 kings"
+        );
+        assert_eq!(
+            error(&setup("4k3/8/8/8/8/8/8/K3K3 w - - 0 1")),
+            "White can't have 2 kings"
+        );
+        assert_eq!(
+            error(&setup("4k3/8/8/8/8/8/8/P3K3 w - - 0 1")),
+            "There can't be a pawn on a1"
+        );
+        assert_eq!(
+            error(&setup("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1")),
+            "Black is in check but it is White to move"
+        );
+
+        let mut castling = setup("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+        castling.castling[0] = true;
+        assert_eq!(
+            error(&castling),
+            "Castling right K needs the king and rook on their first squares"
+        );
+
+        // Nothing has just moved two squares to d3
+        let mut en_passant = setup("4k3/8/8/8/4p3/8/8/4K3 b - - 0 1");
+        en_passant.set_en_passant(Some((3, 2))).unwrap();
+        assert_eq!(error(&en_passant), "No pawn can be taken en passant on d3");
+    }
+
+    #[test]
+    fn test_variant_and_fairy_pieces_are_kept() {
+        let setup = PositionSetup::from_board(&Board::new_variant(Variant::Atomic));
+        assert_eq!(setup.variant, Variant::Atomic);
+        assert_eq!(setup.to_board().unwrap().variant, Variant::Atomic);
+
+        let fen = "4k3/8/8/8/8/8/8/4K3[Qp] w - - 0 1";
+        let setup = PositionSetup::from_fen(fen).unwrap();
+        assert_eq!(setup.to_fen(), fen);
+        assert_eq!(setup.to_board().unwrap().variant, Variant::Crazyhouse);
+
+        let fen = "4k3/8/8/8/8/8/8/4K1A1 w - - 0 1";
+        let board = Board::from_fen_with_pieces(fen, &[FairyPieceType::archbishop()]).unwrap();
+        let mut setup = PositionSetup::from_board(&board);
+        assert_eq!(setup.to_board().unwrap().to_fen(), fen);
+
+        // Loading a FEN or clearing the board doesn't forget the archbishop
+        setup.load_fen("a3k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(setup.piece_at(0, 7), Some('a'));
+        setup.clear();
+        setup.set_piece(0, 0, Some('A'));
+        setup.set_piece(4, 0, Some('K'));
+        setup.set_piece(4, 7, Some('k'));
+        assert_eq!(
+            setup.to_board().unwrap().to_fen(),
+            "4k3/8/8/8/8/8/8/A3K3 w - - 0 1"
+        );
+    }
+}
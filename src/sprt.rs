@@ -0,0 +1,229 @@
+use crate::parser::ParseError;
+use std::fmt;
+
+// Counts of zero are replaced with this so a short run of the same result still has a variance
+const EMPTY_COUNT: f64 = 1e-3;
+
+// What each of the five pair results is worth as a share of the pair's points
+const PAIR_SCORES: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+/// A sequential probability ratio test of whether the first engine is elo1 stronger rather than
+/// elo0.  alpha is the chance of accepting when it is only elo0 stronger and beta the chance of
+/// rejecting when it is elo1 stronger
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+/// Where the test has got to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    /// The first engine is elo1 stronger
+    Accepted,
+    /// The first engine is no more than elo0 stronger
+    Rejected,
+    Undecided,
+}
+
+impl fmt::Display for SprtDecision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let decision = match self {
+            SprtDecision::Accepted => "H1 accepted",
+            SprtDecision::Rejected => "H0 accepted",
+            SprtDecision::Undecided => "no decision yet",
+        };
+        write!(f, "{}", decision)
+    }
+}
+
+/// How many game pairs the first engine scored 0, 1/2, 1, 1 1/2 and 2 points in.  Both games of
+/// a pair are played from the same opening so they are counted together
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pentanomial {
+    pub counts: [u32; 5],
+}
+
+impl Pentanomial {
+    /// Counts the pairs in the first engine's points from each game.  A game without the other
+    /// half of its pair isn't counted
+    pub fn from_results(results: &[f64]) -> Pentanomial {
+        let mut pentanomial = Pentanomial::default();
+        for pair in results.chunks_exact(2) {
+            pentanomial.counts[((pair[0] + pair[1]) * 2.0).round() as usize] += 1;
+        }
+        pentanomial
+    }
+
+    pub fn pairs(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+}
+
+impl Sprt {
+    /// Reads elo0=0,elo1=5,alpha=0.05,beta=0.05.  alpha and beta are 0.05 when they are left out
+    pub fn from_string(sprt_string: &str) -> Result<Sprt, ParseError> {
+        let mut elo0 = None;
+        let mut elo1 = None;
+        let mut alpha = 0.05;
+        let mut beta = 0.05;
+        for option in sprt_string.split(',') {
+            let (name, value) = option
+                .split_once('=')
+                .ok_or_else(|| ParseError::new(&format!("{:?} is not name=value", option)))?;
+            let value: f64 = value.trim().parse().map_err(|_| {
+                ParseError::new(&format!("{:?} is not a number in {:?}", value, option))
+            })?;
+            match name.trim() {
+                "elo0" => elo0 = Some(value),
+                "elo1" => elo1 = Some(value),
+                "alpha" => alpha = value,
+                "beta" => beta = value,
+                _ => {
+                    return Err(ParseError::new(&format!(
+                        "Unknown SPRT option {:?}.  Use elo0, elo1, alpha or beta",
+                        name
+                    )))
+                }
+            }
+        }
+        let (elo0, elo1) = match (elo0, elo1) {
+            (Some(elo0), Some(elo1)) if elo0 < elo1 => (elo0, elo1),
+            (Some(_), Some(_)) => return Err(ParseError::new("elo0 has to be below elo1")),
+            _ => return Err(ParseError::new("An SPRT needs elo0 and elo1")),
+        };
+        let is_probability = |value: f64| value > 0.0 && value < 1.0;
+        if !is_probability(alpha) || !is_probability(beta) {
+            return Err(ParseError::new("alpha and beta have to be between 0 and 1"));
+        }
+        Ok(Sprt {
+            elo0,
+            elo1,
+            alpha,
+            beta,
+        })
+    }
+
+    /// The LLR the test stops at, below for H0 and above for H1
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// The log likelihood ratio of elo1 over elo0 for the pairs.  This is the normal
+    /// approximation of the generalized SPRT, with the variance taken from the pairs themselves
+    /// rather than from single games which are not independent of their partner
+    pub fn llr(&self, pentanomial: &Pentanomial) -> f64 {
+        if pentanomial.pairs() == 0 {
+            return 0.0;
+        }
+        let counts: Vec<f64> = pentanomial
+            .counts
+            .iter()
+            .map(|count| match count {
+                0 => EMPTY_COUNT,
+                count => *count as f64,
+            })
+            .collect();
+        let total: f64 = counts.iter().sum();
+        let mean = counts
+            .iter()
+            .zip(PAIR_SCORES)
+            .map(|(count, score)| count * score)
+            .sum::<f64>()
+            / total;
+        let variance = counts
+            .iter()
+            .zip(PAIR_SCORES)
+            .map(|(count, score)| count * (score - mean).powi(2))
+            .sum::<f64>()
+            / total;
+        let score0 = expected_score(self.elo0);
+        let score1 = expected_score(self.elo1);
+        total * (score1 - score0) * (2.0 * mean - score0 - score1) / (2.0 * variance)
+    }
+
+    pub fn decision(&self, llr: f64) -> SprtDecision {
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            SprtDecision::Accepted
+        } else if llr <= lower {
+            SprtDecision::Rejected
+        } else {
+            SprtDecision::Undecided
+        }
+    }
+}
+
+/// The share of the points a side that much Elo stronger is expected to get
+pub fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sprt::{Pentanomial, Sprt, SprtDecision};
+
+    fn sprt() -> Sprt {
+        Sprt::from_string("elo0=0,elo1=5").unwrap()
+    }
+
+    #[test]
+    fn test_read_sprt() {
+        assert_eq!(
+            Sprt::from_string("elo0=-1.5, elo1=3, alpha=0.1, beta=0.2").unwrap(),
+            Sprt {
+                elo0: -1.5,
+                elo1: 3.0,
+                alpha: 0.1,
+                beta: 0.2
+            }
+        );
+        assert!(Sprt::from_string("elo0=5,elo1=0").is_err());
+        assert!(Sprt::from_string("elo1=5").is_err());
+        assert!(Sprt::from_string("elo0=0,elo1=5,alpha=1").is_err());
+        assert!(Sprt::from_string("elo0=0,elo1=5,gamma=0.1").is_err());
+    }
+
+    #[test]
+    fn test_pentanomial_counts_pairs() {
+        let pentanomial = Pentanomial::from_results(&[1.0, 0.5, 0.0, 0.0, 0.5, 0.5, 1.0, 1.0, 1.0]);
+        assert_eq!(pentanomial.counts, [1, 0, 1, 1, 1]);
+        assert_eq!(pentanomial.pairs(), 4);
+    }
+
+    #[test]
+    fn test_llr() {
+        let sprt = sprt();
+        let (lower, upper) = sprt.bounds();
+        assert!((lower + 2.944).abs() < 0.001);
+        assert!((upper - 2.944).abs() < 0.001);
+        assert_eq!(sprt.llr(&Pentanomial::default()), 0.0);
+
+        // An even match counts against a 5 Elo gain, a slightly better one for it
+        let even = Pentanomial {
+            counts: [5, 20, 50, 20, 5],
+        };
+        assert!((sprt.llr(&even) + 0.0518).abs() < 0.0001);
+        let better = Pentanomial {
+            counts: [3, 20, 50, 25, 6],
+        };
+        assert!((sprt.llr(&better) - 0.3563).abs() < 0.0001);
+        assert_eq!(sprt.decision(sprt.llr(&better)), SprtDecision::Undecided);
+
+        // Two pairs won outright are already enough
+        let won = Pentanomial {
+            counts: [0, 0, 0, 0, 2],
+        };
+        assert!((sprt.llr(&won) - 7.6435).abs() < 0.0001);
+        assert_eq!(sprt.decision(sprt.llr(&won)), SprtDecision::Accepted);
+        let lost = Pentanomial {
+            counts: [2, 0, 0, 0, 0],
+        };
+        assert_eq!(sprt.decision(sprt.llr(&lost)), SprtDecision::Rejected);
+    }
+}
@@ -0,0 +1,480 @@
+use crate::board::Board;
+use crate::game::ResolvedMove;
+use crate::parser;
+use crate::san;
+use crate::variant::Variant;
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct EngineError {
+    details: String,
+}
+
+impl EngineError {
+    pub(crate) fn new(msg: &str) -> EngineError {
+        EngineError {
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl Error for EngineError {
+    fn description(&self) -> &str {
+        &self.details
+    }
+}
+
+/// How long the engine is allowed to think about a move
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchLimit {
+    Depth(u32),
+    MoveTime(Duration),
+}
+
+// A mate is scored past any material so adjudication treats it as the biggest score there is
+const MATE_SCORE: i32 = 100_000;
+
+// How long an engine that ran out of time gets to answer stop before we give up on its move
+const STOP_GRACE: Duration = Duration::from_secs(1);
+
+/// What the engine thinks of the position, from the side of the engine on move
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Score {
+    Centipawns(i32),
+    /// Mate in that many moves, negative when the engine is the one getting mated
+    Mate(i32),
+}
+
+impl Score {
+    /// The score in centipawns with mates counted as more than any material
+    pub fn centipawns(&self) -> i32 {
+        match self {
+            Score::Centipawns(centipawns) => *centipawns,
+            Score::Mate(moves) if *moves > 0 => MATE_SCORE - moves,
+            Score::Mate(moves) => -MATE_SCORE - moves,
+        }
+    }
+}
+
+/// The move an engine chose and the score from the last info line that had one
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub best_move: ResolvedMove,
+    pub score: Option<Score>,
+}
+
+/// An external engine that speaks UCI running as a child process
+pub struct UciEngine {
+    name: String,
+    process: Child,
+    input: ChildStdin,
+    // The engine's lines, read on their own thread so waiting for them can time out
+    output: Receiver<String>,
+    // What the engine lists in its UCI_Variant option, empty when it only plays chess
+    variants: Vec<String>,
+    // The variant the engine was last told to play
+    variant: Variant,
+}
+
+impl UciEngine {
+    /// Starts the engine and waits until it says it is ready
+    pub fn start(path: &str) -> Result<UciEngine, EngineError> {
+        let mut process = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|error| {
+                EngineError::new(&format!("Could not start the engine {:?}: {}", path, error))
+            })?;
+        let input = process.stdin.take().unwrap();
+        let stdout = BufReader::new(process.stdout.take().unwrap());
+        let (sender, output) = mpsc::channel();
+        // Ends when the engine closes its output or the engine is dropped
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut engine = UciEngine {
+            name: String::from(path),
+            process,
+            input,
+            output,
+            variants: Vec::new(),
+            variant: Variant::Standard,
+        };
+
+        engine.send("uci")?;
+        for line in engine.read_until("uciok")? {
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.name = String::from(name.trim());
+            }
+            // option name UCI_Variant type combo default chess var chess var atomic ...
+            if line.starts_with("option name UCI_Variant ") {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                engine.variants = words
+                    .windows(2)
+                    .filter(|pair| pair[0] == "var")
+                    .map(|pair| String::from(pair[1]))
+                    .collect();
+            }
+        }
+        engine.send("ucinewgame")?;
+        engine.send("isready")?;
+        engine.read_until("readyok")?;
+        Ok(engine)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the engine can be told about a game played from the board.  Only 8x8 boards
+    /// without fairy pieces can be, in a variant the engine lists in its UCI_Variant option
+    pub fn can_play(&self, board: &Board) -> Result<(), EngineError> {
+        if board.width() != 8 || board.height() != 8 {
+            return Err(EngineError::new(&format!(
+                "{} can't be sent a {}x{} board",
+                self.name,
+                board.width(),
+                board.height()
+            )));
+        }
+        if !board.fairy_pieces.is_empty() {
+            return Err(EngineError::new(&format!(
+                "{} can't be sent a board with fairy pieces",
+                self.name
+            )));
+        }
+        self.uci_variant_name(board.variant).map(|_| ())
+    }
+
+    // The engine's name for the variant.  None for standard chess on an engine without the option
+    fn uci_variant_name(&self, variant: Variant) -> Result<Option<&'static str>, EngineError> {
+        if variant == Variant::Standard && self.variants.is_empty() {
+            return Ok(None);
+        }
+        match variant
+            .uci_names()
+            .iter()
+            .find(|name| self.variants.iter().any(|listed| listed == *name))
+        {
+            Some(name) => Ok(Some(name)),
+            None => Err(EngineError::new(&format!(
+                "{} doesn't play {}",
+                self.name,
+                variant.pgn_name()
+            ))),
+        }
+    }
+
+    // Switches the engine to the board's variant when it was playing another one
+    fn set_variant(&mut self, board: &Board) -> Result<(), EngineError> {
+        self.can_play(board)?;
+        if board.variant == self.variant {
+            return Ok(());
+        }
+        if let Some(name) = self.uci_variant_name(board.variant)? {
+            self.send(&format!("setoption name UCI_Variant value {}", name))?;
+            self.send("ucinewgame")?;
+            self.send("isready")?;
+            self.read_until("readyok")?;
+        }
+        self.variant = board.variant;
+        Ok(())
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), EngineError> {
+        writeln!(self.input, "{}", command)
+            .and_then(|_| self.input.flush())
+            .map_err(|error| {
+                EngineError::new(&format!("Could not talk to {}: {}", self.name, error))
+            })
+    }
+
+    // Reads lines until one starts with the prefix.  Everything read is returned, the last line
+    // is the one that matched
+    fn read_until(&mut self, prefix: &str) -> Result<Vec<String>, EngineError> {
+        self.read_until_deadline(prefix, None)?
+            .ok_or_else(|| EngineError::new(&format!("{} didn't answer", self.name)))
+    }
+
+    // Like read_until but gives up at the deadline.  None when it did
+    fn read_until_deadline(
+        &mut self,
+        prefix: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<Vec<String>>, EngineError> {
+        let mut lines = Vec::new();
+        loop {
+            let line = match deadline {
+                Some(deadline) => self
+                    .output
+                    .recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => self
+                    .output
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            let line = match line {
+                Ok(line) => String::from(line.trim()),
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(EngineError::new(&format!(
+                        "{} quit while we were waiting for {:?}",
+                        self.name, prefix
+                    )))
+                }
+            };
+            let found = line.starts_with(prefix);
+            lines.push(line);
+            if found {
+                return Ok(Some(lines));
+            }
+        }
+    }
+
+    /// Asks the engine for its move in the board's position.  The board has to be one that was
+    /// played from the start board, which is sent as a FEN with the moves played since
+    pub fn best_move(
+        &mut self,
+        start_board: &Board,
+        board: &Board,
+        limit: &SearchLimit,
+    ) -> Result<ResolvedMove, EngineError> {
+        self.search(start_board, board, limit)
+            .map(|search_result| search_result.best_move)
+    }
+
+    /// Like best_move but keeps the score the engine gave too
+    pub fn search(
+        &mut self,
+        start_board: &Board,
+        board: &Board,
+        limit: &SearchLimit,
+    ) -> Result<SearchResult, EngineError> {
+        self.search_until(start_board, board, limit, None)?
+            .ok_or_else(|| EngineError::new(&format!("{} didn't answer", self.name)))
+    }
+
+    /// Like search but gives up on the engine at the deadline.  None when it hadn't moved by then,
+    /// in which case it is told to stop
+    pub fn search_until(
+        &mut self,
+        start_board: &Board,
+        board: &Board,
+        limit: &SearchLimit,
+        deadline: Option<Instant>,
+    ) -> Result<Option<SearchResult>, EngineError> {
+        self.set_variant(start_board)?;
+        // A move from a search we gave up on may have turned up since
+        while self.output.try_recv().is_ok() {}
+        let moves: Vec<String> = board
+            .played_plies()
+            .iter()
+            .skip(start_board.played_plies().len())
+            .map(|(start, end, promotion_piece)| {
+                to_long_algebraic(*start, *end, promotion_piece.as_deref())
+            })
+            .collect();
+        let mut position = format!("position fen {}", start_board.to_fen());
+        if !moves.is_empty() {
+            position.push_str(&format!(" moves {}", moves.join(" ")));
+        }
+        self.send(&position)?;
+        match limit {
+            SearchLimit::Depth(depth) => self.send(&format!("go depth {}", depth))?,
+            SearchLimit::MoveTime(move_time) => {
+                self.send(&format!("go movetime {}", move_time.as_millis()))?
+            }
+        };
+
+        let mut lines = match self.read_until_deadline("bestmove", deadline)? {
+            Some(lines) => lines,
+            None => {
+                self.send("stop")?;
+                self.read_until_deadline("bestmove", Some(Instant::now() + STOP_GRACE))?;
+                return Ok(None);
+            }
+        };
+        let best_move_line = lines.pop().unwrap();
+        let best_move = best_move_line.split_whitespace().nth(1).unwrap_or("");
+        let best_move = from_long_algebraic(best_move).ok_or_else(|| {
+            EngineError::new(&format!(
+                "{} sent a move we don't understand: {:?}",
+                self.name, best_move_line
+            ))
+        })?;
+        Ok(Some(SearchResult {
+            best_move,
+            score: lines.iter().rev().find_map(|line| parse_score(line)),
+        }))
+    }
+
+    /// Gets the engine's move and plays it on the board.  Returns the move in SAN
+    pub fn play_best_move(
+        &mut self,
+        start_board: &Board,
+        board: &mut Board,
+        limit: &SearchLimit,
+    ) -> Result<String, EngineError> {
+        let (start, end, promotion_piece) = self.best_move(start_board, board, limit)?;
+        let san = san::move_to_san(board, start, end, promotion_piece.as_deref());
+        board
+            .play_move_from_coords(start, end, promotion_piece.as_deref())
+            .map_err(|error| {
+                EngineError::new(&format!(
+                    "{} played {} which isn't legal: {}",
+                    self.name,
+                    to_long_algebraic(start, end, promotion_piece.as_deref()),
+                    error
+                ))
+            })?;
+        Ok(san)
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        if self.send("quit").is_err() {
+            let _ = self.process.kill();
+        }
+        let _ = self.process.wait();
+    }
+}
+
+/// Reads the score out of an info line like "info depth 12 score cp 35 nodes ...".  None when the
+/// line isn't an info line or has no score
+pub fn parse_score(line: &str) -> Option<Score> {
+    let mut words = line.split_whitespace();
+    if words.next() != Some("info") {
+        return None;
+    }
+    words.find(|word| *word == "score")?;
+    match (words.next()?, words.next()?.parse().ok()?) {
+        ("cp", centipawns) => Some(Score::Centipawns(centipawns)),
+        ("mate", moves) => Some(Score::Mate(moves)),
+        _ => None,
+    }
+}
+
+/// Writes a move the way UCI wants it, e2e4 or e7e8q.  Drops are N@f3
+pub fn to_long_algebraic(
+    start_position: (usize, usize),
+    end_position: (usize, usize),
+    promotion_piece: Option<&str>,
+) -> String {
+    if let (true, Some(drop_piece)) = (start_position == end_position, promotion_piece) {
+        return format!(
+            "{}@{}{}",
+            drop_piece,
+            parser::file_from_coordinate(end_position.0),
+            parser::rank_from_coordinate(end_position.1)
+        );
+    }
+    format!(
+        "{}{}{}{}{}",
+        parser::file_from_coordinate(start_position.0),
+        parser::rank_from_coordinate(start_position.1),
+        parser::file_from_coordinate(end_position.0),
+        parser::rank_from_coordinate(end_position.1),
+        promotion_piece.unwrap_or("").to_ascii_lowercase()
+    )
+}
+
+pub fn from_long_algebraic(move_string: &str) -> Option<ResolvedMove> {
+    // Squares are a letter and a number, which can have two digits on a big board
+    let mut squares = Vec::new();
+    let mut rest = move_string;
+    if let Some((piece, square)) = move_string.split_once('@') {
+        if !matches!(piece, "Q" | "R" | "B" | "N" | "P") {
+            return None;
+        }
+        let square = parser::parse_square(square)?;
+        return Some((square, square, Some(piece.to_string())));
+    }
+    while squares.len() < 2 {
+        let rank_end = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, character)| !character.is_ascii_digit())
+            .map_or(rest.len(), |(index, _)| index);
+        squares.push(parser::parse_square(&rest[..rank_end])?);
+        rest = &rest[rank_end..];
+    }
+    let promotion_piece = match rest {
+        "" => None,
+        "q" | "r" | "b" | "n" | "k" => Some(rest.to_ascii_uppercase()),
+        _ => return None,
+    };
+    Some((squares[0], squares[1], promotion_piece))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::uci::{from_long_algebraic, parse_score, to_long_algebraic, Score};
+
+    #[test]
+    fn test_long_algebraic_round_trip() {
+        assert_eq!(to_long_algebraic((4, 1), (4, 3), None), "e2e4");
+        assert_eq!(to_long_algebraic((0, 6), (0, 7), Some("N")), "a7a8n");
+        assert_eq!(from_long_algebraic("e2e4"), Some(((4, 1), (4, 3), None)));
+        assert_eq!(
+            from_long_algebraic("h2h1q"),
+            Some(((7, 1), (7, 0), Some(String::from("Q"))))
+        );
+        assert_eq!(to_long_algebraic((5, 2), (5, 2), Some("N")), "N@f3");
+        assert_eq!(
+            from_long_algebraic("N@f3"),
+            Some(((5, 2), (5, 2), Some(String::from("N"))))
+        );
+    }
+
+    #[test]
+    fn test_bad_long_algebraic() {
+        assert_eq!(from_long_algebraic("(none)"), None);
+        assert_eq!(from_long_algebraic("e2e0"), None);
+        assert_eq!(from_long_algebraic("e2e17"), None);
+        assert_eq!(from_long_algebraic("e7e8p"), None);
+        // Antichess engines can promote to a king
+        assert_eq!(
+            from_long_algebraic("e7e8k"),
+            Some(((4, 6), (4, 7), Some(String::from("K"))))
+        );
+        // Ranks past 9 on a big board
+        assert_eq!(
+            from_long_algebraic("i9i10q"),
+            Some(((8, 8), (8, 9), Some(String::from("Q"))))
+        );
+    }
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(
+            parse_score("info depth 12 seldepth 15 score cp -35 nodes 1000 pv e2e4"),
+            Some(Score::Centipawns(-35))
+        );
+        assert_eq!(
+            parse_score("info depth 20 score mate 3 lowerbound"),
+            Some(Score::Mate(3))
+        );
+        assert_eq!(parse_score("info depth 5 nodes 100"), None);
+        assert_eq!(parse_score("bestmove e2e4"), None);
+        assert!(Score::Mate(-2).centipawns() < Score::Centipawns(-5000).centipawns());
+        assert!(Score::Mate(1).centipawns() > Score::Mate(5).centipawns());
+    }
+}
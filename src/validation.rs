@@ -0,0 +1,294 @@
+use crate::board::Board;
+use crate::game;
+use crate::parser;
+use crate::piece_types::{PieceColor, QuickPiece};
+use crate::pieces::{AnyPiece, PieceMove};
+use crate::variant::Variant;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Something about a position that can't come up in a game
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionError {
+    details: String,
+}
+
+impl PositionError {
+    pub(crate) fn new(msg: &str) -> PositionError {
+        PositionError {
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl Error for PositionError {
+    fn description(&self) -> &str {
+        &self.details
+    }
+}
+
+impl Board {
+    /// Checks the position could come up in a game of the board's variant.  Every problem found is
+    /// returned, not just the first
+    pub fn validate(&self) -> Result<(), Vec<PositionError>> {
+        let mut errors = self.consistency_errors();
+        errors.extend(self.king_errors());
+        // Check can only be worked out when the kings are where the board says they are
+        if errors.is_empty() {
+            errors.extend(self.check_errors());
+        }
+        errors.extend(self.pawn_errors());
+        errors.extend(self.material_errors());
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn pieces_of(&self, piece_color: &PieceColor) -> &Vec<AnyPiece> {
+        match piece_color {
+            PieceColor::WHITE => &self.live_white_pieces,
+            PieceColor::BLACK => &self.live_black_pieces,
+        }
+    }
+
+    // position_board and the live pieces have to agree on every square
+    fn consistency_errors(&self) -> Vec<PositionError> {
+        let height = self.height();
+        if self.position_board.iter().any(|file| file.len() != height) {
+            return vec![PositionError::new(&format!(
+                "The files of the board aren't all {} squares long",
+                height
+            ))];
+        }
+
+        let mut errors = Vec::new();
+        let mut pieces_on_square = HashMap::new();
+        for piece_color in [PieceColor::WHITE, PieceColor::BLACK] {
+            for piece in self.pieces_of(&piece_color) {
+                let (x_coord, y_coord) = piece.get_pos();
+                let letter = piece.get_piece_character();
+                if x_coord >= self.width() || y_coord >= height {
+                    errors.push(PositionError::new(&format!(
+                        "The {} at ({}, {}) is off the board",
+                        piece_name(&piece_color, letter),
+                        x_coord,
+                        y_coord
+                    )));
+                    continue;
+                }
+                *pieces_on_square.entry((x_coord, y_coord)).or_insert(0) += 1;
+                let expected = match piece {
+                    AnyPiece::King(_) => QuickPiece::KING(piece_color),
+                    _ => QuickPiece::PIECE(piece_color),
+                };
+                let found = self.position_board[x_coord][y_coord];
+                if found != expected {
+                    errors.push(PositionError::new(&format!(
+                        "There is a {} on {} but the board has {} there",
+                        piece_name(&piece_color, letter),
+                        parser::square_name((x_coord, y_coord)),
+                        square_contents(found)
+                    )));
+                }
+            }
+        }
+
+        for x_coord in 0..self.width() {
+            for y_coord in 0..height {
+                let square = parser::square_name((x_coord, y_coord));
+                match pieces_on_square.get(&(x_coord, y_coord)) {
+                    Some(count) if *count > 1 => errors.push(PositionError::new(&format!(
+                        "{} pieces are on {}",
+                        count, square
+                    ))),
+                    None if self.position_board[x_coord][y_coord] != QuickPiece::EMPTY => errors
+                        .push(PositionError::new(&format!(
+                            "The board has {} on {} but no piece is there",
+                            square_contents(self.position_board[x_coord][y_coord]),
+                            square
+                        ))),
+                    _ => (),
+                }
+            }
+        }
+
+        for (piece_color, king_position) in [
+            (PieceColor::WHITE, self.white_king_position),
+            (PieceColor::BLACK, self.black_king_position),
+        ] {
+            let kings: Vec<(usize, usize)> = self
+                .pieces_of(&piece_color)
+                .iter()
+                .filter(|piece| matches!(piece, AnyPiece::King(_)))
+                .map(|king| king.get_pos())
+                .collect();
+            if let [king] = kings.as_slice() {
+                if *king != king_position {
+                    errors.push(PositionError::new(&format!(
+                        "The {} king is on {} but the board has it on {}",
+                        color_name(&piece_color).to_ascii_lowercase(),
+                        parser::square_name(*king),
+                        parser::square_name(king_position)
+                    )));
+                }
+            }
+        }
+        errors
+    }
+
+    // One king each, except that Antichess kings are ordinary pieces, the Horde has none and an
+    // Atomic king can be blown up
+    fn king_errors(&self) -> Vec<PositionError> {
+        let mut errors = Vec::new();
+        for piece_color in [PieceColor::WHITE, PieceColor::BLACK] {
+            let allowed_kings = match (self.variant, piece_color) {
+                (Variant::Antichess, _) => continue,
+                (Variant::Horde, PieceColor::WHITE) => 0..=0,
+                (Variant::Atomic, _) => 0..=1,
+                _ => 1..=1,
+            };
+            let king_count = self
+                .pieces_of(&piece_color)
+                .iter()
+                .filter(|piece| matches!(piece, AnyPiece::King(_)))
+                .count();
+            if !allowed_kings.contains(&king_count) {
+                errors.push(PositionError::new(&format!(
+                    "{} can't have {} kings",
+                    color_name(&piece_color),
+                    king_count
+                )));
+            }
+        }
+        errors
+    }
+
+    // The side that just moved can't have left its king in check
+    fn check_errors(&self) -> Vec<PositionError> {
+        let side_to_move = PieceColor::opposite_color(&self.last_move_color);
+        if game::is_board_in_check(&side_to_move, self) {
+            vec![PositionError::new(&format!(
+                "{} is in check but it is {} to move",
+                color_name(&self.last_move_color),
+                color_name(&side_to_move)
+            ))]
+        } else {
+            Vec::new()
+        }
+    }
+
+    // Pawns promote on the last rank and never go back to the first, though Horde starts some
+    // white pawns there
+    fn pawn_errors(&self) -> Vec<PositionError> {
+        let last_rank = self.height().saturating_sub(1);
+        let mut errors = Vec::new();
+        for piece_color in [PieceColor::WHITE, PieceColor::BLACK] {
+            let first_rank = match piece_color {
+                PieceColor::WHITE => 0,
+                PieceColor::BLACK => last_rank,
+            };
+            let horde_pawns = self.variant == Variant::Horde && piece_color == PieceColor::WHITE;
+            for piece in self.pieces_of(&piece_color) {
+                let (x_coord, y_coord) = piece.get_pos();
+                if matches!(piece, AnyPiece::Pawn(_))
+                    && (y_coord == 0 || y_coord == last_rank)
+                    && !(horde_pawns && y_coord == first_rank)
+                {
+                    errors.push(PositionError::new(&format!(
+                        "There can't be a pawn on {}",
+                        parser::square_name((x_coord, y_coord))
+                    )));
+                }
+            }
+        }
+        errors
+    }
+
+    // Every piece past the number a side starts with has to be a promoted pawn.  Only looked at
+    // when the board is the size of the variant's starting position, and not in Crazyhouse where
+    // captured pieces change sides.  Extra kings are left to king_errors except in Antichess,
+    // where a pawn can promote to one
+    fn material_errors(&self) -> Vec<PositionError> {
+        if self.variant == Variant::Crazyhouse {
+            return Vec::new();
+        }
+        let start = Board::new_variant(self.variant);
+        if start.width() != self.width() || start.height() != self.height() {
+            return Vec::new();
+        }
+
+        let piece_counts = |pieces: &Vec<AnyPiece>| {
+            let mut counts: HashMap<&'static str, usize> = HashMap::new();
+            for piece in pieces {
+                *counts.entry(piece.get_piece_character()).or_insert(0) += 1;
+            }
+            counts
+        };
+        let mut errors = Vec::new();
+        for piece_color in [PieceColor::WHITE, PieceColor::BLACK] {
+            let counts = piece_counts(self.pieces_of(&piece_color));
+            let start_counts = piece_counts(start.pieces_of(&piece_color));
+            let start_count = |letter: &str| start_counts.get(letter).copied().unwrap_or(0);
+            let pawns = counts.get("P").copied().unwrap_or(0);
+            let promoted: usize = counts
+                .iter()
+                .filter(|(letter, _)| {
+                    **letter != "P" && (**letter != "K" || self.variant == Variant::Antichess)
+                })
+                .map(|(letter, count)| count.saturating_sub(start_count(letter)))
+                .sum();
+            if pawns + promoted > start_count("P") {
+                errors.push(PositionError::new(&format!(
+                    "{} has {} pawns and {} promoted pieces but only starts with {} pawns",
+                    color_name(&piece_color),
+                    pawns,
+                    promoted,
+                    start_count("P")
+                )));
+            }
+        }
+        errors
+    }
+}
+
+fn color_name(piece_color: &PieceColor) -> &'static str {
+    match piece_color {
+        PieceColor::WHITE => "White",
+        PieceColor::BLACK => "Black",
+    }
+}
+
+// white knight, black pawn.  Fairy pieces go by their letter
+fn piece_name(piece_color: &PieceColor, letter: &str) -> String {
+    let kind = match letter {
+        "K" => "king",
+        "Q" => "queen",
+        "R" => "rook",
+        "B" => "bishop",
+        "N" => "knight",
+        "P" => "pawn",
+        _ => letter,
+    };
+    format!("{} {}", color_name(piece_color).to_ascii_lowercase(), kind)
+}
+
+fn square_contents(quick_piece: QuickPiece) -> String {
+    match quick_piece {
+        QuickPiece::EMPTY => String::from("nothing"),
+        QuickPiece::PIECE(piece_color) => {
+            format!("a {} piece", color_name(&piece_color).to_ascii_lowercase())
+        }
+        QuickPiece::KING(piece_color) => {
+            format!("a {} king", color_name(&piece_color).to_ascii_lowercase())
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::board::Board;
+use crate::controller::Termination;
+use crate::game;
+use crate::parser::ParseError;
+use crate::piece_types::PieceColor;
+use std::fmt;
+
+// Three-check is won on the third check given
+const CHECKS_TO_WIN: u32 = 3;
+
+const HORDE_FEN: &str =
+    "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1";
+const RACING_KINGS_FEN: &str = "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1";
+
+/// The rules a game is played with.  Every variant moves the pieces the standard way, they add to
+/// the board or change how the game can be won
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Captured pieces go to the capturer's pocket and can be dropped back on the board
+    Crazyhouse,
+    /// Giving the third check wins
+    ThreeCheck,
+    /// Getting the king to one of the four center squares wins
+    KingOfTheHill,
+    /// Captures blow up the pieces around them.  Blowing up the other king wins
+    Atomic,
+    /// Captures have to be made, the king is an ordinary piece, and losing every piece or having
+    /// no moves wins
+    Antichess,
+    /// White has 36 pawns and no king against a normal army, and loses when every pawn is gone
+    Horde,
+    /// Check isn't allowed and the first king to the eighth rank wins
+    RacingKings,
+}
+
+impl Variant {
+    /// Reads a variant from the command line or a PGN Variant tag.  Case, spaces and dashes
+    /// don't matter so Three-check, threecheck and three check are all the same
+    pub fn from_string(variant_string: &str) -> Result<Variant, ParseError> {
+        let name: String = variant_string
+            .chars()
+            .filter(|character| !matches!(character, ' ' | '-' | '_'))
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match name.as_str() {
+            "standard" | "chess" => Ok(Variant::Standard),
+            "crazyhouse" | "zh" => Ok(Variant::Crazyhouse),
+            "threecheck" | "3check" => Ok(Variant::ThreeCheck),
+            "kingofthehill" | "koth" => Ok(Variant::KingOfTheHill),
+            "atomic" => Ok(Variant::Atomic),
+            "antichess" | "giveaway" => Ok(Variant::Antichess),
+            "horde" => Ok(Variant::Horde),
+            "racingkings" | "racing" => Ok(Variant::RacingKings),
+            _ => Err(ParseError::new(&format!(
+                "{:?} is not a variant.  Use standard, crazyhouse, threecheck, kingofthehill, atomic, antichess, horde or racingkings",
+                variant_string
+            ))),
+        }
+    }
+
+    /// The name used in the PGN Variant tag
+    pub fn pgn_name(&self) -> &'static str {
+        match self {
+            Variant::Standard => "Standard",
+            Variant::Crazyhouse => "Crazyhouse",
+            Variant::ThreeCheck => "Three-check",
+            Variant::KingOfTheHill => "King of the Hill",
+            Variant::Atomic => "Atomic",
+            Variant::Antichess => "Antichess",
+            Variant::Horde => "Horde",
+            Variant::RacingKings => "Racing Kings",
+        }
+    }
+
+    /// The names UCI engines give the variant in their UCI_Variant option.  Engines don't agree
+    /// on all of them so the first one an engine lists is used
+    pub fn uci_names(&self) -> &'static [&'static str] {
+        match self {
+            Variant::Standard => &["chess"],
+            Variant::Crazyhouse => &["crazyhouse"],
+            Variant::ThreeCheck => &["3check", "threecheck"],
+            Variant::KingOfTheHill => &["kingofthehill"],
+            Variant::Atomic => &["atomic"],
+            Variant::Antichess => &["antichess", "giveaway"],
+            Variant::Horde => &["horde"],
+            Variant::RacingKings => &["racingkings"],
+        }
+    }
+
+    /// The FEN of the starting position when it isn't the standard one
+    pub fn starting_fen(&self) -> Option<&'static str> {
+        match self {
+            Variant::Horde => Some(HORDE_FEN),
+            Variant::RacingKings => Some(RACING_KINGS_FEN),
+            _ => None,
+        }
+    }
+
+    /// What a pawn can promote to.  Antichess adds the king
+    pub fn promotion_pieces(&self) -> &'static [&'static str] {
+        match self {
+            Variant::Antichess => &["Q", "R", "B", "N", "K"],
+            _ => &["Q", "R", "B", "N"],
+        }
+    }
+
+    /// The win the variant adds to checkmate, looked at after every move.  Returns the winner and
+    /// why they won
+    pub fn winner(&self, board: &Board) -> Option<(PieceColor, Termination)> {
+        match self {
+            Variant::ThreeCheck => [PieceColor::WHITE, PieceColor::BLACK]
+                .into_iter()
+                .find(|color| board.checks_given(color) >= CHECKS_TO_WIN)
+                .map(|color| (color, Termination::ThreeChecks)),
+            Variant::KingOfTheHill => {
+                let on_hill = |king_position| is_hill_square(board, king_position);
+                if on_hill(board.white_king_position) {
+                    Some((PieceColor::WHITE, Termination::KingOfTheHill))
+                } else if on_hill(board.black_king_position) {
+                    Some((PieceColor::BLACK, Termination::KingOfTheHill))
+                } else {
+                    None
+                }
+            }
+            Variant::Atomic => [PieceColor::WHITE, PieceColor::BLACK]
+                .into_iter()
+                .find(|color| !board.has_king(color))
+                .map(|color| (PieceColor::opposite_color(&color), Termination::Explosion)),
+            Variant::Antichess => {
+                if let Some(color) =
+                    [PieceColor::WHITE, PieceColor::BLACK]
+                        .into_iter()
+                        .find(|color| match color {
+                            PieceColor::WHITE => board.live_white_pieces.is_empty(),
+                            PieceColor::BLACK => board.live_black_pieces.is_empty(),
+                        })
+                {
+                    return Some((color, Termination::NoPiecesLeft));
+                }
+                // Being stalemated wins too
+                let to_move = PieceColor::opposite_color(&board.last_move_color);
+                if game::legal_moves(&to_move, &mut board.clone()).is_empty() {
+                    Some((to_move, Termination::NoMovesLeft))
+                } else {
+                    None
+                }
+            }
+            Variant::Horde if board.live_white_pieces.is_empty() => {
+                Some((PieceColor::BLACK, Termination::HordeDestroyed))
+            }
+            Variant::RacingKings => {
+                let last_rank = board.height() - 1;
+                let white_home = board.white_king_position.1 == last_rank;
+                let black_home = board.black_king_position.1 == last_rank;
+                match (white_home, black_home) {
+                    (false, true) => Some((PieceColor::BLACK, Termination::EighthRank)),
+                    // Black gets one more move to draw level
+                    (true, false) if board.last_move_color == PieceColor::WHITE => {
+                        let black_king = board.black_king_position;
+                        let can_reach = game::legal_moves(&PieceColor::BLACK, &mut board.clone())
+                            .iter()
+                            .any(|(start, end)| *start == black_king && end.1 == last_rank);
+                        if can_reach {
+                            None
+                        } else {
+                            Some((PieceColor::WHITE, Termination::EighthRank))
+                        }
+                    }
+                    (true, false) => Some((PieceColor::WHITE, Termination::EighthRank)),
+                    _ => None,
+                }
+            }
+            Variant::Standard | Variant::Crazyhouse | Variant::Horde => None,
+        }
+    }
+
+    /// True when the variant ends the game in a draw.  Only Racing Kings has one of its own, both
+    /// kings reaching the eighth rank
+    pub fn is_draw(&self, board: &Board) -> bool {
+        *self == Variant::RacingKings
+            && board.white_king_position.1 == board.height() - 1
+            && board.black_king_position.1 == board.height() - 1
+    }
+}
+
+// The middle two files and ranks of the board, or the middle one when there is an odd number.
+// That is d4, d5, e4 and e5 on a normal board
+fn is_hill_square(board: &Board, (x_coord, y_coord): (usize, usize)) -> bool {
+    let is_middle = |coord: usize, size: usize| (size - 1) / 2 <= coord && coord <= size / 2;
+    is_middle(x_coord, board.width()) && is_middle(y_coord, board.height())
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.pgn_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::controller::Termination;
+    use crate::piece_types::PieceColor;
+    use crate::variant::Variant;
+
+    #[test]
+    fn test_variant_names() {
+        assert_eq!(
+            Variant::from_string("Three-check").unwrap(),
+            Variant::ThreeCheck
+        );
+        assert_eq!(
+            Variant::from_string("king of the hill").unwrap(),
+            Variant::KingOfTheHill
+        );
+        assert_eq!(
+            Variant::from_string("Crazyhouse").unwrap(),
+            Variant::Crazyhouse
+        );
+        assert!(Variant::from_string("shogi").is_err());
+        // Losers has check and mate so it isn't Antichess
+        assert!(Variant::from_string("losers").is_err());
+        for variant in [
+            Variant::Standard,
+            Variant::Crazyhouse,
+            Variant::ThreeCheck,
+            Variant::KingOfTheHill,
+            Variant::Atomic,
+            Variant::Antichess,
+            Variant::Horde,
+            Variant::RacingKings,
+        ] {
+            assert_eq!(Variant::from_string(variant.pgn_name()).unwrap(), variant);
+        }
+    }
+
+    #[test]
+    fn test_king_on_the_hill_wins() {
+        let board = Board::from_fen("4k3/8/8/8/3K4/8/8/8 b - - 0 1").unwrap();
+        assert_eq!(
+            Variant::KingOfTheHill.winner(&board),
+            Some((PieceColor::WHITE, Termination::KingOfTheHill))
+        );
+        assert_eq!(Variant::Standard.winner(&board), None);
+    }
+
+    #[test]
+    fn test_hill_is_the_middle_of_any_board() {
+        // e4, e5, f4 and f5 on ten files
+        let board = Board::from_fen("5k4/10/10/10/5K4/10/10/10 b - - 0 1").unwrap();
+        assert_eq!(
+            Variant::KingOfTheHill.winner(&board),
+            Some((PieceColor::WHITE, Termination::KingOfTheHill))
+        );
+        let board = Board::from_fen("5k4/10/10/10/3K6/10/10/10 b - - 0 1").unwrap();
+        assert_eq!(Variant::KingOfTheHill.winner(&board), None);
+
+        // Only c3 with five files and ranks
+        let board = Board::from_fen("k4/5/2K2/5/5 b - - 0 1").unwrap();
+        assert_eq!(
+            Variant::KingOfTheHill.winner(&board),
+            Some((PieceColor::WHITE, Termination::KingOfTheHill))
+        );
+        let board = Board::from_fen("k4/5/3K1/5/5 b - - 0 1").unwrap();
+        assert_eq!(Variant::KingOfTheHill.winner(&board), None);
+    }
+}
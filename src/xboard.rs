@@ -0,0 +1,412 @@
+use crate::board::Board;
+use crate::chess_game::Game;
+use crate::clock::{Clock, DelayKind, TimeControl, TimeStage};
+use crate::engine::{self, SearchProgress};
+use crate::piece_types::PieceColor;
+use crate::player::{Player, Players};
+use crate::uci::{self, Score, SearchLimit};
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+// What we tell the GUI after protover 2.  Moves come after usermove so they can't be taken for
+// commands, and the engine only thinks on its own move so it doesn't need to be interrupted
+const FEATURES: &str = "feature myname=\"chess\" setboard=1 usermove=1 time=1 ping=1 playother=0 colors=0 sigint=0 sigterm=0 reuse=1 analyze=0 variants=\"normal\" done=1";
+
+// xboard wants a mate in N as this plus N, and a mate against as minus this minus N
+const XBOARD_MATE: i32 = 100_000;
+
+/// The engine's side of a game played over the Chess Engine Communication Protocol that xboard
+/// and WinBoard speak.  The built-in engine does the thinking
+pub struct XboardSession {
+    game: Game,
+    // The color the engine plays.  None in force mode, when it only keeps track of the moves
+    engine_color: Option<PieceColor>,
+    // From st or sd, which win over the clock
+    limit: Option<SearchLimit>,
+    // From level
+    time_control: Option<TimeControl>,
+    // What the last time command said was left on the engine's clock
+    engine_time: Option<Duration>,
+    // Whether to send thinking output
+    post: bool,
+}
+
+impl Default for XboardSession {
+    fn default() -> Self {
+        XboardSession::new()
+    }
+}
+
+impl XboardSession {
+    /// A session in the starting position with the engine playing Black, which is where xboard
+    /// expects a new engine to be
+    pub fn new() -> XboardSession {
+        XboardSession {
+            game: Game::default(),
+            engine_color: Some(PieceColor::BLACK),
+            limit: None,
+            time_control: None,
+            engine_time: None,
+            post: false,
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        self.game.board()
+    }
+
+    /// Runs one command from the GUI and writes whatever the engine answers with.  quit is left
+    /// to whoever is reading the commands
+    pub fn command(&mut self, line: &str, output: &mut impl Write) -> io::Result<()> {
+        let line = line.trim();
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        let argument = argument.trim();
+        match command {
+            // Nothing the engine has to do anything about.  otim is the GUI's clock, which
+            // doesn't change how long the engine thinks
+            "" | "xboard" | "accepted" | "rejected" | "random" | "hard" | "easy" | "computer"
+            | "name" | "rating" | "ics" | "otim" | "draw" | "." => Ok(()),
+            "protover" => writeln!(output, "{}", FEATURES),
+            "ping" => writeln!(output, "pong {}", argument),
+            "new" => {
+                // sd doesn't last past the game it was sent in
+                self.game = Game::default();
+                self.engine_color = Some(PieceColor::BLACK);
+                self.limit = None;
+                self.engine_time = None;
+                Ok(())
+            }
+            "force" | "result" => {
+                self.engine_color = None;
+                Ok(())
+            }
+            "go" => {
+                self.engine_color = Some(self.game.side_to_move());
+                self.engine_move(output)
+            }
+            "usermove" => self.user_move(argument, output),
+            "setboard" => match Board::from_fen(argument) {
+                Ok(board) => {
+                    self.game = Game::from_board(board, Players::default(), None);
+                    Ok(())
+                }
+                Err(error) => writeln!(output, "tellusererror Illegal position: {}", error),
+            },
+            "time" => match argument.parse::<u64>() {
+                Ok(centiseconds) => {
+                    self.engine_time = Some(Duration::from_millis(centiseconds * 10));
+                    Ok(())
+                }
+                Err(_) => writeln!(output, "Error (bad time): {}", line),
+            },
+            "level" => match parse_level(argument) {
+                Some(time_control) => {
+                    self.time_control = Some(time_control);
+                    self.limit = None;
+                    Ok(())
+                }
+                None => writeln!(output, "Error (bad level): {}", line),
+            },
+            "st" => match argument.parse::<u64>() {
+                Ok(seconds) => {
+                    self.limit = Some(SearchLimit::MoveTime(Duration::from_secs(seconds)));
+                    Ok(())
+                }
+                Err(_) => writeln!(output, "Error (bad time): {}", line),
+            },
+            "sd" => match argument.parse::<u32>() {
+                Ok(depth) => {
+                    self.limit = Some(SearchLimit::Depth(depth));
+                    Ok(())
+                }
+                Err(_) => writeln!(output, "Error (bad depth): {}", line),
+            },
+            "undo" | "remove" => {
+                let plies = if command == "undo" { 1 } else { 2 };
+                match self.game.take_back(plies) {
+                    Ok(()) => Ok(()),
+                    Err(error) => writeln!(output, "Error ({}): {}", error, line),
+                }
+            }
+            "post" | "nopost" => {
+                self.post = command == "post";
+                Ok(())
+            }
+            // Protocol 1 GUIs send moves on their own
+            _ if uci::from_long_algebraic(command).is_some() => self.user_move(command, output),
+            _ => writeln!(output, "Error (unknown command): {}", command),
+        }
+    }
+
+    // Plays the GUI's move and answers it when it is the engine's turn
+    fn user_move(&mut self, move_string: &str, output: &mut impl Write) -> io::Result<()> {
+        let played = uci::from_long_algebraic(move_string).is_some_and(|(start, end, piece)| {
+            self.game.submit_move(start, end, piece.as_deref()).is_ok()
+        });
+        if !played {
+            return writeln!(output, "Illegal move: {}", move_string);
+        }
+        self.write_result(output)?;
+        if self.engine_color == Some(self.game.side_to_move()) {
+            self.engine_move(output)?;
+        }
+        Ok(())
+    }
+
+    fn engine_move(&mut self, output: &mut impl Write) -> io::Result<()> {
+        if self.game.is_over() {
+            return Ok(());
+        }
+        let color = self.game.side_to_move();
+        let limit = self.search_limit(&color);
+        let think_start = Instant::now();
+        let post = self.post;
+        let search_result = engine::search_with_progress(self.game.board(), &limit, |progress| {
+            if post {
+                let _ = writeln!(output, "{}", thinking_line(progress, think_start.elapsed()));
+                let _ = output.flush();
+            }
+        });
+        let (start, end, promotion_piece) = match search_result {
+            Some(search_result) => search_result.best_move,
+            None => return Ok(()),
+        };
+        if let Err(error) = self
+            .game
+            .submit_move(start, end, promotion_piece.as_deref())
+        {
+            return writeln!(output, "tellusererror {}", error);
+        }
+        writeln!(
+            output,
+            "move {}",
+            uci::to_long_algebraic(start, end, promotion_piece.as_deref())
+        )?;
+        self.write_result(output)
+    }
+
+    /// How long the engine thinks as the color.  st and sd come first, then a share of what time
+    /// said was left.  The moves left in a level with a move count aren't kept, so the engine
+    /// thinks as though the whole count were still to go
+    pub fn search_limit(&self, color: &PieceColor) -> SearchLimit {
+        let clock = self.engine_time.map(|engine_time| {
+            let time_control = self
+                .time_control
+                .clone()
+                .unwrap_or_else(|| TimeControl::sudden_death(engine_time));
+            let mut clock = Clock::new(time_control);
+            clock.set_remaining(color, engine_time);
+            clock
+        });
+        Player::Engine { limit: self.limit }.search_limit(clock.as_ref(), color)
+    }
+
+    // 1-0 {White wins by checkmate} once the game is over
+    fn write_result(&self, output: &mut impl Write) -> io::Result<()> {
+        match self.game.outcome() {
+            Some(outcome) => writeln!(
+                output,
+                "{} {{{}}}",
+                outcome.result.to_pgn_string(),
+                outcome.reason()
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Runs chess xboard.  Commands are read until quit or the end of the input
+pub fn run_xboard(input: impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    let mut session = XboardSession::new();
+    for line in input.lines() {
+        let line = line?;
+        if line.trim() == "quit" {
+            break;
+        }
+        session.command(&line, output)?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+// level 40 5 0 is 40 moves in 5 minutes and level 0 2:30 3 is 2 1/2 minutes plus 3 seconds a move
+fn parse_level(argument: &str) -> Option<TimeControl> {
+    let (moves, base_time, increment) = match argument.split_whitespace().collect::<Vec<_>>()[..] {
+        [moves, base_time, increment] => (moves, base_time, increment),
+        _ => return None,
+    };
+    let moves: u32 = moves.parse().ok()?;
+    let base_seconds = match base_time.split_once(':') {
+        Some((minutes, seconds)) => {
+            minutes.parse::<u64>().ok()? * 60 + seconds.parse::<u64>().ok()?
+        }
+        None => base_time.parse::<u64>().ok()? * 60,
+    };
+    let increment = Duration::try_from_secs_f64(increment.parse().ok()?).ok()?;
+    Some(TimeControl {
+        stages: vec![TimeStage::new(
+            (moves > 0).then_some(moves),
+            Duration::from_secs(base_seconds),
+            increment,
+        )],
+        delay_kind: match increment.is_zero() {
+            true => DelayKind::SuddenDeath,
+            false => DelayKind::Fischer,
+        },
+    })
+}
+
+// ply score time nodes pv, with the time in centiseconds
+fn thinking_line(progress: &SearchProgress, elapsed: Duration) -> String {
+    let search_result = &progress.search_result;
+    let score = match search_result.score {
+        Some(Score::Centipawns(centipawns)) => centipawns,
+        Some(Score::Mate(moves)) if moves > 0 => XBOARD_MATE + moves,
+        Some(Score::Mate(moves)) => -XBOARD_MATE + moves,
+        None => 0,
+    };
+    let (start, end, promotion_piece) = &search_result.best_move;
+    format!(
+        "{} {} {} {} {}",
+        progress.depth,
+        score,
+        elapsed.as_millis() / 10,
+        progress.nodes,
+        uci::to_long_algebraic(*start, *end, promotion_piece.as_deref())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::{DelayKind, TimeControl, TimeStage};
+    use crate::piece_types::PieceColor;
+    use crate::uci::SearchLimit;
+    use crate::xboard::{parse_level, XboardSession};
+    use std::time::Duration;
+
+    // Sends the commands one after the other and returns everything the engine answered
+    fn send(session: &mut XboardSession, commands: &[&str]) -> String {
+        let mut output = Vec::new();
+        for command in commands {
+            session.command(command, &mut output).unwrap();
+        }
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_handshake() {
+        let mut session = XboardSession::new();
+        let output = send(&mut session, &["xboard", "protover 2", "ping 7", "hello"]);
+        assert!(output.starts_with("feature "));
+        assert!(output.contains("usermove=1"));
+        assert!(output.contains("setboard=1"));
+        assert!(output.contains("done=1\npong 7\nError (unknown command): hello\n"));
+    }
+
+    #[test]
+    fn test_engine_answers_moves() {
+        let mut session = XboardSession::new();
+        let output = send(&mut session, &["new", "sd 1", "usermove e2e4"]);
+        assert!(output.starts_with("move "));
+        assert_eq!(session.board().fullmove_number, 2);
+
+        let output = send(&mut session, &["usermove e2e5", "e7e5"]);
+        assert_eq!(output, "Illegal move: e2e5\nIllegal move: e7e5\n");
+    }
+
+    #[test]
+    fn test_force_and_go() {
+        let mut session = XboardSession::new();
+        let output = send(
+            &mut session,
+            &["new", "force", "usermove e2e4", "usermove e7e5", "sd 1"],
+        );
+        assert_eq!(output, "");
+        let output = send(&mut session, &["go"]);
+        assert!(output.starts_with("move "));
+        // The engine has White now so it doesn't answer Black's move
+        let output = send(&mut session, &["usermove a7a6"]);
+        assert!(output.starts_with("move "));
+        assert_eq!(session.board().fullmove_number, 3);
+    }
+
+    #[test]
+    fn test_setboard_mate_and_result() {
+        let mut session = XboardSession::new();
+        let output = send(
+            &mut session,
+            &[
+                "setboard 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+                "sd 2",
+                "post",
+                "go",
+            ],
+        );
+        assert!(output.contains("\n2 100001 "));
+        assert!(output.ends_with("move a1a8\n1-0 {White wins by checkmate}\n"));
+
+        let output = send(&mut session, &["setboard 8/8/8/8"]);
+        assert!(output.starts_with("tellusererror Illegal position"));
+    }
+
+    #[test]
+    fn test_undo_and_remove() {
+        let mut session = XboardSession::new();
+        send(
+            &mut session,
+            &["force", "e2e4", "e7e5", "g1f3", "undo", "remove"],
+        );
+        assert_eq!(
+            session.board().to_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        let output = send(&mut session, &["undo"]);
+        assert!(output.starts_with("Error (There are only 0 moves to take back)"));
+    }
+
+    #[test]
+    fn test_think_time_from_the_clock() {
+        let mut session = XboardSession::new();
+        assert_eq!(
+            session.search_limit(&PieceColor::BLACK),
+            SearchLimit::MoveTime(Duration::from_secs(1))
+        );
+        send(&mut session, &["level 40 5 0", "time 30000", "otim 100"]);
+        assert_eq!(
+            session.search_limit(&PieceColor::BLACK),
+            SearchLimit::MoveTime(Duration::from_millis(7500))
+        );
+        send(&mut session, &["st 2"]);
+        assert_eq!(
+            session.search_limit(&PieceColor::BLACK),
+            SearchLimit::MoveTime(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(
+            parse_level("0 2:30 3"),
+            Some(TimeControl {
+                stages: vec![TimeStage::new(
+                    None,
+                    Duration::from_secs(150),
+                    Duration::from_secs(3)
+                )],
+                delay_kind: DelayKind::Fischer,
+            })
+        );
+        assert_eq!(
+            parse_level("40 5 0"),
+            Some(TimeControl {
+                stages: vec![TimeStage::new(
+                    Some(40),
+                    Duration::from_secs(300),
+                    Duration::ZERO
+                )],
+                delay_kind: DelayKind::SuddenDeath,
+            })
+        );
+        assert_eq!(parse_level("40 5"), None);
+    }
+}
@@ -0,0 +1,102 @@
+use chess::board::Board;
+use chess::chess_game::Game;
+use chess::controller::Termination;
+use chess::game;
+use chess::parser::GameResult;
+use chess::piece_types::PieceColor;
+use chess::player::Players;
+use chess::variant::Variant;
+
+fn antichess_board(fen: &str) -> Board {
+    let mut board = Board::from_fen(fen).unwrap();
+    board.set_variant(Variant::Antichess);
+    board
+}
+
+fn play(game: &mut Game, move_string: &str) {
+    let (start, end, promotion_piece) = game.resolve_move(move_string).unwrap();
+    game.submit_move(start, end, promotion_piece.as_deref())
+        .unwrap();
+}
+
+#[test]
+fn test_captures_are_compulsory() {
+    let mut board = Board::new_variant(Variant::Antichess);
+    assert!(game::player_move(&mut board, "e3").is_ok());
+    assert!(game::player_move(&mut board, "b5").is_ok());
+    // The bishop can take on b5 so nothing else may be played
+    assert_eq!(
+        game::legal_moves(&PieceColor::WHITE, &mut board),
+        vec![((5, 0), (1, 4))]
+    );
+    assert!(game::player_move(&mut board, "a3").is_err());
+    assert!(game::player_move(&mut board, "Bxb5").is_ok());
+}
+
+#[test]
+fn test_king_has_no_check() {
+    // The rook stares at the king and the king walks next to it
+    let mut board = antichess_board("4k3/8/8/8/8/8/8/4R2K b - - 0 1");
+    assert!(!game::is_board_in_check(&PieceColor::WHITE, &board));
+    assert!(game::player_move(&mut board, "Kd8").is_ok());
+    assert!(game::player_move(&mut board, "Rd1").is_ok());
+    assert!(game::player_move(&mut board, "Ke8").is_ok());
+
+    let mut game = Game::from_board(
+        antichess_board("4k3/8/8/8/8/8/8/4R2K w - - 0 1"),
+        Players::default(),
+        None,
+    );
+    play(&mut game, "Rxe8");
+    assert_eq!(game.moves()[0].san, "Rxe8");
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::BlackWin);
+    assert_eq!(outcome.termination, Termination::NoPiecesLeft);
+    assert_eq!(outcome.to_string(), "Black has lost every piece. 0-1");
+}
+
+#[test]
+fn test_promote_to_king() {
+    let mut board = antichess_board("8/P7/8/8/8/8/8/7k w - - 0 1");
+    assert!(game::player_move(&mut board, "a8=K").is_ok());
+    assert_eq!(board.to_fen(), "K7/8/8/8/8/8/8/7k b - - 0 1");
+
+    let mut board = Board::from_fen("8/P7/8/8/8/8/8/k6K w - - 0 1").unwrap();
+    assert!(game::player_move(&mut board, "a8=K").is_err());
+}
+
+#[test]
+fn test_stalemate_wins() {
+    let board = antichess_board("8/8/8/8/8/p7/P7/8 w - - 0 1");
+    assert_eq!(
+        Variant::Antichess.winner(&board),
+        Some((PieceColor::WHITE, Termination::NoMovesLeft))
+    );
+
+    let mut game = Game::from_board(
+        antichess_board("8/8/8/8/p7/8/P7/8 w - - 0 1"),
+        Players::default(),
+        None,
+    );
+    play(&mut game, "a3");
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::BlackWin);
+    assert_eq!(outcome.to_string(), "Black has no moves left. 0-1");
+}
+
+#[test]
+fn test_antichess_perft() {
+    let board = Board::new_variant(Variant::Antichess);
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1"
+    );
+    assert_eq!(game::perft(&board, 3), 8067);
+    let board = antichess_board("8/1p6/8/8/8/8/P7/8 w - - 0 1");
+    for (depth, count) in [2, 4, 4, 3, 1].into_iter().enumerate() {
+        assert_eq!(game::perft(&board, depth as u32 + 1), count);
+    }
+    // Five promotions counting the king
+    let board = antichess_board("8/8/8/8/8/8/p7/7K b - - 0 1");
+    assert_eq!(game::perft(&board, 1), 5);
+}
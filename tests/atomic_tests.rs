@@ -0,0 +1,80 @@
+use chess::board::Board;
+use chess::chess_game::Game;
+use chess::controller::Termination;
+use chess::game;
+use chess::parser::GameResult;
+use chess::piece_types::PieceColor;
+use chess::player::Players;
+use chess::variant::Variant;
+
+fn atomic_board(fen: &str) -> Board {
+    let mut board = Board::from_fen(fen).unwrap();
+    board.set_variant(Variant::Atomic);
+    board
+}
+
+fn play(game: &mut Game, move_string: &str) {
+    let (start, end, promotion_piece) = game.resolve_move(move_string).unwrap();
+    game.submit_move(start, end, promotion_piece.as_deref())
+        .unwrap();
+}
+
+#[test]
+fn test_capture_explodes() {
+    let mut board = atomic_board("4k3/8/2npb3/3r4/8/8/3R4/4K3 w - - 0 1");
+    game::player_move(&mut board, "Rxd5").unwrap();
+    // The rooks, the knight and the bishop go.  The pawn next to them stays
+    assert_eq!(board.to_fen(), "4k3/8/3p4/8/8/8/8/4K3 b - - 0 1");
+}
+
+#[test]
+fn test_kings_cant_capture() {
+    let mut board = atomic_board("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1");
+    assert!(game::player_move(&mut board, "Kxd2").is_err());
+    assert!(!game::legal_moves(&PieceColor::WHITE, &mut board).contains(&((4, 0), (3, 1))));
+}
+
+#[test]
+fn test_touching_kings_are_not_in_check() {
+    let mut board = atomic_board("8/8/8/8/8/8/3k4/r2K4 w - - 0 1");
+    // The rook can't take the king without the black king going too
+    assert!(!game::is_board_in_check(&PieceColor::BLACK, &board));
+    // and the white king may step next to the black one out of the rook's way
+    assert!(game::player_move(&mut board, "Ke2").is_ok());
+}
+
+#[test]
+fn test_a_capture_cant_blow_up_your_own_king() {
+    let mut board = atomic_board("4k3/8/8/8/8/8/3p4/3QK3 w - - 0 1");
+    assert!(game::player_move(&mut board, "Qxd2").is_err());
+}
+
+#[test]
+fn test_blowing_up_the_king_wins() {
+    let mut game = Game::from_board(
+        Board::new_variant(Variant::Atomic),
+        Players::default(),
+        None,
+    );
+    for move_string in ["Nf3", "e6", "Ng5", "a6"] {
+        play(&mut game, move_string);
+    }
+    assert!(!game.is_over());
+    play(&mut game, "Nxf7");
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::WhiteWin);
+    assert_eq!(outcome.termination, Termination::Explosion);
+    assert!(!game.board().has_king(&PieceColor::BLACK));
+}
+
+#[test]
+fn test_atomic_perft() {
+    let board = Board::new_variant(Variant::Atomic);
+    assert_eq!(game::perft(&board, 3), 8902);
+    let board = atomic_board("rn2kb1r/1pp1p2p/p2q1pp1/3P4/2P3b1/4PN2/PP3PPP/R2QKB1R b KQkq - 0 1");
+    assert_eq!(game::perft(&board, 1), 40);
+    assert_eq!(game::perft(&board, 2), 1238);
+    let board = atomic_board("rn1qkb1r/p5pp/2p5/3p4/N3P3/5P2/PPP4P/R1BQK3 w Qkq - 0 1");
+    assert_eq!(game::perft(&board, 1), 28);
+    assert_eq!(game::perft(&board, 2), 833);
+}
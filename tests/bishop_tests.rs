@@ -8,9 +8,8 @@ fn test_can_move_no_where() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let bishop = Bishop::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
-        bishop.can_move(4, 4, &board),
-        false,
+    assert!(
+        !bishop.can_move(4, 4, &board),
         "Expected to not be able to move to {}, {} from {}, {}",
         4,
         4,
@@ -26,36 +25,32 @@ fn test_can_move_lower_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let bishop = Bishop::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         bishop.can_move(3, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(2, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         6,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(1, 7, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         1,
         7,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(0, 8, &board),
-        false,
+    assert!(
+        !bishop.can_move(0, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         0,
         8,
@@ -70,36 +65,32 @@ fn test_can_move_upper_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let bishop = Bishop::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         bishop.can_move(5, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(6, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         6,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(7, 7, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         7,
         7,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(8, 8, &board),
-        false,
+    assert!(
+        !bishop.can_move(8, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         8,
         8,
@@ -115,36 +106,32 @@ fn test_can_move_upper_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let bishop = Bishop::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         bishop.can_move(5, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(6, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(7, 1, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         7,
         1,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(8, 0, &board),
-        false,
+    assert!(
+        !bishop.can_move(8, 0, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         8,
         0,
@@ -160,36 +147,32 @@ fn test_can_move_lower_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let bishop = Bishop::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         bishop.can_move(3, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(2, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(1, 1, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         1,
         1,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(0, 0, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         0,
         0,
@@ -209,36 +192,32 @@ fn test_can_move_through_piece() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 3, 5, &mut board);
 
-    assert_eq!(
-        bishop.can_move(6, 6, &board),
-        false,
+    assert!(
+        !bishop.can_move(6, 6, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         6,
         6,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(2, 2, &board),
-        false,
+    assert!(
+        !bishop.can_move(2, 2, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(6, 2, &board),
-        false,
+    assert!(
+        !bishop.can_move(6, 2, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         6,
         2,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(2, 6, &board),
-        false,
+    assert!(
+        !bishop.can_move(2, 6, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         2,
         6,
@@ -252,36 +231,32 @@ fn test_can_move_through_piece() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 5, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 3, 5, &mut board);
 
-    assert_eq!(
-        bishop.can_move(6, 6, &board),
-        false,
+    assert!(
+        !bishop.can_move(6, 6, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         6,
         6,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(2, 2, &board),
-        false,
+    assert!(
+        !bishop.can_move(2, 2, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(6, 2, &board),
-        false,
+    assert!(
+        !bishop.can_move(6, 2, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         6,
         2,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(2, 6, &board),
-        false,
+    assert!(
+        !bishop.can_move(2, 6, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         2,
         6,
@@ -301,36 +276,32 @@ fn test_can_move_capture_same_color() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 3, 5, &mut board);
 
     // @TODO This should be replaced with an actual insertion function.  probably when I add the actual function impls to board
-    assert_eq!(
-        bishop.can_move(5, 5, &board),
-        false,
+    assert!(
+        !bishop.can_move(5, 5, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         5,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(3, 3, &board),
-        false,
+    assert!(
+        !bishop.can_move(3, 3, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(5, 3, &board),
-        false,
+    assert!(
+        !bishop.can_move(5, 3, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         3,
         4,
         4
     );
-    assert_eq!(
-        bishop.can_move(3, 5, &board),
-        false,
+    assert!(
+        !bishop.can_move(3, 5, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         3,
         5,
@@ -348,36 +319,32 @@ fn test_can_move_capture_opposing_color() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 3, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 3, 5, &mut board);
-    assert_eq!(
+    assert!(
         bishop.can_move(5, 5, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         5,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(3, 3, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(5, 3, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         5,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         bishop.can_move(3, 5, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         3,
         5,
@@ -409,7 +376,7 @@ fn test_moves_on_board_4_4() {
         (7, 1),
     ];
     expected_possible_moves.sort();
-    let mut actual_possible_moves = bishop.moves_on_board();
+    let mut actual_possible_moves = bishop.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -424,7 +391,7 @@ fn test_moves_on_board_0_0() {
 
     let mut expected_possible_moves: Vec<(usize, usize)> = (1..8).zip(1..8).collect();
     expected_possible_moves.sort();
-    let mut actual_possible_moves = bishop.moves_on_board();
+    let mut actual_possible_moves = bishop.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -439,7 +406,7 @@ fn test_moves_on_board_0_7() {
 
     let mut expected_possible_moves: Vec<(usize, usize)> = (1..8).zip((0..7).rev()).collect();
     expected_possible_moves.sort();
-    let mut actual_possible_moves = bishop.moves_on_board();
+    let mut actual_possible_moves = bishop.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -454,7 +421,7 @@ fn test_moves_on_board_7_0() {
 
     let mut expected_possible_moves: Vec<(usize, usize)> = (0..7).rev().zip(1..8).collect();
     expected_possible_moves.sort();
-    let mut actual_possible_moves = bishop.moves_on_board();
+    let mut actual_possible_moves = bishop.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -469,7 +436,7 @@ fn test_moves_on_board_7_7() {
 
     let mut expected_possible_moves: Vec<(usize, usize)> = (0..7).rev().zip((0..7).rev()).collect();
     expected_possible_moves.sort();
-    let mut actual_possible_moves = bishop.moves_on_board();
+    let mut actual_possible_moves = bishop.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -0,0 +1,101 @@
+use chess::board::Board;
+use chess::game;
+use chess::pieces::fairy::FairyPieceType;
+use chess::san;
+
+const CAPABLANCA_FEN: &str = "rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1";
+const GARDNER_FEN: &str = "rnbqk/ppppp/5/PPPPP/RNBQK w - - 0 1";
+const LOS_ALAMOS_FEN: &str = "rnqknr/pppppp/6/6/PPPPPP/RNQKNR w - - 0 1";
+
+fn capablanca_board(fen: &str) -> Board {
+    Board::from_fen_with_pieces(
+        fen,
+        &[FairyPieceType::archbishop(), FairyPieceType::chancellor()],
+    )
+    .unwrap()
+}
+
+fn play(board: &mut Board, move_string: &str) {
+    let (start, end, promotion_piece) = game::resolve_move(board, move_string).unwrap();
+    board
+        .play_move_from_coords(start, end, promotion_piece.as_deref())
+        .unwrap();
+}
+
+#[test]
+fn test_board_sizes_from_fen() {
+    let board = capablanca_board(CAPABLANCA_FEN);
+    assert_eq!((board.width(), board.height()), (10, 8));
+    assert_eq!(board.to_fen(), CAPABLANCA_FEN);
+
+    let board = Board::from_fen(GARDNER_FEN).unwrap();
+    assert_eq!((board.width(), board.height()), (5, 5));
+    assert_eq!(board.to_fen(), GARDNER_FEN);
+
+    assert!(Board::empty(0, 8).is_err());
+    assert!(Board::empty(17, 8).is_err());
+    assert_eq!(
+        Board::empty(6, 6).unwrap().to_fen(),
+        "6/6/6/6/6/6 w - - 0 1"
+    );
+}
+
+#[test]
+fn test_small_board_move_counts() {
+    // Five pawn steps and two knight moves, pawns can't move two squares on a small board
+    let board = Board::from_fen(GARDNER_FEN).unwrap();
+    assert_eq!(game::perft(&board, 1), 7);
+    assert_eq!(game::perft(&board, 2), 53);
+    let board = Board::from_fen(LOS_ALAMOS_FEN).unwrap();
+    assert_eq!(game::perft(&board, 1), 10);
+    assert!(game::player_move(&mut board.clone(), "a4").is_err());
+}
+
+#[test]
+fn test_capablanca_moves() {
+    // 20 pawn moves, 4 knight moves and 4 archbishop moves
+    let board = capablanca_board(CAPABLANCA_FEN);
+    assert_eq!(game::perft(&board, 1), 28);
+
+    let mut board = capablanca_board(CAPABLANCA_FEN);
+    for move_string in [
+        "j4", "a5", "j5", "a4", "Rj4", "Ra5", "Rb4", "Rh5", "Rb3", "Rh3",
+    ] {
+        play(&mut board, move_string);
+    }
+    play(&mut board, "ixh3");
+    assert_eq!(
+        board.to_fen(),
+        "1nabqkbcnr/1ppppppppp/10/9P/p9/1R5P2/PPPPPPPP2/RNABQKBCN1 b Qk - 0 6"
+    );
+}
+
+#[test]
+fn test_capablanca_castling() {
+    let mut board = capablanca_board("r4k3r/10/10/10/10/10/10/R4K3R w KQkq - 0 1");
+    assert_eq!(san::move_to_san(&board, (5, 0), (8, 0), None), "O-O");
+    play(&mut board, "O-O");
+    play(&mut board, "O-O-O");
+    assert_eq!(board.to_fen(), "2kr5r/10/10/10/10/10/10/R6RK1 w - - 2 2");
+
+    // The king can't go over an attacked square on its way to the i file
+    let mut board = capablanca_board("r4k3r/10/10/10/10/10/7r2/R4K3R w KQ - 0 1");
+    assert!(game::player_move(&mut board, "O-O").is_err());
+    assert!(game::player_move(&mut board, "O-O-O").is_ok());
+}
+
+#[test]
+fn test_notation_past_h_and_9() {
+    let mut board = Board::from_fen("9k/10/10/10/10/10/10/10/10/R8K w - - 0 1").unwrap();
+    assert_eq!(san::move_to_san(&board, (0, 0), (0, 9), None), "Ra10+");
+    play(&mut board, "Ra10+");
+    play(&mut board, "Kj9");
+    play(&mut board, "Rj10+");
+    assert!(board.to_fen().starts_with("9R/9k/"));
+
+    // A pawn promotes on the last rank of a small board
+    let mut board = Board::from_fen("k5/4P1/6/6/6/K5 w - - 0 1").unwrap();
+    assert!(game::player_move(&mut board.clone(), "e6").is_err());
+    play(&mut board, "e6=Q+");
+    assert_eq!(board.to_fen(), "k3Q1/6/6/6/6/K5 b - - 0 1");
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::process::Command;
+
+// Runs chess play with the lines as a script and returns what it printed
+fn run_script(name: &str, lines: &[&str]) -> String {
+    run_script_with_args(name, lines, &[])
+}
+
+fn run_script_with_args(name: &str, lines: &[&str], args: &[&str]) -> String {
+    let path = std::env::temp_dir().join(format!("chess_cli_script_{}", name));
+    fs::write(&path, lines.join("\n")).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_chess"))
+        .args(["play", "--script", path.to_str().unwrap()])
+        .args(args)
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_script_plays_to_checkmate() {
+    let output = run_script(
+        "mate",
+        &["# fool's mate", "f3", "e5", "g4", "Qh4#", "pgn", "e4"],
+    );
+    assert!(output.contains("Black wins by checkmate. 0-1"));
+    assert!(output.contains("1. f3 e5 2. g4 Qh4# 0-1"));
+    assert!(output.contains("The game is over (0-1)"));
+}
+
+#[test]
+fn test_script_commands() {
+    let output = run_script(
+        "commands",
+        &["e4", "undo", "fen", "Bxprint", "offer tea", "resign"],
+    );
+    assert!(output.contains("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"));
+    assert!(output.contains("Did you mean offer draw?"));
+    assert!(output.contains("White resigns. 0-1"));
+}
+
+#[test]
+fn test_script_draw_offers() {
+    let output = run_script(
+        "draw",
+        &[
+            "offer draw",
+            "e4",
+            "e5",
+            "offer draw",
+            "withdraw",
+            "accept",
+            "offer draw",
+            "Nf3",
+            "accept draw",
+            "pgn",
+        ],
+    );
+    assert!(!output.contains("WHITE declines the draw"));
+    assert!(output.contains("BLACK declines the draw"));
+    assert!(output.contains("BLACK hasn't offered a draw"));
+    assert!(output.contains("The players agreed to a draw. 1/2-1/2"));
+    assert!(output.contains("1. e4 e5 2. Nf3 1/2-1/2"));
+}
+
+#[test]
+fn test_script_en_passant_output() {
+    let output = run_script("en_passant", &["e4", "Nf6", "e5", "d5", "exd6", "pgn"]);
+    assert!(output.contains("1. e4 Nf6 2. e5 d5 3. exd6"));
+    // Nothing but the board, the moves and the PGN gets printed
+    for debug_line in ["break", "validEn", "removing the piece"] {
+        assert!(!output.contains(debug_line));
+    }
+}
+
+#[test]
+fn test_script_against_the_built_in_engine() {
+    let pgn_path = std::env::temp_dir().join("chess_cli_back_rank.pgn");
+    fs::write(
+        &pgn_path,
+        "[SetUp \"1\"]\n[FEN \"6k1/2p2ppp/8/8/8/8/8/R5K1 b - - 0 1\"]\n\n1... c6 *\n",
+    )
+    .unwrap();
+    let load = format!("load {}", pgn_path.display());
+    let output = run_script_with_args(
+        "built_in_engine",
+        &[&load, "pgn"],
+        &["--white", "engine:depth=2"],
+    );
+    assert!(output.contains("Built-in engine plays Ra8#"));
+    assert!(output.contains("1... c6 2. Ra8# 1-0"));
+}
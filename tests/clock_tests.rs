@@ -0,0 +1,67 @@
+use chess::board::Board;
+use chess::clock::{Clock, TimeControl};
+use chess::game;
+use chess::parser::GameResult;
+use chess::piece_types::{PieceColor, QuickPiece};
+use chess::pieces::king::King;
+use chess::pieces::knight::Knight;
+use chess::pieces::AnyPiece;
+use chess::variant::Variant;
+use std::collections::HashMap;
+use std::time::Duration;
+
+mod common;
+
+#[test]
+fn test_flag_is_loss_on_time() {
+    let board = Board::new();
+    let mut clock = Clock::new(TimeControl::sudden_death(Duration::from_secs(5)));
+    clock.start(PieceColor::WHITE);
+    assert_eq!(game::result_on_time(&clock, &board), None);
+
+    clock.tick(Duration::from_secs(6));
+    assert_eq!(
+        game::result_on_time(&clock, &board),
+        Some(GameResult::BlackWin)
+    );
+}
+
+#[test]
+fn test_flag_against_lone_knight_is_draw() {
+    let mut board = Board {
+        position_board: common::create_board_with_piece(0, 0, QuickPiece::KING(PieceColor::WHITE)),
+        live_white_pieces: vec![AnyPiece::King(King::new(0, 0, PieceColor::WHITE))],
+        live_black_pieces: vec![],
+        white_king_position: (0, 0),
+        black_king_position: (7, 7),
+        last_move_color: PieceColor::BLACK,
+        played_moves: Vec::new(),
+        board_state_hashes: HashMap::new(),
+        variant: Variant::Standard,
+        pockets: None,
+        promoted_squares: Vec::new(),
+        check_counts: (0, 0),
+        halfmove_clock: 0,
+        fullmove_number: 1,
+        fairy_pieces: Vec::new(),
+    };
+    common::insert_piece_into_board(
+        AnyPiece::King(King::new(7, 7, PieceColor::BLACK)),
+        &PieceColor::BLACK,
+        7,
+        7,
+        &mut board,
+    );
+    common::insert_piece_into_board(
+        AnyPiece::Knight(Knight::new(5, 5, PieceColor::BLACK)),
+        &PieceColor::BLACK,
+        5,
+        5,
+        &mut board,
+    );
+
+    let mut clock = Clock::new(TimeControl::sudden_death(Duration::from_secs(1)));
+    clock.start(PieceColor::WHITE);
+    clock.tick(Duration::from_secs(2));
+    assert_eq!(game::result_on_time(&clock, &board), Some(GameResult::Draw));
+}
@@ -22,12 +22,11 @@ pub fn insert_piece_into_board(
         _ => QuickPiece::PIECE(owned_piece_color),
     };
 
-    match &quick_piece {
-        QuickPiece::KING(color) => match color {
+    if let QuickPiece::KING(color) = &quick_piece {
+        match color {
             PieceColor::WHITE => board.white_king_position = (x_coord, y_coord),
             PieceColor::BLACK => board.black_king_position = (x_coord, y_coord),
-        },
-        _ => (),
+        }
     }
 
     match piece_color {
@@ -53,7 +52,7 @@ pub fn insert_quick_piece_into_board(
     piece: QuickPiece,
     x_coord: usize,
     y_coord: usize,
-    board: &mut Vec<Vec<QuickPiece>>,
+    board: &mut [Vec<QuickPiece>],
 ) {
     board.get_mut(x_coord).unwrap().remove(y_coord);
     board.get_mut(x_coord).unwrap().insert(y_coord, piece);
@@ -106,6 +105,58 @@ pub fn create_board_with_piece(
     board
 }
 
+/// Writes a pretend UCI engine script to the temp directory.  It answers the handshake, says
+/// whatever is in options before uciok and runs go_reply for every go, which can look at the last
+/// position command in $position.  Every line it is sent goes to the log_path file next to it
+#[cfg(unix)]
+#[allow(dead_code)]
+pub fn write_engine(name: &str, options: &str, go_reply: &str) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = std::env::temp_dir().join(format!("chess_fake_engine_{}", name));
+    let script = format!(
+        "#!/bin/sh
+: > {log}
+while read line; do
+  echo \"$line\" >> {log}
+  case \"$line\" in
+    uci) echo 'id name Fake {}'; {}echo 'uciok' ;;
+    isready) echo 'readyok' ;;
+    position*) position=\"$line\" ;;
+    go*) {} ;;
+    quit) exit 0 ;;
+  esac
+done
+",
+        name,
+        options,
+        go_reply,
+        log = log_path(&path).display()
+    );
+    std::fs::write(&path, script).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+/// A pretend engine that always plays the same move
+#[cfg(unix)]
+#[allow(dead_code)]
+pub fn fake_engine(name: &str, best_move: &str) -> std::path::PathBuf {
+    write_engine(
+        name,
+        "",
+        &format!(
+            "echo 'info depth 1 score cp 0'; echo 'bestmove {}'",
+            best_move
+        ),
+    )
+}
+
+#[allow(dead_code)]
+pub fn log_path(engine_path: &std::path::Path) -> std::path::PathBuf {
+    engine_path.with_extension("log")
+}
+
 #[cfg(test)]
 mod tests {
     use super::create_board_with_piece;
@@ -119,18 +170,18 @@ mod tests {
         for _ in 0..8 {
             assert_eq!(board.len(), 8)
         }
-        let piece = board.get(0).unwrap().get(0).unwrap();
+        let piece = board.first().unwrap().first().unwrap();
         //let expected_piece = QuickPiece::PIECE(PieceColor::WHITE);
 
         match piece {
             QuickPiece::PIECE(color) => match color {
-                PieceColor::WHITE => assert!(true),
+                PieceColor::WHITE => (),
                 PieceColor::BLACK => {
-                    assert!(false, "Expected Piece of Color WHITE, but found BLACK")
+                    panic!("Expected Piece of Color WHITE, but found BLACK")
                 }
             },
-            QuickPiece::EMPTY => assert!(false, "Expected Piece of color WHITE, but found EMPTY"),
-            QuickPiece::KING(_) => assert!(false, "Expected Piece of color WHITE, but found KING"),
+            QuickPiece::EMPTY => panic!("Expected Piece of color WHITE, but found EMPTY"),
+            QuickPiece::KING(_) => panic!("Expected Piece of color WHITE, but found KING"),
         }
     }
 
@@ -142,17 +193,16 @@ mod tests {
         for _ in 0..8 {
             assert_eq!(board.len(), 8)
         }
-        let piece = board.get(0).unwrap().get(0).unwrap();
+        let piece = board.first().unwrap().first().unwrap();
 
         match piece {
-            QuickPiece::PIECE(_) => assert!(false, "Expected KING of color BLACK, but found PIECE"),
-            QuickPiece::EMPTY => assert!(false, "Expected KING of color BLACK, but found EMPTY"),
+            QuickPiece::PIECE(_) => panic!("Expected KING of color BLACK, but found PIECE"),
+            QuickPiece::EMPTY => panic!("Expected KING of color BLACK, but found EMPTY"),
             QuickPiece::KING(color) => match color {
-                PieceColor::WHITE => assert!(
-                    false,
-                    "The color WHITE for the KING on the board is incorrect"
-                ),
-                PieceColor::BLACK => assert!(true),
+                PieceColor::WHITE => {
+                    panic!("The color WHITE for the KING on the board is incorrect")
+                }
+                PieceColor::BLACK => (),
             },
         }
     }
@@ -180,14 +230,13 @@ mod tests {
         let piece = board.get(7).unwrap().get(7).unwrap();
 
         match piece {
-            QuickPiece::PIECE(_) => assert!(false, "Expected KING of color BLACK, but found PIECE"),
-            QuickPiece::EMPTY => assert!(false, "Expected KING of color BLACK, but found EMPTY"),
+            QuickPiece::PIECE(_) => panic!("Expected KING of color BLACK, but found PIECE"),
+            QuickPiece::EMPTY => panic!("Expected KING of color BLACK, but found EMPTY"),
             QuickPiece::KING(color) => match color {
-                PieceColor::WHITE => assert!(
-                    false,
-                    "The color WHITE for the KING on the board is incorrect"
-                ),
-                PieceColor::BLACK => assert!(true),
+                PieceColor::WHITE => {
+                    panic!("The color WHITE for the KING on the board is incorrect")
+                }
+                PieceColor::BLACK => (),
             },
         }
     }
@@ -215,14 +264,13 @@ mod tests {
         let piece = board.get(3).unwrap().get(2).unwrap();
 
         match piece {
-            QuickPiece::PIECE(_) => assert!(false, "Expected KING of color BLACK, but found PIECE"),
-            QuickPiece::EMPTY => assert!(false, "Expected KING of color BLACK, but found EMPTY"),
+            QuickPiece::PIECE(_) => panic!("Expected KING of color BLACK, but found PIECE"),
+            QuickPiece::EMPTY => panic!("Expected KING of color BLACK, but found EMPTY"),
             QuickPiece::KING(color) => match color {
-                PieceColor::WHITE => assert!(
-                    false,
-                    "The color WHITE for the KING on the board is incorrect"
-                ),
-                PieceColor::BLACK => assert!(true),
+                PieceColor::WHITE => {
+                    panic!("The color WHITE for the KING on the board is incorrect")
+                }
+                PieceColor::BLACK => (),
             },
         }
     }
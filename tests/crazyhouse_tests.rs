@@ -0,0 +1,111 @@
+use chess::board::Board;
+use chess::chess_game::Game;
+use chess::game;
+use chess::pgn::{parse_pgn, PgnGame};
+use chess::piece_types::PieceColor;
+use chess::player::Players;
+use chess::san;
+use chess::variant::Variant;
+
+fn play(board: &mut Board, move_string: &str) {
+    let (start, end, promotion_piece) = game::resolve_move(board, move_string).unwrap();
+    board
+        .play_move_from_coords(start, end, promotion_piece.as_deref())
+        .unwrap();
+}
+
+#[test]
+fn test_captures_go_to_the_pocket() {
+    let mut board = Board::new_variant(Variant::Crazyhouse);
+    for move_string in ["e4", "d5", "exd5", "Qxd5"] {
+        play(&mut board, move_string);
+    }
+    let pockets = board.pockets.as_ref().unwrap();
+    assert_eq!(pockets.pieces(&PieceColor::WHITE), ["P"]);
+    assert_eq!(pockets.pieces(&PieceColor::BLACK), ["P"]);
+    assert_eq!(
+        board.to_fen(),
+        "rnb1kbnr/ppp1pppp/8/3q4/8/8/PPPP1PPP/RNBQKBNR[Pp] w KQkq - 0 3"
+    );
+
+    assert_eq!(san::move_to_san(&board, (4, 5), (4, 5), Some("P")), "P@e6");
+    play(&mut board, "P@e6");
+    assert!(board.pockets.as_ref().unwrap().is_empty(&PieceColor::WHITE));
+}
+
+#[test]
+fn test_drop_rules() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K2r[NPp] w - - 0 1").unwrap();
+    // White is in check along the first rank so only a block will do
+    assert!(game::player_move(&mut board, "N@a3").is_err());
+    assert!(game::player_move(&mut board, "P@f1").is_err());
+    assert!(game::player_move(&mut board, "Q@f1").is_err());
+    assert!(game::player_move(&mut board, "N@h1").is_err());
+    let drops = game::legal_drops(&PieceColor::WHITE, &mut board);
+    assert!(drops.contains(&(String::from("N"), (5, 0))));
+    assert!(!drops.iter().any(|(piece, _)| piece == "P"));
+    assert!(game::player_move(&mut board, "N@f1").is_ok());
+
+    let mut standard_board = Board::new();
+    assert!(game::player_move(&mut standard_board, "N@e4").is_err());
+}
+
+#[test]
+fn test_promoted_pieces_return_as_pawns() {
+    let mut board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3[] w - - 0 1").unwrap();
+    play(&mut board, "axb8=Q");
+    assert_eq!(board.to_fen(), "1Q~2k3/8/8/8/8/8/8/4K3[N] b - - 0 1");
+    play(&mut board, "Kd7");
+    play(&mut board, "Qb5+");
+    play(&mut board, "Kd8");
+    play(&mut board, "Qd3+");
+    play(&mut board, "Ke8");
+    play(&mut board, "Qd4");
+    // The ~ marker follows the queen around the board
+    assert_eq!(board.promoted_squares, [(3, 3)]);
+    assert!(board.to_fen().starts_with("4k3/8/8/8/3Q~4/8/8/4K3[N] b"));
+
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/r2Q~K3[] b - - 0 1").unwrap();
+    play(&mut board, "Rxd1+");
+    assert_eq!(
+        board.pockets.as_ref().unwrap().pieces(&PieceColor::BLACK),
+        ["P"]
+    );
+}
+
+#[test]
+fn test_drop_can_mate() {
+    // The king is boxed in by its own pieces and a dropped knight can't be taken
+    let mut game = Game::from_board(
+        Board::from_fen("6rk/6pp/8/8/8/8/8/4K3[N] w - - 0 1").unwrap(),
+        Players::default(),
+        None,
+    );
+    let (start, end, promotion_piece) = game.resolve_move("N@f7").unwrap();
+    game.submit_move(start, end, promotion_piece.as_deref())
+        .unwrap();
+    assert_eq!(game.moves()[0].san, "N@f7#");
+    assert!(game.is_over());
+}
+
+#[test]
+fn test_crazyhouse_pgn_round_trip() {
+    let mut board = Board::new_variant(Variant::Crazyhouse);
+    for move_string in ["e4", "d5", "exd5", "Qxd5", "P@e4"] {
+        play(&mut board, move_string);
+    }
+    let pgn_game = PgnGame::from_board(
+        &Board::new_variant(Variant::Crazyhouse),
+        &board,
+        "?",
+        "?",
+        None,
+    );
+    let pgn_text = pgn_game.to_pgn();
+    assert!(pgn_text.contains("[Variant \"Crazyhouse\"]"));
+    assert!(!pgn_text.contains("[FEN"));
+    assert!(pgn_text.contains("3. P@e4"));
+
+    let (loaded_board, _) = parse_pgn(&pgn_text).unwrap().replay().unwrap();
+    assert_eq!(loaded_board.to_fen(), board.to_fen());
+}
@@ -0,0 +1,83 @@
+#![cfg(unix)]
+
+mod common;
+
+use chess::epd;
+use chess::player::Player;
+use chess::uci::SearchLimit;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -";
+
+// A suite the e4 engine passes the first position of and fails the other two
+fn e4_suite(name: &str) -> (String, Player) {
+    let suite = [
+        format!("{} bm e4; id \"king pawn\";", START_FEN),
+        format!("{} am e4; id \"not e4\";", START_FEN),
+        format!("{} bm d4 Nf3;", START_FEN),
+    ];
+    let suite_path = std::env::temp_dir().join(format!("chess_epd_suite_{}.epd", name));
+    fs::write(&suite_path, suite.join("\n")).unwrap();
+    let engine = common::fake_engine(&format!("epd_{}", name), "e2e4");
+    (
+        suite_path.to_str().unwrap().to_string(),
+        Player::Uci {
+            path: engine.to_str().unwrap().to_string(),
+            limit: None,
+        },
+    )
+}
+
+#[test]
+fn test_engine_runs_suite() {
+    let (suite_path, player) = e4_suite("run");
+    assert_eq!(
+        epd::run_suite(&suite_path, &player, Some(SearchLimit::Depth(1))).unwrap(),
+        (1, 3)
+    );
+    assert!(epd::run_suite(&suite_path, &Player::Human, None).is_err());
+}
+
+#[test]
+fn test_built_in_engine_runs_suite() {
+    let suite_path = std::env::temp_dir().join("chess_epd_suite_built_in.epd");
+    fs::write(
+        &suite_path,
+        "6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id \"back rank\";",
+    )
+    .unwrap();
+    let player = Player::from_string("engine:depth=2").unwrap();
+    assert_eq!(
+        epd::run_suite(suite_path.to_str().unwrap(), &player, None).unwrap(),
+        (1, 1)
+    );
+}
+
+#[test]
+fn test_epd_command() {
+    let (suite_path, player) = e4_suite("command");
+    let engine_path = match player {
+        Player::Uci { path, .. } => path,
+        _ => unreachable!(),
+    };
+    let output = Command::new(env!("CARGO_BIN_EXE_chess"))
+        .args([
+            "epd",
+            &suite_path,
+            "--movetime",
+            "10",
+            "--engine",
+            &format!("uci:{}", engine_path),
+        ])
+        .output()
+        .unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(output.contains("king pawn: passed with e4 (bm e4)"));
+    assert!(output.contains("not e4: failed with e4 (am e4)"));
+    assert!(output.contains("3: failed with e4 (bm d4 Nf3)"));
+    assert!(output.contains("Passed 1 of 3"));
+    let log = fs::read_to_string(common::log_path(Path::new(&engine_path))).unwrap();
+    assert!(log.contains("go movetime 10"));
+}
@@ -0,0 +1,92 @@
+use chess::board::Board;
+use chess::epd::{parse_epd_line, parse_epd_suite};
+use chess::piece_types::{PieceColor, QuickPiece};
+
+#[test]
+fn test_start_position_fen_matches_new_board() {
+    let mut fen_board =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let mut new_board = Board::new();
+
+    assert_eq!(fen_board.position_board, new_board.position_board);
+    assert_eq!(fen_board.last_move_color, PieceColor::BLACK);
+    assert_eq!(fen_board.white_king_position, (4, 0));
+    assert_eq!(fen_board.black_king_position, (4, 7));
+    assert_eq!(fen_board.get_current_hash(), new_board.get_current_hash());
+}
+
+#[test]
+fn test_fen_en_passant_square_allows_capture() {
+    let mut board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2").unwrap();
+    assert_eq!(
+        board.position_board[3][4],
+        QuickPiece::PIECE(PieceColor::BLACK)
+    );
+
+    board
+        .play_move(chess::parser::parse_move("exd6").unwrap())
+        .unwrap();
+    assert_eq!(board.position_board[3][4], QuickPiece::EMPTY);
+    assert_eq!(board.live_black_pieces.len(), 1);
+}
+
+#[test]
+fn test_invalid_fen_is_an_error() {
+    // Ranks of different lengths and a board wider than 16 files
+    assert!(Board::from_fen("8/7/8 w - -").is_err());
+    assert!(Board::from_fen("17/17 w - -").is_err());
+    assert!(Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq -").is_err());
+    assert!(Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq -").is_err());
+
+    // Pawns on the back ranks, though Horde puts white pawns on the first
+    assert!(Board::from_fen("4k3/8/8/8/8/8/8/p3K3 b - - 0 1").is_err());
+    assert!(Board::from_fen("p3k3/8/8/8/8/8/8/4K3 w - - 0 1").is_err());
+    assert!(Board::from_fen("P3k3/8/8/8/8/8/8/4K3 b - - 0 1").is_err());
+    assert!(Board::from_fen("4k3/8/8/8/8/8/8/P3K3 w - - 0 1").is_ok());
+
+    // The en passant square has to be behind a pawn of the side that just moved
+    assert!(Board::from_fen("k7/8/8/8/8/8/8/7K w - e3").is_err());
+    assert!(Board::from_fen("k7/8/8/8/8/8/8/7K b - e6").is_err());
+    assert!(Board::from_fen("k7/8/8/8/4P3/8/8/7K b - e3").is_ok());
+}
+
+#[test]
+fn test_parse_epd_operations() {
+    let position = parse_epd_line(
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4rK1 w - - bm Qg6; id \"WAC.001\"; c0 \"mate; in 3\";",
+    )
+    .unwrap();
+
+    assert_eq!(
+        position.fen,
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4rK1 w - -"
+    );
+    assert_eq!(position.best_moves, vec!["Qg6".to_string()]);
+    assert_eq!(position.id, Some("WAC.001".to_string()));
+    assert_eq!(position.comment, Some("mate; in 3".to_string()));
+}
+
+#[test]
+fn test_best_and_avoid_moves() {
+    let position = parse_epd_line(
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4rK1 w - - bm Qg6; id \"WAC.001\";",
+    )
+    .unwrap();
+    assert!(position.is_solved_by("Qg6").unwrap());
+    assert!(position.is_solved_by("Qg3g6").unwrap());
+    assert!(!position.is_solved_by("Kxf1").unwrap());
+
+    let position =
+        parse_epd_line("4k3/8/8/8/8/8/4P3/4K2R w K - am O-O; id \"avoid castle\";").unwrap();
+    assert!(!position.is_solved_by("O-O").unwrap());
+    assert!(position.is_solved_by("e4").unwrap());
+}
+
+#[test]
+fn test_parse_suite_skips_comments() {
+    let suite = "# a small suite\n\n4k3/8/8/8/8/8/4P3/4K3 w - - bm e4;\nnot an epd line\n";
+    let positions = parse_epd_suite(suite);
+    assert_eq!(positions.len(), 2);
+    assert!(positions[0].is_ok());
+    assert!(positions[1].is_err());
+}
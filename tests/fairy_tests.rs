@@ -0,0 +1,165 @@
+mod common;
+use chess::board::Board;
+use chess::game;
+use chess::piece_types::{PieceColor, QuickPiece};
+use chess::pieces::fairy::FairyPieceType;
+use chess::pieces::{coord_on_board, PieceMove};
+use std::sync::Arc;
+
+fn fairy_board(fen: &str) -> Board {
+    Board::from_fen_with_pieces(
+        fen,
+        &[
+            FairyPieceType::archbishop(),
+            FairyPieceType::chancellor(),
+            FairyPieceType::amazon(),
+            FairyPieceType::camel(),
+            FairyPieceType::grasshopper(),
+        ],
+    )
+    .unwrap()
+}
+
+fn play(board: &mut Board, move_string: &str) {
+    let (start, end, promotion_piece) = game::resolve_move(board, move_string).unwrap();
+    board
+        .play_move_from_coords(start, end, promotion_piece.as_deref())
+        .unwrap();
+}
+
+// Moves one square straight, written by hand instead of with Betza
+#[derive(Clone, Debug)]
+struct Wazir {
+    pos_x: usize,
+    pos_y: usize,
+}
+
+impl PieceMove for Wazir {
+    fn can_move(&self, x_coord: usize, y_coord: usize, quick_board: &[Vec<QuickPiece>]) -> bool {
+        coord_on_board(x_coord, y_coord, quick_board)
+            && self
+                .moves_on_board(quick_board.len(), quick_board[0].len())
+                .contains(&(x_coord, y_coord))
+    }
+    fn moves_on_board(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let (x_coord, y_coord) = (self.pos_x as isize, self.pos_y as isize);
+        [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .iter()
+            .map(|(x_step, y_step)| (x_coord + x_step, y_coord + y_step))
+            .filter(|(x, y)| (0..width as isize).contains(x) && (0..height as isize).contains(y))
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect()
+    }
+    fn get_pos(&self) -> (usize, usize) {
+        (self.pos_x, self.pos_y)
+    }
+    fn set_pos(&mut self, x_coord: usize, y_coord: usize) {
+        self.pos_x = x_coord;
+        self.pos_y = y_coord;
+    }
+}
+
+#[test]
+fn test_archbishop_moves() {
+    let board = common::create_board_with_piece(3, 3, QuickPiece::PIECE(PieceColor::WHITE));
+    let archbishop = FairyPieceType::make_piece(
+        &Arc::new(FairyPieceType::archbishop()),
+        3,
+        3,
+        PieceColor::WHITE,
+    );
+    // 13 bishop squares and 8 knight squares from d4
+    assert_eq!(archbishop.moves_on_board(8, 8).len(), 21);
+    assert!(archbishop.can_move(7, 7, &board));
+    assert!(archbishop.can_move(4, 5, &board));
+    assert!(!archbishop.can_move(3, 7, &board));
+}
+
+#[test]
+fn test_grasshopper_hops() {
+    let board = fairy_board("4k3/8/4p3/8/8/8/4G3/4K3 w - - 0 1");
+    let grasshopper = board
+        .live_white_pieces
+        .iter()
+        .find(|piece| piece.get_pos() == (4, 1))
+        .unwrap();
+    // Lands just past the pawn on e6 and nowhere else up the file
+    assert!(grasshopper.can_move(4, 6, &board.position_board));
+    assert!(!grasshopper.can_move(4, 4, &board.position_board));
+    assert!(!grasshopper.can_move(4, 5, &board.position_board));
+    // Nothing to jump along the rank
+    assert!(!grasshopper.can_move(0, 1, &board.position_board));
+}
+
+#[test]
+fn test_piece_type_errors() {
+    assert!(FairyPieceType::from_betza('Q', "Queen", "Q").is_err());
+    assert!(FairyPieceType::from_betza('a', "Archbishop", "BN").is_err());
+    assert!(FairyPieceType::from_betza('X', "Nothing", "").is_err());
+    assert!(FairyPieceType::from_betza('X', "Unknown", "BY").is_err());
+    assert!(FairyPieceType::from_betza('X', "Modifier", "BNm").is_err());
+
+    let mut board = Board::new();
+    board.register_piece(FairyPieceType::archbishop()).unwrap();
+    assert!(board
+        .register_piece(FairyPieceType::from_betza('A', "Alfil", "A").unwrap())
+        .is_err());
+}
+
+#[test]
+fn test_custom_piece() {
+    let wazir = FairyPieceType::new('W', "Wazir", |pos_x, pos_y, _| Wazir { pos_x, pos_y })
+        .unwrap()
+        .with_images("WhiteWazir.png", "BlackWazir.png");
+    assert_eq!(wazir.image(&PieceColor::BLACK), "BlackWazir.png");
+
+    let mut board =
+        Board::from_fen_with_pieces("4k3/8/8/8/8/8/8/W3K3 w - - 0 1", &[wazir]).unwrap();
+    assert!(game::player_move(&mut board, "Wb2").is_err());
+    play(&mut board, "Wa2");
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/W7/4K3 b - - 1 1");
+}
+
+#[test]
+fn test_fairy_pieces_on_the_board() {
+    let fen = "r1a1k1cr/8/8/8/8/8/4P3/RMALKGCR w - - 0 1";
+    let mut board = fairy_board(fen);
+    assert_eq!(board.to_fen(), fen);
+    assert!(Board::from_fen(fen).is_err());
+
+    // The archbishop leaps like a knight or slides like a bishop and the camel goes three by one
+    play(&mut board, "Ab3");
+    play(&mut board, "Cf6");
+    play(&mut board, "Lc4");
+    play(&mut board, "Ah3");
+    assert_eq!(
+        board.to_fen(),
+        "r3k2r/8/5c2/8/2L5/1A5a/4P3/RM2KGCR w - - 4 3"
+    );
+    // The grasshopper has to land right behind the pawn it hops
+    assert!(game::player_move(&mut board, "Ge3").is_err());
+    assert!(game::player_move(&mut board, "Gd3").is_ok());
+}
+
+#[test]
+fn test_promote_to_fairy_piece() {
+    let mut board = fairy_board("3k4/P7/8/8/8/8/8/4K3 w - - 0 1");
+    assert!(board.promotion_pieces().contains(&"A"));
+    play(&mut board, "a8=A");
+    assert_eq!(board.to_fen(), "A2k4/8/8/8/8/8/8/4K3 b - - 0 1");
+    // The new archbishop covers c7 with its knight move
+    assert!(game::player_move(&mut board, "Kc7").is_err());
+    assert!(game::player_move(&mut board, "Kd7").is_ok());
+}
+
+#[test]
+fn test_later_betza_move_can_capture() {
+    // The queen move only moves, so the capture has to come from the king step after it
+    let mover = |betza: &str| FairyPieceType::from_betza('X', "Mover", betza).unwrap();
+    for betza in ["mQcK", "cKmQ"] {
+        let mut board =
+            Board::from_fen_with_pieces("4k3/8/8/8/3p4/3X4/8/4K3 w - - 0 1", &[mover(betza)])
+                .unwrap();
+        assert!(game::player_move(&mut board, "Xxd4").is_ok(), "{}", betza);
+    }
+}
@@ -0,0 +1,76 @@
+use chess::board::Board;
+use chess::chess_game::Game;
+use chess::controller::Termination;
+use chess::game;
+use chess::parser::GameResult;
+use chess::piece_types::PieceColor;
+use chess::player::Players;
+use chess::variant::Variant;
+
+fn horde_board(fen: &str) -> Board {
+    let mut board = Board::from_fen(fen).unwrap();
+    board.set_variant(Variant::Horde);
+    board
+}
+
+#[test]
+fn test_horde_start() {
+    let mut board = Board::new_variant(Variant::Horde);
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1"
+    );
+    assert_eq!(board.live_white_pieces.len(), 36);
+    assert_eq!(board.king_position(&PieceColor::WHITE), None);
+    assert_eq!(game::legal_moves(&PieceColor::WHITE, &mut board).len(), 8);
+}
+
+#[test]
+fn test_first_rank_pawns_move_two() {
+    let mut board = horde_board("4k3/8/8/8/8/8/8/4P3 w - - 0 1");
+    assert!(game::player_move(&mut board, "e3").is_ok());
+    // There's no en passant after a step from the first rank
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/4P3/8/8 b - - 0 1");
+
+    // but not through a piece
+    let mut board = horde_board("4k3/8/8/8/8/8/4p3/4P3 w - - 0 1");
+    assert!(game::player_move(&mut board, "e3").is_err());
+}
+
+#[test]
+fn test_first_rank_pawns_move_two_from_the_second_rank() {
+    let mut board = horde_board("4k3/8/8/8/8/8/8/P7 w - - 0 1");
+    for pawn_move in ["a2", "Kd8", "a4"] {
+        assert!(game::player_move(&mut board, pawn_move).is_ok());
+    }
+    // That was an ordinary double step so en passant works as usual
+    assert_eq!(board.to_fen(), "3k4/8/8/8/P7/8/8/8 b - a3 0 2");
+
+    // A pawn past the second rank only moves one
+    let mut board = horde_board("4k3/8/8/8/8/P7/8/8 w - - 0 1");
+    assert!(game::player_move(&mut board, "a5").is_err());
+}
+
+#[test]
+fn test_horde_wiped_out() {
+    let mut game = Game::from_board(
+        horde_board("4k3/8/8/8/8/8/3q4/4P3 b - - 0 1"),
+        Players::default(),
+        None,
+    );
+    let (start, end, promotion_piece) = game.resolve_move("Qxe1").unwrap();
+    game.submit_move(start, end, promotion_piece.as_deref())
+        .unwrap();
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::BlackWin);
+    assert_eq!(outcome.termination, Termination::HordeDestroyed);
+    assert_eq!(outcome.to_string(), "The horde is wiped out. 0-1");
+}
+
+#[test]
+fn test_horde_perft() {
+    let board = Board::new_variant(Variant::Horde);
+    for (depth, count) in [8, 128, 1274].into_iter().enumerate() {
+        assert_eq!(game::perft(&board, depth as u32 + 1), count);
+    }
+}
@@ -8,9 +8,8 @@ fn test_can_move_no_where() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let king = King::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
-        king.can_move(4, 4, &board),
-        false,
+    assert!(
+        !king.can_move(4, 4, &board),
         "Expected to not be able to move to {}, {} from {}, {}",
         4,
         4,
@@ -26,18 +25,16 @@ fn test_can_move_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let king = King::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         king.can_move(4, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
-        king.can_move(4, 6, &board),
-        false,
+    assert!(
+        !king.can_move(4, 6, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         6,
@@ -52,18 +49,16 @@ fn test_can_move_down() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let king = King::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         king.can_move(5, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
-        king.can_move(6, 4, &board),
-        false,
+    assert!(
+        !king.can_move(6, 4, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         6,
         4,
@@ -79,18 +74,16 @@ fn test_can_move_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let king = King::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         king.can_move(4, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         3,
         4,
         4
     );
-    assert_eq!(
-        king.can_move(4, 2, &board),
-        false,
+    assert!(
+        !king.can_move(4, 2, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         2,
@@ -106,18 +99,16 @@ fn test_can_move_up() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let king = King::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         king.can_move(3, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
-        king.can_move(2, 4, &board),
-        false,
+    assert!(
+        !king.can_move(2, 4, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         2,
         2,
@@ -136,36 +127,32 @@ fn test_can_move_capture_same_color() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
 
-    assert_eq!(
-        king.can_move(4, 5, &board),
-        false,
+    assert!(
+        !king.can_move(4, 5, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
-        king.can_move(3, 4, &board),
-        false,
+    assert!(
+        !king.can_move(3, 4, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         3,
         4,
         4,
         4
     );
-    assert_eq!(
-        king.can_move(5, 4, &board),
-        false,
+    assert!(
+        !king.can_move(5, 4, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
-        king.can_move(4, 3, &board),
-        false,
+    assert!(
+        !king.can_move(4, 3, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         4,
         3,
@@ -184,36 +171,32 @@ fn test_can_move_capture_opposing_color() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
 
-    assert_eq!(
+    assert!(
         king.can_move(4, 5, &board),
-        true,
         "Expected to not capture opposing color piece at {},{} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         king.can_move(3, 4, &board),
-        true,
         "Expected to not capture opposing color piece at {},{} from {},{}",
         3,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         king.can_move(5, 4, &board),
-        true,
         "Expected to not capture opposing color piece at {},{} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         king.can_move(4, 3, &board),
-        true,
         "Expected to not capture opposing color piece at {},{} from {},{}",
         4,
         3,
@@ -227,9 +210,8 @@ fn test_can_move_out_of_bounds_right() {
     let board = common::create_board_with_piece(7, 7, QuickPiece::PIECE(PieceColor::BLACK));
     let king = King::new(7, 7, PieceColor::BLACK);
 
-    assert_eq!(
-        false,
-        king.can_move(7, 8, &board),
+    assert!(
+        !king.can_move(7, 8, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         7,
         8,
@@ -243,9 +225,8 @@ fn test_can_move_out_of_bounds_up() {
     let board = common::create_board_with_piece(7, 7, QuickPiece::PIECE(PieceColor::BLACK));
     let king = King::new(7, 7, PieceColor::BLACK);
 
-    assert_eq!(
-        false,
-        king.can_move(8, 7, &board),
+    assert!(
+        !king.can_move(8, 7, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         8,
         7,
@@ -273,7 +254,7 @@ fn test_moves_on_board_4_4() {
     ];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = king.moves_on_board();
+    let mut actual_possible_moves = king.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -289,7 +270,7 @@ fn test_moves_on_board_0_0() {
     let mut expected_possible_moves: Vec<(usize, usize)> = vec![(0, 1), (1, 1), (1, 0)];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = king.moves_on_board();
+    let mut actual_possible_moves = king.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -305,7 +286,7 @@ fn test_moves_on_board_7_7() {
     let mut expected_possible_moves: Vec<(usize, usize)> = vec![(6, 7), (6, 6), (7, 6)];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = king.moves_on_board();
+    let mut actual_possible_moves = king.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -8,9 +8,8 @@ fn test_can_move_no_where() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
-        knight.can_move(4, 4, &board),
-        false,
+    assert!(
+        !knight.can_move(4, 4, &board),
         "Expected to not be able to move to {}, {} from {}, {}",
         4,
         4,
@@ -26,36 +25,32 @@ fn test_can_move_upper_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         knight.can_move(2, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         5,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(2, 6, &board),
-        false,
+    assert!(
+        !knight.can_move(2, 6, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         2,
         6,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(3, 5, &board),
-        false,
+    assert!(
+        !knight.can_move(3, 5, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         3,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         knight.can_move(3, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         6,
@@ -71,36 +66,32 @@ fn test_can_move_lower_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         knight.can_move(5, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         6,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         knight.can_move(6, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         5,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(6, 6, &board),
-        false,
+    assert!(
+        !knight.can_move(6, 6, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         6,
         6,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(5, 5, &board),
-        false,
+    assert!(
+        !knight.can_move(5, 5, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         5,
         5,
@@ -116,36 +107,32 @@ fn test_can_move_lower_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         knight.can_move(5, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         knight.can_move(6, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         3,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(5, 3, &board),
-        false,
+    assert!(
+        !knight.can_move(5, 3, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         5,
         3,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(6, 2, &board),
-        false,
+    assert!(
+        !knight.can_move(6, 2, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         6,
         2,
@@ -161,36 +148,32 @@ fn test_can_move_upper_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         knight.can_move(3, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         knight.can_move(2, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         3,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(3, 3, &board),
-        false,
+    assert!(
+        !knight.can_move(3, 3, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
-        knight.can_move(2, 2, &board),
-        false,
+    assert!(
+        !knight.can_move(2, 2, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         2,
         2,
@@ -206,9 +189,8 @@ fn test_can_move_capture_same_color() {
     let knight = Knight::new(4, 4, PieceColor::WHITE);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 6, &mut board);
-    assert_eq!(
-        knight.can_move(5, 6, &board),
-        false,
+    assert!(
+        !knight.can_move(5, 6, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         6,
@@ -225,9 +207,8 @@ fn test_can_move_capture_opposing_color() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 5, 6, &mut board);
 
-    assert_eq!(
+    assert!(
         knight.can_move(5, 6, &board),
-        true,
         "Expected to capture same opposing piece at {},{} from {},{}",
         5,
         6,
@@ -241,18 +222,16 @@ fn test_can_move_out_of_bounds_upper_right() {
     let board = common::create_board_with_piece(7, 7, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(7, 7, PieceColor::WHITE);
 
-    assert_eq!(
-        knight.can_move(5, 8, &board),
-        false,
+    assert!(
+        !knight.can_move(5, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         5,
         8,
         7,
         7
     );
-    assert_eq!(
-        knight.can_move(6, 9, &board),
-        false,
+    assert!(
+        !knight.can_move(6, 9, &board),
         "Expected not to not be able to move to {}, {} from {},{}",
         6,
         9,
@@ -266,18 +245,16 @@ fn test_can_move_out_of_bounds_lower_right() {
     let board = common::create_board_with_piece(7, 7, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(7, 7, PieceColor::WHITE);
 
-    assert_eq!(
-        knight.can_move(8, 9, &board),
-        false,
+    assert!(
+        !knight.can_move(8, 9, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         8,
         9,
         7,
         7
     );
-    assert_eq!(
-        knight.can_move(9, 8, &board),
-        false,
+    assert!(
+        !knight.can_move(9, 8, &board),
         "Expected not to not be able to move to {}, {} from {},{}",
         9,
         8,
@@ -291,18 +268,16 @@ fn test_can_move_out_of_bounds_lower_left() {
     let board = common::create_board_with_piece(7, 7, QuickPiece::PIECE(PieceColor::WHITE));
     let knight = Knight::new(7, 7, PieceColor::WHITE);
 
-    assert_eq!(
-        knight.can_move(9, 6, &board),
-        false,
+    assert!(
+        !knight.can_move(9, 6, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         9,
         6,
         7,
         7
     );
-    assert_eq!(
-        knight.can_move(8, 5, &board),
-        false,
+    assert!(
+        !knight.can_move(8, 5, &board),
         "Expected not to not be able to move to {}, {} from {},{}",
         8,
         5,
@@ -0,0 +1,60 @@
+use chess::board::Board;
+use chess::game;
+use chess::piece_types::{PieceColor, QuickPiece};
+
+#[test]
+fn test_starting_position_has_twenty_moves() {
+    let mut board = Board::new();
+    assert_eq!(game::legal_moves(&PieceColor::WHITE, &mut board).len(), 20);
+}
+
+#[test]
+fn test_legal_moves_from_knight() {
+    let mut board = Board::new();
+    let mut moves = game::legal_moves_from(1, 0, &mut board);
+    moves.sort();
+    assert_eq!(moves, vec![(0, 2), (2, 2)]);
+    assert!(game::legal_moves_from(4, 4, &mut board).is_empty());
+}
+
+#[test]
+fn test_play_move_from_coords() {
+    let mut board = Board::new();
+    assert!(board.play_move_from_coords((4, 1), (4, 3), None).is_ok());
+    assert!(board.play_move_from_coords((3, 6), (3, 4), None).is_ok());
+    assert!(board.play_move_from_coords((4, 3), (3, 4), None).is_ok());
+    assert_eq!(
+        board.position_board[3][4],
+        QuickPiece::PIECE(PieceColor::WHITE)
+    );
+    assert_eq!(board.live_black_pieces.len(), 15);
+}
+
+#[test]
+fn test_play_move_from_coords_wrong_color() {
+    let mut board = Board::new();
+    assert!(board.play_move_from_coords((4, 6), (4, 4), None).is_err());
+}
+
+#[test]
+fn test_play_move_from_coords_castle() {
+    let mut board = Board::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1").unwrap();
+    assert!(game::legal_moves_from(4, 0, &mut board).contains(&(6, 0)));
+    assert!(board.play_move_from_coords((4, 0), (6, 0), None).is_ok());
+    assert_eq!(board.white_king_position, (6, 0));
+    assert_eq!(
+        board.position_board[5][0],
+        QuickPiece::PIECE(PieceColor::WHITE)
+    );
+}
+
+#[test]
+fn test_last_move_squares() {
+    let mut board = Board::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1").unwrap();
+    assert_eq!(board.last_move_squares(), None);
+    assert!(board.play_move_from_coords((4, 0), (2, 0), None).is_ok());
+    // Castling shows the king move and not the rook move
+    assert_eq!(board.last_move_squares(), Some(((4, 0), (2, 0))));
+    assert!(board.play_move_from_coords((0, 6), (0, 5), None).is_ok());
+    assert_eq!(board.last_move_squares(), Some(((0, 6), (0, 5))));
+}
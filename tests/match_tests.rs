@@ -0,0 +1,233 @@
+#![cfg(unix)]
+
+mod common;
+
+use chess::clock::TimeControl;
+use chess::engine_match::{self, DrawRule, EngineMatch, MatchOptions, MatchScore, ResignRule};
+use chess::player::Player;
+use chess::sprt::Sprt;
+use chess::uci::SearchLimit;
+use common::{fake_engine, write_engine};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+const OPENING_EPD: &str = "7k/p7/8/8/8/8/P7/7K w - - id \"shuffle\";\n";
+
+// An engine that walks its king back and forth in the opening and always gives the same score
+fn shuffling_engine(name: &str, score: i32) -> PathBuf {
+    write_engine(
+        name,
+        "",
+        &format!(
+            "set -- $position; shift 8; [ \"$1\" = moves ] && shift
+      case $(($# % 2)).$(($# / 2 % 2)) in
+        0.0) move=h1g1 ;; 0.1) move=g1h1 ;; 1.0) move=h8g8 ;; 1.1) move=g8h8 ;;
+      esac
+      echo 'info depth 1 score cp {}'; echo \"bestmove $move\"",
+            score
+        ),
+    )
+}
+
+fn engine_player(path: &Path) -> Player {
+    Player::Uci {
+        path: path.to_str().unwrap().to_string(),
+        limit: Some(SearchLimit::Depth(1)),
+    }
+}
+
+fn openings_file(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("chess_match_{}.epd", name));
+    fs::write(&path, OPENING_EPD).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn shuffle_match(white: &Path, black: &Path, name: &str) -> MatchOptions {
+    MatchOptions {
+        engines: vec![engine_player(white), engine_player(black)],
+        games: 2,
+        openings: Some(openings_file(name)),
+        ..MatchOptions::default()
+    }
+}
+
+#[test]
+fn test_losing_engine_resigns() {
+    let winner = shuffling_engine("winner", 900);
+    let loser = shuffling_engine("loser", -900);
+    let mut options = shuffle_match(&winner, &loser, "resign");
+    options.settings.resign = Some(ResignRule {
+        score: 500,
+        moves: 2,
+    });
+    let pgn_path = std::env::temp_dir().join("chess_match_resign.pgn");
+    options.pgn = Some(pgn_path.to_str().unwrap().to_string());
+
+    let score = engine_match::run_match(&options).unwrap();
+    assert_eq!(
+        score,
+        MatchScore {
+            wins: 2,
+            losses: 0,
+            draws: 0
+        }
+    );
+    let pgn_text = fs::read_to_string(&pgn_path).unwrap();
+    let games = chess::pgn::parse_pgn_games(&pgn_text).unwrap();
+    assert_eq!(games.len(), 2);
+    // The colors swap for the second game of the opening
+    assert_eq!(games[0].tag("White"), Some("Fake winner"));
+    assert_eq!(games[0].result(), "1-0");
+    assert_eq!(games[1].tag("White"), Some("Fake loser"));
+    assert_eq!(games[1].result(), "0-1");
+    assert_eq!(games[1].tag("Termination"), Some("normal"));
+    assert_eq!(games[0].tag("FEN"), Some("7k/p7/8/8/8/8/P7/7K w - - 0 1"));
+    // The loser resigns on its second move
+    assert_eq!(games[0].moves.len(), 4);
+}
+
+#[test]
+fn test_sprt_stops_the_match() {
+    let winner = shuffling_engine("sprt_winner", 900);
+    let loser = shuffling_engine("sprt_loser", -900);
+    let mut options = shuffle_match(&winner, &loser, "sprt");
+    options.games = 20;
+    options.settings.resign = Some(ResignRule {
+        score: 500,
+        moves: 1,
+    });
+    options.sprt = Some(Sprt::from_string("elo0=0,elo1=5").unwrap());
+    // Two pairs won outright are past the bound
+    let score = engine_match::run_match(&options).unwrap();
+    assert_eq!(score.games(), 4);
+    assert_eq!(score.wins, 4);
+}
+
+#[test]
+fn test_repetition_is_a_draw() {
+    let first = shuffling_engine("level_1", 0);
+    let second = shuffling_engine("level_2", 0);
+    let mut engine_match = EngineMatch::start(
+        [engine_player(&first), engine_player(&second)],
+        engine_match::load_openings(&openings_file("repetition")).unwrap(),
+        Default::default(),
+    )
+    .unwrap();
+    assert_eq!(engine_match.play_next_game(), 0.5);
+    assert_eq!(engine_match.play_next_game(), 0.5);
+    assert_eq!(engine_match.results(), &[0.5, 0.5]);
+    let game = &engine_match.games()[0];
+    assert_eq!(game.tag("Termination"), Some("adjudication"));
+    assert_eq!(game.result(), "1/2-1/2");
+    assert!(engine_match
+        .summary()
+        .ends_with("0 - 0 - 2 [0.500] 2\nElo difference: 0.0 +/- 0.0"));
+}
+
+#[test]
+fn test_level_game_is_adjudicated_a_draw() {
+    let first = shuffling_engine("drawish_1", 5);
+    let second = shuffling_engine("drawish_2", -5);
+    let mut options = shuffle_match(&first, &second, "draw");
+    options.games = 1;
+    options.settings.draw = Some(DrawRule {
+        after_move: 0,
+        score: 10,
+        moves: 1,
+    });
+    let mut engine_match = EngineMatch::start(
+        [engine_player(&first), engine_player(&second)],
+        engine_match::load_openings(options.openings.as_ref().unwrap()).unwrap(),
+        options.settings,
+    )
+    .unwrap();
+    assert_eq!(engine_match.play_next_game(), 0.5);
+    assert_eq!(engine_match.games()[0].moves.len(), 2);
+}
+
+#[test]
+fn test_illegal_move_loses() {
+    let cheat = fake_engine("match_cheat", "h1h3");
+    let honest = shuffling_engine("honest", 0);
+    let score = engine_match::run_match(&shuffle_match(&cheat, &honest, "cheat")).unwrap();
+    assert_eq!(score.losses, 2);
+}
+
+#[test]
+fn test_hanging_engine_loses_on_time() {
+    // Never answers go
+    let hanging = write_engine("match_hanging", "", ":");
+    let honest = shuffling_engine("patient", 0);
+    let mut options = shuffle_match(&hanging, &honest, "hanging");
+    options.settings.time_control = Some(TimeControl::sudden_death(Duration::from_millis(200)));
+    let match_start = Instant::now();
+    let mut engine_match = EngineMatch::start(
+        [engine_player(&hanging), engine_player(&honest)],
+        engine_match::load_openings(options.openings.as_ref().unwrap()).unwrap(),
+        options.settings,
+    )
+    .unwrap();
+    assert_eq!(engine_match.play_next_game(), 0.0);
+    assert_eq!(engine_match.play_next_game(), 0.0);
+    assert!(match_start.elapsed() < Duration::from_secs(10));
+    for game in engine_match.games() {
+        assert_eq!(game.tag("Termination"), Some("time forfeit"));
+    }
+}
+
+#[test]
+fn test_built_in_engine_plays_a_match() {
+    let opponent = shuffling_engine("built_in_opponent", 0);
+    let mut options = shuffle_match(&opponent, &opponent, "built_in");
+    options.engines[0] = Player::from_string("engine:depth=1").unwrap();
+    let score = engine_match::run_match(&options).unwrap();
+    assert_eq!(score.games(), 2);
+    let engine_match = EngineMatch::start(
+        options.engines.clone().try_into().unwrap(),
+        Vec::new(),
+        options.settings,
+    )
+    .unwrap();
+    assert_eq!(
+        engine_match.names(),
+        ["Built-in engine", "Fake built_in_opponent"]
+    );
+}
+
+#[test]
+fn test_match_needs_two_engines() {
+    let options = MatchOptions {
+        engines: vec![Player::Human, Player::Human],
+        ..MatchOptions::default()
+    };
+    assert!(engine_match::run_match(&options).is_err());
+}
+
+#[test]
+fn test_match_command() {
+    let first = shuffling_engine("command_1", 0);
+    let second = shuffling_engine("command_2", 0);
+    let output = Command::new(env!("CARGO_BIN_EXE_chess"))
+        .args([
+            "match",
+            "--engine",
+            &format!("uci:{}:depth=1", first.display()),
+            "--engine",
+            &format!("uci:{}:depth=1", second.display()),
+            "--games",
+            "2",
+            "--openings",
+            &openings_file("command"),
+            "--draw-score",
+            "10",
+            "--draw-after",
+            "1",
+        ])
+        .output()
+        .unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(output.contains("Game 2: Fake command_2 vs Fake command_1: Drawn by adjudication"));
+    assert!(output.contains("Score of Fake command_1 vs Fake command_2: 0 - 0 - 2 [0.500] 2"));
+}
@@ -9,9 +9,8 @@ fn test_can_move_no_where() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let pawn = Pawn::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
-        pawn.can_move(4, 4, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 4, &board),
         "Expected to not be able to move to {}, {} from {}, {}",
         4,
         4,
@@ -25,18 +24,16 @@ fn test_can_move_white_forward() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let pawn = Pawn::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         pawn.can_move(4, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
-        pawn.can_move(4, 3, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 3, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         3,
@@ -50,18 +47,16 @@ fn test_can_move_black_forward() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::BLACK));
     let pawn = Pawn::new(4, 4, PieceColor::BLACK);
 
-    assert_eq!(
-        pawn.can_move(4, 5, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 5, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         pawn.can_move(4, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         3,
@@ -75,18 +70,16 @@ fn test_can_move_forward_white_right() {
     let mut board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let pawn = Pawn::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         pawn.can_move(5, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         5,
         4,
         4
     );
-    assert_eq!(
-        pawn.can_move(5, 3, &board),
-        false,
+    assert!(
+        !pawn.can_move(5, 3, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         5,
         3,
@@ -95,9 +88,8 @@ fn test_can_move_forward_white_right() {
     );
 
     common::insert_quick_piece_into_board(PIECE(PieceColor::BLACK), 5, 5, &mut board);
-    assert_eq!(
+    assert!(
         pawn.can_move(5, 5, &board),
-        true,
         "Expected to be able to take to {}, {} from {},{}",
         5,
         5,
@@ -111,18 +103,16 @@ fn test_can_move_forward_black_right() {
     let mut board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::BLACK));
     let pawn = Pawn::new(4, 4, PieceColor::BLACK);
 
-    assert_eq!(
-        pawn.can_move(3, 5, &board),
-        false,
+    assert!(
+        !pawn.can_move(3, 5, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         3,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         pawn.can_move(5, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         3,
@@ -130,9 +120,8 @@ fn test_can_move_forward_black_right() {
         4
     );
     common::insert_quick_piece_into_board(PIECE(PieceColor::WHITE), 5, 3, &mut board);
-    assert_eq!(
+    assert!(
         pawn.can_move(5, 3, &board),
-        true,
         "Expected to be able to take to {}, {} from {},{}",
         5,
         3,
@@ -146,9 +135,8 @@ fn test_can_move_out_of_bounds_white() {
     let board = common::create_board_with_piece(0, 7, QuickPiece::PIECE(PieceColor::WHITE));
     let pawn = Pawn::new(0, 7, PieceColor::WHITE);
 
-    assert_eq!(
-        pawn.can_move(0, 8, &board),
-        false,
+    assert!(
+        !pawn.can_move(0, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         0,
         8,
@@ -162,9 +150,8 @@ fn test_can_move_out_of_bounds_black() {
     let board = common::create_board_with_piece(7, 7, QuickPiece::PIECE(PieceColor::BLACK));
     let pawn = Pawn::new(7, 7, PieceColor::WHITE);
 
-    assert_eq!(
-        pawn.can_move(6, 8, &board),
-        false,
+    assert!(
+        !pawn.can_move(6, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         6,
         8,
@@ -178,18 +165,16 @@ fn test_can_move_forward_white_left() {
     let mut board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let pawn = Pawn::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         pawn.can_move(3, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         5,
         4,
         4
     );
-    assert_eq!(
-        pawn.can_move(3, 3, &board),
-        false,
+    assert!(
+        !pawn.can_move(3, 3, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         3,
         3,
@@ -198,9 +183,8 @@ fn test_can_move_forward_white_left() {
     );
 
     common::insert_quick_piece_into_board(PIECE(PieceColor::BLACK), 3, 5, &mut board);
-    assert_eq!(
+    assert!(
         pawn.can_move(3, 5, &board),
-        true,
         "Expected to be able to take to {}, {} from {},{}",
         3,
         5,
@@ -214,18 +198,16 @@ fn test_can_move_forward_black_left() {
     let mut board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::BLACK));
     let pawn = Pawn::new(4, 4, PieceColor::BLACK);
 
-    assert_eq!(
-        pawn.can_move(3, 5, &board),
-        false,
+    assert!(
+        !pawn.can_move(3, 5, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         3,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         pawn.can_move(3, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         3,
@@ -233,9 +215,8 @@ fn test_can_move_forward_black_left() {
         4
     );
     common::insert_quick_piece_into_board(PIECE(PieceColor::WHITE), 3, 3, &mut board);
-    assert_eq!(
+    assert!(
         pawn.can_move(3, 3, &board),
-        true,
         "Expected to be able to take to {}, {} from {},{}",
         3,
         3,
@@ -249,45 +230,40 @@ fn test_move_two_initial_black() {
     let mut board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::BLACK));
     let pawn = Pawn::new(4, 4, PieceColor::BLACK);
 
-    assert_eq!(
-        pawn.can_move(4, 5, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 5, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
-        pawn.can_move(4, 6, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 6, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         6,
         4,
         4
     );
-    assert_eq!(
-        pawn.can_move(4, 7, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 7, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         7,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         pawn.can_move(4, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         pawn.can_move(4, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         2,
@@ -295,9 +271,8 @@ fn test_move_two_initial_black() {
         4
     );
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 4, 3, &mut board);
-    assert_eq!(
-        pawn.can_move(4, 2, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 2, &board),
         "Expected to not be able to move to {}, {} from {},{} through black piece at {},{}",
         4,
         2,
@@ -307,9 +282,8 @@ fn test_move_two_initial_black() {
         3
     );
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
-    assert_eq!(
-        pawn.can_move(4, 2, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 2, &board),
         "Expected to not be able to move to {}, {} from {},{} through white piece at {},{}",
         4,
         2,
@@ -318,9 +292,8 @@ fn test_move_two_initial_black() {
         4,
         3
     );
-    assert_eq!(
-        pawn.can_move(4, 1, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 1, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         1,
@@ -334,18 +307,16 @@ fn test_move_two_initial_white() {
     let mut board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let pawn = Pawn::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         pawn.can_move(4, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         pawn.can_move(4, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         6,
@@ -353,9 +324,8 @@ fn test_move_two_initial_white() {
         4
     );
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 4, 5, &mut board);
-    assert_eq!(
-        pawn.can_move(4, 6, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 6, &board),
         "Expected to not be able to move to {}, {} from {},{} through black piece at {},{}",
         4,
         6,
@@ -365,9 +335,8 @@ fn test_move_two_initial_white() {
         5
     );
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 5, &mut board);
-    assert_eq!(
-        pawn.can_move(4, 6, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 6, &board),
         "Expected to not be able to move to {}, {} from {},{} through white piece at {},{}",
         4,
         6,
@@ -377,9 +346,8 @@ fn test_move_two_initial_white() {
         5
     );
 
-    assert_eq!(
-        pawn.can_move(4, 7, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 7, &board),
         "Expected to be able to move to {}, {} from {},{}",
         4,
         5,
@@ -387,27 +355,24 @@ fn test_move_two_initial_white() {
         4
     );
 
-    assert_eq!(
-        pawn.can_move(4, 3, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 3, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         3,
         4,
         4
     );
-    assert_eq!(
-        pawn.can_move(4, 2, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 2, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         2,
         4,
         4
     );
-    assert_eq!(
-        pawn.can_move(4, 1, &board),
-        false,
+    assert!(
+        !pawn.can_move(4, 1, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         4,
         1,
@@ -426,7 +391,7 @@ fn test_moves_on_board_white_start() {
     let mut expected_possible_moves: Vec<(usize, usize)> = vec![(3, 2), (4, 2), (5, 2), (4, 3)];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = pawn.moves_on_board();
+    let mut actual_possible_moves = pawn.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -441,7 +406,7 @@ fn test_moves_on_board_black_start() {
     let mut expected_possible_moves: Vec<(usize, usize)> = vec![(3, 5), (4, 5), (5, 5), (4, 4)];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = pawn.moves_on_board();
+    let mut actual_possible_moves = pawn.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -450,14 +415,14 @@ fn test_moves_on_board_black_start() {
 #[test]
 fn test_moves_on_board_black_has_moved() {
     let mut board = common::create_board_with_piece(4, 5, QuickPiece::PIECE(PieceColor::BLACK));
-    let pawn = Pawn::new(4, 5, PieceColor::BLACK);
+    let pawn = Pawn::with_starting_y(4, 5, PieceColor::BLACK, 6);
 
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 4, 5, &mut board);
 
     let mut expected_possible_moves: Vec<(usize, usize)> = vec![(3, 4), (4, 4), (5, 4)];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = pawn.moves_on_board();
+    let mut actual_possible_moves = pawn.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -466,15 +431,40 @@ fn test_moves_on_board_black_has_moved() {
 #[test]
 fn test_moves_on_board_white_has_moved() {
     let mut board = common::create_board_with_piece(4, 2, QuickPiece::PIECE(PieceColor::WHITE));
-    let pawn = Pawn::new(4, 2, PieceColor::WHITE);
+    let pawn = Pawn::with_starting_y(4, 2, PieceColor::WHITE, 1);
 
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 2, &mut board);
 
     let mut expected_possible_moves: Vec<(usize, usize)> = vec![(3, 3), (4, 3), (5, 3)];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = pawn.moves_on_board();
+    let mut actual_possible_moves = pawn.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
 }
+
+#[test]
+fn test_moves_on_board_first_rank_start() {
+    // Horde pawns start on the first rank and can still move two squares
+    let pawn = Pawn::new(4, 0, PieceColor::WHITE);
+
+    let mut expected_possible_moves: Vec<(usize, usize)> = vec![(3, 1), (4, 1), (5, 1), (4, 2)];
+
+    expected_possible_moves.sort();
+    let mut actual_possible_moves = pawn.moves_on_board(8, 8);
+    actual_possible_moves.sort();
+
+    assert_eq!(actual_possible_moves, expected_possible_moves);
+}
+
+#[test]
+fn test_moves_on_board_last_rank() {
+    // Nothing is left for a pawn that is already on its last rank
+    assert!(Pawn::new(4, 0, PieceColor::BLACK)
+        .moves_on_board(8, 8)
+        .is_empty());
+    assert!(Pawn::new(4, 7, PieceColor::WHITE)
+        .moves_on_board(8, 8)
+        .is_empty());
+}
@@ -0,0 +1,50 @@
+use chess::board::Board;
+use chess::game;
+
+// Known move counts for the standard test positions
+fn assert_perft(fen: &str, counts: &[u64]) {
+    let board = Board::from_fen(fen).unwrap();
+    for (depth, count) in counts.iter().enumerate() {
+        assert_eq!(
+            game::perft(&board, depth as u32 + 1),
+            *count,
+            "perft {} of {}",
+            depth + 1,
+            fen
+        );
+    }
+}
+
+#[test]
+fn test_perft_start_position() {
+    assert_perft(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &[20, 400, 8902],
+    );
+}
+
+#[test]
+fn test_perft_castling_and_pins() {
+    // Kiwipete
+    assert_perft(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2039],
+    );
+    // En passant that would leave the king open along the rank
+    assert_perft(
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        &[14, 191, 2812],
+    );
+}
+
+#[test]
+fn test_perft_promotions() {
+    assert_perft(
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        &[6, 264, 9467],
+    );
+    assert_perft(
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        &[44, 1486],
+    );
+}
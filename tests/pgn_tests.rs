@@ -0,0 +1,108 @@
+use chess::board::Board;
+use chess::clock::{Clock, TimeControl};
+use chess::game;
+use chess::pgn::{parse_pgn, PgnGame};
+use chess::piece_types::PieceColor;
+use std::time::Duration;
+
+#[test]
+fn test_to_fen_round_trip() {
+    assert_eq!(
+        Board::new().to_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+    );
+
+    let mut board = Board::new();
+    board.play_move_from_coords((4, 1), (4, 3), None).unwrap();
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+
+    let fen = "r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 0 1";
+    assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+}
+
+#[test]
+fn test_fen_move_counters() {
+    let mut board = Board::new();
+    for san in "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7".split_whitespace() {
+        let (start, end, promotion_piece) = game::resolve_move(&mut board, san).unwrap();
+        board
+            .play_move_from_coords(start, end, promotion_piece.as_deref())
+            .unwrap();
+    }
+    assert_eq!(
+        board.to_fen(),
+        "r1bqk2r/1pppbppp/p1n2n2/4p3/B3P3/5N2/PPPP1PPP/RNBQ1RK1 w kq - 4 6"
+    );
+
+    // A capture sets the clock back to 0
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/3r4/4K3 w - - 12 40").unwrap();
+    board.play_move_from_coords((4, 0), (3, 1), None).unwrap();
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/3K4/8 b - - 0 40");
+
+    let fen = "4k3/8/8/8/8/8/8/4K3 b - - 37 80";
+    assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+    assert!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - x 1").is_err());
+    assert!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 0").is_err());
+}
+
+#[test]
+fn test_moved_rook_loses_castling_rights() {
+    let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    board.play_move_from_coords((7, 0), (7, 1), None).unwrap();
+    board.play_move_from_coords((7, 7), (7, 6), None).unwrap();
+    board.play_move_from_coords((7, 1), (7, 0), None).unwrap();
+    assert_eq!(board.to_fen(), "r3k3/7r/8/8/8/8/8/R3K2R b Qq - 3 2");
+}
+
+#[test]
+fn test_save_and_load_game() {
+    let start_board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 1").unwrap();
+    let mut board = start_board.clone();
+    board.play_move_from_coords((4, 7), (3, 7), None).unwrap();
+    board.play_move_from_coords((4, 1), (4, 3), None).unwrap();
+
+    let mut clock = Clock::new(TimeControl::fischer(
+        Duration::from_secs(300),
+        Duration::from_secs(2),
+    ));
+    clock.start(PieceColor::BLACK);
+    clock.tick(Duration::from_secs(10));
+    clock.end_turn();
+    clock.tick(Duration::from_millis(4500));
+    clock.end_turn();
+
+    let pgn_game = PgnGame::from_board(&start_board, &board, "Alice", "Bob", Some(&clock));
+    assert_eq!(pgn_game.moves, vec!["Kd8", "e4"]);
+    let pgn_text = pgn_game.to_pgn();
+    assert!(pgn_text.starts_with("[Event \"?\"]"));
+    assert!(pgn_text.contains("[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 1\"]"));
+    assert!(pgn_text.contains("1... Kd8 2. e4 *"));
+
+    let loaded_game = parse_pgn(&pgn_text).unwrap();
+    assert_eq!(loaded_game, pgn_game);
+    assert_eq!(loaded_game.tag("White"), Some("Alice"));
+    let (loaded_board, resolved_moves) = loaded_game.replay().unwrap();
+    assert_eq!(loaded_board.to_fen(), board.to_fen());
+    assert_eq!(resolved_moves[1], ((4, 1), (4, 3), None));
+
+    let loaded_clock = loaded_game.restore_clock().unwrap().unwrap();
+    assert_eq!(
+        loaded_clock.remaining(&PieceColor::BLACK),
+        Duration::from_secs(292)
+    );
+    assert_eq!(
+        loaded_clock.remaining(&PieceColor::WHITE),
+        Duration::from_millis(297_500)
+    );
+    assert_eq!(loaded_clock.running(), Some(PieceColor::BLACK));
+    assert_eq!(loaded_clock.moves_made(&PieceColor::WHITE), 1);
+}
+
+#[test]
+fn test_bad_move_in_pgn() {
+    let pgn_game = parse_pgn("1. e4 e5 2. Ke3 *").unwrap();
+    assert!(pgn_game.replay().is_err());
+}
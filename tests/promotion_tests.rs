@@ -0,0 +1,55 @@
+use chess::board::Board;
+use chess::game;
+use chess::pieces::AnyPiece;
+use chess::pieces::PieceMove;
+
+fn piece_at(pieces: &[AnyPiece], position: (usize, usize)) -> Option<&AnyPiece> {
+    pieces.iter().find(|piece| piece.get_pos() == position)
+}
+
+#[test]
+fn test_white_promotes_to_knight() {
+    let mut board = Board::from_fen("8/4P3/8/8/8/8/8/k6K w - - 0 1").unwrap();
+    assert!(game::player_move(&mut board, "e8=N").is_ok());
+    assert!(matches!(
+        piece_at(&board.live_white_pieces, (4, 7)),
+        Some(AnyPiece::Knight(_))
+    ));
+}
+
+#[test]
+fn test_black_promotes_on_first_rank() {
+    let mut board = Board::from_fen("k6K/8/8/8/8/8/3p4/8 b - - 0 1").unwrap();
+    assert!(game::player_move(&mut board, "d1=R").is_ok());
+    assert!(matches!(
+        piece_at(&board.live_black_pieces, (3, 0)),
+        Some(AnyPiece::Rook(_))
+    ));
+}
+
+#[test]
+fn test_promotion_without_piece_is_an_error() {
+    let mut board = Board::from_fen("8/4P3/8/8/8/8/8/k6K w - - 0 1").unwrap();
+    assert!(game::player_move(&mut board, "e8").is_err());
+    assert!(board.play_move_from_coords((4, 6), (4, 7), None).is_err());
+    // Nothing should have changed
+    assert!(matches!(
+        piece_at(&board.live_white_pieces, (4, 6)),
+        Some(AnyPiece::Pawn(_))
+    ));
+}
+
+#[test]
+fn test_invalid_promotion_piece_is_an_error() {
+    let mut board = Board::from_fen("8/4P3/8/8/8/8/8/k6K w - - 0 1").unwrap();
+    assert!(board
+        .play_move_from_coords((4, 6), (4, 7), Some("K"))
+        .is_err());
+    assert!(matches!(
+        piece_at(&board.live_white_pieces, (4, 6)),
+        Some(AnyPiece::Pawn(_))
+    ));
+    assert!(board
+        .play_move_from_coords((4, 6), (4, 7), Some("Q"))
+        .is_ok());
+}
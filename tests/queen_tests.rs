@@ -8,9 +8,8 @@ fn test_can_move_no_where() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
-        queen.can_move(4, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 4, &board),
         "Expected to not be able to move to {}, {} from {}, {}",
         4,
         4,
@@ -26,36 +25,32 @@ fn test_can_move_lower_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(3, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(2, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         6,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(1, 7, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         1,
         7,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(0, 8, &board),
-        false,
+    assert!(
+        !queen.can_move(0, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         0,
         8,
@@ -70,36 +65,32 @@ fn test_can_move_upper_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(5, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(6, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         6,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(7, 7, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         7,
         7,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(8, 8, &board),
-        false,
+    assert!(
+        !queen.can_move(8, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         8,
         8,
@@ -115,36 +106,32 @@ fn test_can_move_upper_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(5, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(6, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(7, 1, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         7,
         1,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(8, 0, &board),
-        false,
+    assert!(
+        !queen.can_move(8, 0, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         8,
         0,
@@ -160,36 +147,32 @@ fn test_can_move_lower_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(3, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(2, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(1, 1, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         1,
         1,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(0, 0, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         0,
         0,
@@ -209,27 +192,23 @@ fn test_can_move_through_piece() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
 
-    assert_eq!(
-        queen.can_move(4, 6, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 6, &board),
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",4,5,4,6,4,4
     );
-    assert_eq!(
-        queen.can_move(2, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(2, 4, &board),
 
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",3,4,2,4,4,4
     );
-    assert_eq!(
-        queen.can_move(6, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(6, 4, &board),
 
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",5,4,6,4,4,4
 
     );
-    assert_eq!(
-        queen.can_move(4, 2, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 2, &board),
 
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",4,3,4,2,4,4
 
@@ -240,29 +219,25 @@ fn test_can_move_through_piece() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 4, 3, &mut board);
 
-    assert_eq!(
-        queen.can_move(4, 6, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 6, &board),
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",4,5,4,6,4,4
 
     );
-    assert_eq!(
-        queen.can_move(2, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(2, 4, &board),
 
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",3,4,4,6,4,4
 
     );
-    assert_eq!(
-        queen.can_move(6, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(6, 4, &board),
 
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",5,4,6,4,4,4
 
     );
-    assert_eq!(
-        queen.can_move(4, 2, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 2, &board),
 
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",4,3,4,2,4,4
 
@@ -279,36 +254,32 @@ fn test_can_move_through_piece_diag() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 3, 5, &mut board);
 
-    assert_eq!(
-        queen.can_move(6, 6, &board),
-        false,
+    assert!(
+        !queen.can_move(6, 6, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         6,
         6,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(2, 2, &board),
-        false,
+    assert!(
+        !queen.can_move(2, 2, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(6, 2, &board),
-        false,
+    assert!(
+        !queen.can_move(6, 2, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         6,
         2,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(2, 6, &board),
-        false,
+    assert!(
+        !queen.can_move(2, 6, &board),
         "Expected to not be able to move through some color to {}, {} from {},{}",
         2,
         6,
@@ -321,36 +292,32 @@ fn test_can_move_through_piece_diag() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 5, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 3, 5, &mut board);
 
-    assert_eq!(
-        queen.can_move(6, 6, &board),
-        false,
+    assert!(
+        !queen.can_move(6, 6, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         6,
         6,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(2, 2, &board),
-        false,
+    assert!(
+        !queen.can_move(2, 2, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(6, 2, &board),
-        false,
+    assert!(
+        !queen.can_move(6, 2, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         6,
         2,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(2, 6, &board),
-        false,
+    assert!(
+        !queen.can_move(2, 6, &board),
         "Expected to not be able to move through opposing color to {}, {} from {},{}",
         2,
         6,
@@ -369,36 +336,32 @@ fn test_can_move_capture_same_color_diag() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 3, 5, &mut board);
 
-    assert_eq!(
-        queen.can_move(5, 5, &board),
-        false,
+    assert!(
+        !queen.can_move(5, 5, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         5,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(3, 3, &board),
-        false,
+    assert!(
+        !queen.can_move(3, 3, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(5, 3, &board),
-        false,
+    assert!(
+        !queen.can_move(5, 3, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         3,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(3, 5, &board),
-        false,
+    assert!(
+        !queen.can_move(3, 5, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         3,
         5,
@@ -417,36 +380,32 @@ fn test_can_move_capture_same_color_cross() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
 
-    assert_eq!(
-        queen.can_move(4, 5, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 5, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(3, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(3, 4, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         3,
         4,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(5, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(5, 4, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(4, 3, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 3, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         4,
         3,
@@ -465,36 +424,32 @@ fn test_can_move_capture_opposing_color_cross() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 4, 3, &mut board);
 
-    assert_eq!(
+    assert!(
         queen.can_move(4, 5, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(3, 4, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         3,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(5, 4, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(4, 3, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         4,
         3,
@@ -513,36 +468,32 @@ fn test_can_move_capture_opposing_color_diag() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 3, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 3, 5, &mut board);
 
-    assert_eq!(
+    assert!(
         queen.can_move(5, 5, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         5,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(3, 3, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(5, 3, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         5,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(3, 5, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         3,
         5,
@@ -558,36 +509,32 @@ fn test_can_move_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(4, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(4, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         6,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(4, 7, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         7,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(4, 8, &board),
-        false,
+    assert!(
+        !queen.can_move(4, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         0,
         8,
@@ -602,36 +549,32 @@ fn test_can_move_down() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(5, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(6, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(7, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         7,
         4,
         4,
         4
     );
-    assert_eq!(
-        queen.can_move(8, 4, &board),
-        false,
+    assert!(
+        !queen.can_move(8, 4, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         8,
         4,
@@ -647,36 +590,32 @@ fn test_can_move_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(4, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(4, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(4, 1, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         1,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(4, 0, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         0,
@@ -692,36 +631,32 @@ fn test_can_move_up() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let queen = Queen::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         queen.can_move(3, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(2, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(1, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         1,
         1,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         queen.can_move(0, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         0,
         4,
@@ -768,7 +703,7 @@ fn test_moves_on_board_4_4() {
         (4, 7),
     ]);
     expected_possible_moves.sort();
-    let mut actual_possible_moves = queen.moves_on_board();
+    let mut actual_possible_moves = queen.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -798,7 +733,7 @@ fn test_moves_on_board_0_0() {
         (7, 0),
     ]);
     expected_possible_moves.sort();
-    let mut actual_possible_moves = queen.moves_on_board();
+    let mut actual_possible_moves = queen.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -829,7 +764,7 @@ fn test_moves_on_board_7_7() {
         (7, 6),
     ]);
     expected_possible_moves.sort();
-    let mut actual_possible_moves = queen.moves_on_board();
+    let mut actual_possible_moves = queen.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
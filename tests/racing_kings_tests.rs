@@ -0,0 +1,75 @@
+use chess::board::Board;
+use chess::chess_game::Game;
+use chess::controller::Termination;
+use chess::game;
+use chess::parser::GameResult;
+use chess::player::Players;
+use chess::variant::Variant;
+
+fn racing_game(fen: &str) -> Game {
+    let mut board = Board::from_fen(fen).unwrap();
+    board.set_variant(Variant::RacingKings);
+    Game::from_board(board, Players::default(), None)
+}
+
+fn play(game: &mut Game, move_string: &str) -> bool {
+    let (start, end, promotion_piece) = game.resolve_move(move_string).unwrap();
+    game.submit_move(start, end, promotion_piece.as_deref())
+        .is_ok()
+}
+
+#[test]
+fn test_racing_kings_start() {
+    let board = Board::new_variant(Variant::RacingKings);
+    assert_eq!(board.to_fen(), "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1");
+}
+
+#[test]
+fn test_no_checks() {
+    let mut game = racing_game("8/8/8/8/8/8/k7/6RK w - - 0 1");
+    // Both rook moves would check the king
+    assert!(!play(&mut game, "Ra1"));
+    assert!(!play(&mut game, "Rg2"));
+    assert!(play(&mut game, "Rg3"));
+    // and the king can't walk into one
+    assert!(!play(&mut game, "Ka3"));
+    assert!(play(&mut game, "Kb1"));
+}
+
+#[test]
+fn test_first_to_the_eighth_rank() {
+    let mut game = racing_game("8/6K1/8/8/8/8/k7/8 w - - 0 1");
+    assert!(play(&mut game, "Kg8"));
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::WhiteWin);
+    assert_eq!(outcome.termination, Termination::EighthRank);
+    assert_eq!(
+        outcome.to_string(),
+        "White's king reaches the eighth rank. 1-0"
+    );
+}
+
+#[test]
+fn test_black_can_draw_level() {
+    let mut game = racing_game("8/k5K1/8/8/8/8/8/8 w - - 0 1");
+    assert!(play(&mut game, "Kg8"));
+    assert!(!game.is_over());
+    assert!(play(&mut game, "Ka8"));
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::Draw);
+    assert_eq!(outcome.termination, Termination::EighthRankTie);
+
+    // Moving anywhere else loses
+    let mut game = racing_game("8/k5K1/8/8/8/8/8/8 w - - 0 1");
+    assert!(play(&mut game, "Kg8"));
+    assert!(play(&mut game, "Ka6"));
+    assert_eq!(game.outcome().unwrap().termination, Termination::EighthRank);
+}
+
+#[test]
+fn test_racing_kings_perft() {
+    let board = Board::new_variant(Variant::RacingKings);
+    for (depth, count) in [21, 421, 11264].into_iter().enumerate() {
+        assert_eq!(game::perft(&board, depth as u32 + 1), count);
+    }
+}
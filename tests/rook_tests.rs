@@ -8,9 +8,8 @@ fn test_can_move_no_where() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let rook = Rook::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
-        rook.can_move(4, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 4, &board),
         "Expected to not be able to move to {}, {} from {}, {}",
         4,
         4,
@@ -26,36 +25,32 @@ fn test_can_move_right() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let rook = Rook::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         rook.can_move(4, 5, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(4, 6, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         6,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(4, 7, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         7,
         4,
         4
     );
-    assert_eq!(
-        rook.can_move(4, 8, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 8, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         0,
         8,
@@ -70,36 +65,32 @@ fn test_can_move_down() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let rook = Rook::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         rook.can_move(5, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(6, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         6,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(7, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         7,
         4,
         4,
         4
     );
-    assert_eq!(
-        rook.can_move(8, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(8, 4, &board),
         "Expected to not be able to move to {}, {} from {},{}",
         8,
         4,
@@ -115,36 +106,32 @@ fn test_can_move_left() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let rook = Rook::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         rook.can_move(4, 3, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(4, 2, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(4, 1, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         1,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(4, 0, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         4,
         0,
@@ -160,36 +147,32 @@ fn test_can_move_up() {
     let board = common::create_board_with_piece(4, 4, QuickPiece::PIECE(PieceColor::WHITE));
     let rook = Rook::new(4, 4, PieceColor::WHITE);
 
-    assert_eq!(
+    assert!(
         rook.can_move(3, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         3,
         3,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(2, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         2,
         2,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(1, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         1,
         1,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(0, 4, &board),
-        true,
         "Expected to be able to move to {}, {} from {},{}",
         0,
         4,
@@ -208,27 +191,23 @@ fn test_can_move_through_piece() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
 
-    assert_eq!(
-        rook.can_move(4, 6, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 6, &board),
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",4,5,4,6,4,4
     );
-    assert_eq!(
-        rook.can_move(2, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(2, 4, &board),
 
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",3,4,2,4,4,4
    );
-    assert_eq!(
-        rook.can_move(6, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(6, 4, &board),
 
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",5,4,6,4,4,4
 
     );
-    assert_eq!(
-        rook.can_move(4, 2, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 2, &board),
 
         "Expected to not be able to move through same color piece at {},{}, to space {},{} from {},{}",4,3,4,2,4,4
 
@@ -239,29 +218,25 @@ fn test_can_move_through_piece() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::BLACK), 4, 3, &mut board);
 
-    assert_eq!(
-        rook.can_move(4, 6, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 6, &board),
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",4,5,4,6,4,4
 
     );
-    assert_eq!(
-        rook.can_move(2, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(2, 4, &board),
 
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",3,4,4,6,4,4
 
     );
-    assert_eq!(
-        rook.can_move(6, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(6, 4, &board),
 
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",5,4,6,4,4,4
 
     );
-    assert_eq!(
-        rook.can_move(4, 2, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 2, &board),
 
         "Expected to not be able to move through opposing color piece at {},{}, to space {},{} from {},{}",4,3,4,2,4,4
 
@@ -278,36 +253,32 @@ fn test_can_move_capture_same_color() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
 
-    assert_eq!(
-        rook.can_move(4, 5, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 5, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
-        rook.can_move(3, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(3, 4, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         3,
         4,
         4,
         4
     );
-    assert_eq!(
-        rook.can_move(5, 4, &board),
-        false,
+    assert!(
+        !rook.can_move(5, 4, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
-        rook.can_move(4, 3, &board),
-        false,
+    assert!(
+        !rook.can_move(4, 3, &board),
         "Expected to not capture same color piece at {},{} from {},{}",
         4,
         3,
@@ -326,36 +297,32 @@ fn test_can_move_capture_opposing_color() {
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 5, 4, &mut board);
     common::insert_quick_piece_into_board(QuickPiece::PIECE(PieceColor::WHITE), 4, 3, &mut board);
 
-    assert_eq!(
+    assert!(
         rook.can_move(4, 5, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         4,
         5,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(3, 4, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         3,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(5, 4, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         5,
         4,
         4,
         4
     );
-    assert_eq!(
+    assert!(
         rook.can_move(4, 3, &board),
-        true,
         "Expected to capture opposing color piece at {},{} from {},{}",
         4,
         3,
@@ -389,7 +356,7 @@ fn moves_on_board_4_4() {
     ];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = rook.moves_on_board();
+    let mut actual_possible_moves = rook.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -420,7 +387,7 @@ fn moves_on_board_0_0() {
     ];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = rook.moves_on_board();
+    let mut actual_possible_moves = rook.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -450,7 +417,7 @@ fn moves_on_board_7_7() {
     ];
 
     expected_possible_moves.sort();
-    let mut actual_possible_moves = rook.moves_on_board();
+    let mut actual_possible_moves = rook.moves_on_board(8, 8);
     actual_possible_moves.sort();
 
     assert_eq!(actual_possible_moves, expected_possible_moves);
@@ -0,0 +1,108 @@
+#![cfg(unix)]
+
+mod common;
+
+use chess::board::Board;
+use chess::game;
+use chess::uci::{SearchLimit, UciEngine};
+use chess::variant::Variant;
+use common::{fake_engine, log_path, write_engine};
+use std::fs;
+use std::time::Duration;
+
+#[test]
+fn test_engine_plays_its_move() {
+    let path = fake_engine("e5", "e7e5");
+    let mut engine = UciEngine::start(path.to_str().unwrap()).unwrap();
+    assert_eq!(engine.name(), "Fake e5");
+
+    let mut board = Board::new();
+    assert!(game::player_move(&mut board, "e4").is_ok());
+    let san = engine
+        .play_best_move(
+            &Board::new(),
+            &mut board,
+            &SearchLimit::MoveTime(Duration::from_millis(10)),
+        )
+        .unwrap();
+    assert_eq!(san, "e5");
+    assert_eq!(board.last_move_squares(), Some(((4, 6), (4, 4))));
+}
+
+#[test]
+fn test_engine_illegal_move_is_an_error() {
+    let path = fake_engine("illegal", "e2e5");
+    let mut engine = UciEngine::start(path.to_str().unwrap()).unwrap();
+    let mut board = Board::new();
+    assert!(engine
+        .play_best_move(&Board::new(), &mut board, &SearchLimit::Depth(1))
+        .is_err());
+}
+
+#[test]
+fn test_missing_engine_is_an_error() {
+    assert!(UciEngine::start("/this/engine/does/not/exist").is_err());
+}
+
+#[test]
+fn test_engine_is_sent_the_start_fen() {
+    let path = fake_engine("start_fen", "e8d8");
+    let mut engine = UciEngine::start(path.to_str().unwrap()).unwrap();
+    let start_board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 3 20").unwrap();
+    let mut board = start_board.clone();
+    assert!(game::player_move(&mut board, "e4").is_ok());
+    let san = engine
+        .play_best_move(&start_board, &mut board, &SearchLimit::Depth(1))
+        .unwrap();
+    assert_eq!(san, "Kd8");
+    drop(engine);
+    let log = fs::read_to_string(log_path(&path)).unwrap();
+    assert!(log.contains("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 3 20 moves e2e4\n"));
+    assert!(!log.contains("UCI_Variant"));
+}
+
+#[test]
+fn test_engine_is_told_the_variant() {
+    let path = write_engine(
+        "variants",
+        "echo 'option name UCI_Variant type combo default chess var chess var atomic'; ",
+        "echo 'info depth 1 score cp 0'; echo 'bestmove e7e5'",
+    );
+    let mut engine = UciEngine::start(path.to_str().unwrap()).unwrap();
+    let start_board = Board::new_variant(Variant::Atomic);
+    let mut board = start_board.clone();
+    assert!(game::player_move(&mut board, "e4").is_ok());
+    engine
+        .play_best_move(&start_board, &mut board, &SearchLimit::Depth(1))
+        .unwrap();
+    drop(engine);
+    let log = fs::read_to_string(log_path(&path)).unwrap();
+    assert!(log.contains("setoption name UCI_Variant value atomic\n"));
+    assert!(log.contains(
+        "position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 moves e2e4\n"
+    ));
+
+    // It doesn't list Horde
+    let engine = UciEngine::start(path.to_str().unwrap()).unwrap();
+    assert!(engine
+        .can_play(&Board::new_variant(Variant::Atomic))
+        .is_ok());
+    assert!(engine
+        .can_play(&Board::new_variant(Variant::Horde))
+        .is_err());
+}
+
+#[test]
+fn test_engine_refuses_what_it_cant_be_told() {
+    let path = fake_engine("chess_only", "e7e5");
+    let mut engine = UciEngine::start(path.to_str().unwrap()).unwrap();
+    assert!(engine.can_play(&Board::new()).is_ok());
+
+    let crazyhouse = Board::new_variant(Variant::Crazyhouse);
+    assert!(engine.can_play(&crazyhouse).is_err());
+    assert!(engine
+        .best_move(&crazyhouse, &crazyhouse, &SearchLimit::Depth(1))
+        .is_err());
+    let big_board = Board::from_fen("4k5/10/10/10/10/10/10/4K5 w - - 0 1").unwrap();
+    assert!(engine.can_play(&big_board).is_err());
+}
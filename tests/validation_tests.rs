@@ -0,0 +1,133 @@
+mod common;
+use chess::board::Board;
+use chess::piece_types::{PieceColor, QuickPiece};
+use chess::pieces::king::King;
+use chess::pieces::knight::Knight;
+use chess::pieces::AnyPiece;
+use chess::variant::Variant;
+
+fn errors(board: &Board) -> Vec<String> {
+    match board.validate() {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors.iter().map(|error| error.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_valid_positions() {
+    assert!(Board::new().validate().is_ok());
+    for variant in [Variant::Horde, Variant::RacingKings, Variant::Antichess] {
+        assert!(Board::new_variant(variant).validate().is_ok());
+    }
+    // Three queens is fine after two promotions
+    let board = Board::from_fen("4k3/8/8/8/8/8/2PPPP2/QQQ1K3 w - - 0 1").unwrap();
+    assert!(board.validate().is_ok());
+    // The side on move can be in check
+    let board = Board::from_fen("4k3/4R3/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    assert!(board.validate().is_ok());
+}
+
+#[test]
+fn test_every_error_is_reported() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/P3K2K w - - 0 1").unwrap();
+    assert_eq!(
+        errors(&board),
+        ["White can't have 2 kings", "There can't be a pawn on a1"]
+    );
+
+    let board = Board::from_fen("4k3/8/8/8/8/8/PPPPPPPP/QQ2K3 w - - 0 1").unwrap();
+    assert_eq!(
+        errors(&board),
+        ["White has 8 pawns and 1 promoted pieces but only starts with 8 pawns"]
+    );
+
+    let board = Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+    assert_eq!(
+        errors(&board),
+        ["White can't have 0 kings", "Black can't have 0 kings"]
+    );
+}
+
+#[test]
+fn test_side_not_on_move_in_check() {
+    let board = Board::from_fen("4k3/4R3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        errors(&board),
+        ["Black is in check but it is White to move"]
+    );
+
+    // Both kings in check is the same mistake
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4R1Kr w - - 0 1").unwrap();
+    assert_eq!(
+        errors(&board),
+        ["Black is in check but it is White to move"]
+    );
+}
+
+#[test]
+fn test_extra_kings_are_not_promoted_pieces() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/PPPPPPPP/3KK3 w - - 0 1").unwrap();
+    assert_eq!(errors(&board), ["White can't have 2 kings"]);
+
+    // An Antichess pawn can promote to a king so there it is one
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/PPPPPPPP/3KK3 w - - 0 1").unwrap();
+    board.set_variant(Variant::Antichess);
+    assert_eq!(
+        errors(&board),
+        ["White has 8 pawns and 1 promoted pieces but only starts with 8 pawns"]
+    );
+}
+
+#[test]
+fn test_board_and_pieces_disagree() {
+    let mut board = Board::new();
+    // A knight the board doesn't know about and a square the pieces don't know about
+    board
+        .live_white_pieces
+        .push(AnyPiece::Knight(Knight::new(4, 3, PieceColor::WHITE)));
+    board.position_board[3][4] = QuickPiece::PIECE(PieceColor::BLACK);
+    board.white_king_position = (3, 0);
+    assert_eq!(
+        errors(&board),
+        [
+            "There is a white knight on e4 but the board has nothing there",
+            "The board has a black piece on d5 but no piece is there",
+            "The white king is on e1 but the board has it on d1",
+            "White has 8 pawns and 1 promoted pieces but only starts with 8 pawns"
+        ]
+    );
+}
+
+#[test]
+fn test_hand_built_boards() {
+    // Putting a king on top of the d1 queen leaves the queen in the white pieces
+    let mut board = Board::new();
+    common::insert_piece_into_board(
+        AnyPiece::King(King::new(3, 0, PieceColor::WHITE)),
+        &PieceColor::WHITE,
+        3,
+        0,
+        &mut board,
+    );
+    assert_eq!(
+        errors(&board),
+        [
+            "There is a white queen on d1 but the board has a white king there",
+            "2 pieces are on d1",
+            "White can't have 2 kings"
+        ]
+    );
+
+    // Only the quick board gets the piece
+    let mut board = Board::new();
+    common::insert_quick_piece_into_board(
+        QuickPiece::PIECE(PieceColor::BLACK),
+        4,
+        3,
+        &mut board.position_board,
+    );
+    assert_eq!(
+        errors(&board),
+        ["The board has a black piece on e4 but no piece is there"]
+    );
+}
@@ -0,0 +1,102 @@
+use chess::board::Board;
+use chess::chess_game::Game;
+use chess::controller::Termination;
+use chess::parser::GameResult;
+use chess::pgn::parse_pgn;
+use chess::piece_types::PieceColor;
+use chess::player::Players;
+use chess::variant::Variant;
+
+fn variant_game(variant: Variant) -> Game {
+    Game::from_board(Board::new_variant(variant), Players::default(), None)
+}
+
+fn play(game: &mut Game, move_string: &str) {
+    let (start, end, promotion_piece) = game.resolve_move(move_string).unwrap();
+    game.submit_move(start, end, promotion_piece.as_deref())
+        .unwrap();
+}
+
+#[test]
+fn test_three_checks_win() {
+    let mut game = variant_game(Variant::ThreeCheck);
+    for move_string in ["e4", "e5", "Bc4", "Nc6", "Bxf7+", "Kxf7", "Qh5+", "Ke7"] {
+        play(&mut game, move_string);
+    }
+    assert_eq!(game.board().checks_given(&PieceColor::WHITE), 2);
+    assert_eq!(
+        game.board().to_fen(),
+        "r1bq1bnr/ppppk1pp/2n5/4p2Q/4P3/8/PPPP1PPP/RNB1K1NR w KQ - 2 5 +2+0"
+    );
+    assert!(!game.is_over());
+
+    play(&mut game, "Qxe5+");
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::WhiteWin);
+    assert_eq!(outcome.termination, Termination::ThreeChecks);
+    assert_eq!(
+        outcome.to_string(),
+        "White wins by giving three checks. 1-0"
+    );
+}
+
+#[test]
+fn test_three_check_fen() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +1+2").unwrap();
+    assert_eq!(board.variant, Variant::ThreeCheck);
+    assert_eq!(board.checks_given(&PieceColor::BLACK), 2);
+    assert!(board.to_fen().ends_with(" +1+2"));
+    assert!(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +1").is_err());
+
+    let mut game = Game::from_board(board, Players::default(), None);
+    play(&mut game, "Ra8+");
+    assert!(!game.is_over());
+    play(&mut game, "Kd7");
+    play(&mut game, "Ra7+");
+    assert_eq!(
+        game.outcome().unwrap().termination,
+        Termination::ThreeChecks
+    );
+}
+
+#[test]
+fn test_king_of_the_hill() {
+    let mut game = variant_game(Variant::KingOfTheHill);
+    for move_string in ["e3", "a6", "Ke2", "a5", "Kd3", "a4"] {
+        play(&mut game, move_string);
+    }
+    assert!(!game.is_over());
+    play(&mut game, "Kd4");
+    let outcome = game.outcome().unwrap();
+    assert_eq!(outcome.result, GameResult::WhiteWin);
+    assert_eq!(outcome.termination, Termination::KingOfTheHill);
+    assert_eq!(outcome.to_string(), "White's king reaches the hill. 1-0");
+
+    // The same moves in a standard game go on
+    let mut game = Game::default();
+    for move_string in ["e3", "a6", "Ke2", "a5", "Kd3", "a4", "Kd4"] {
+        play(&mut game, move_string);
+    }
+    assert!(!game.is_over());
+}
+
+#[test]
+fn test_variant_pgn_round_trip() {
+    let mut game = variant_game(Variant::KingOfTheHill);
+    for move_string in ["e4", "d5"] {
+        play(&mut game, move_string);
+    }
+    let pgn_text = game.to_pgn("?", "?").to_pgn();
+    assert!(pgn_text.contains("[Variant \"King of the Hill\"]"));
+    assert!(!pgn_text.contains("[FEN"));
+    let loaded = Game::from_pgn(&parse_pgn(&pgn_text).unwrap(), Players::default()).unwrap();
+    assert_eq!(loaded.board().variant, Variant::KingOfTheHill);
+
+    // A set up Three-check position keeps its checks
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +1+2").unwrap();
+    let game = Game::from_board(board, Players::default(), None);
+    let pgn_text = game.to_pgn("?", "?").to_pgn();
+    assert!(pgn_text.contains("[Variant \"Three-check\"]"));
+    let loaded = Game::from_pgn(&parse_pgn(&pgn_text).unwrap(), Players::default()).unwrap();
+    assert_eq!(loaded.board().checks_given(&PieceColor::BLACK), 2);
+}
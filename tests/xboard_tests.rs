@@ -0,0 +1,40 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Runs chess xboard with the commands on its input and returns what it printed
+fn run_xboard(commands: &[&str]) -> String {
+    let mut process = Command::new(env!("CARGO_BIN_EXE_chess"))
+        .arg("xboard")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut input = process.stdin.take().unwrap();
+    for command in commands {
+        writeln!(input, "{}", command).unwrap();
+    }
+    drop(input);
+    let output = process.wait_with_output().unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_xboard_game() {
+    let output = run_xboard(&[
+        "xboard",
+        "protover 2",
+        "new",
+        "sd 1",
+        "usermove e2e4",
+        "force",
+        "setboard 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+        "sd 2",
+        "go",
+        "quit",
+        "ping 1",
+    ]);
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines[0].starts_with("feature ") && lines[0].ends_with("done=1"));
+    assert!(lines[1].starts_with("move "));
+    assert_eq!(&lines[2..], ["move a1a8", "1-0 {White wins by checkmate}"]);
+}